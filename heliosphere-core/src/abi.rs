@@ -0,0 +1,608 @@
+//! Contract ABI encoding and decoding with Tron `Address` as `address` type
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use alloy_primitives::{I256, U256};
+use core::{
+    fmt::{Display, Write},
+    str::FromStr,
+};
+
+use crate::{error::Error, Address};
+
+/// ABI type
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AbiType {
+    /// `address` (20 bytes, 0x41 prefix is added on decoding)
+    Address,
+    /// `uintN` (`trcToken` is `uint256`)
+    Uint(usize),
+    /// `intN`
+    Int(usize),
+    /// `bool`
+    Bool,
+    /// `string`
+    String,
+    /// `bytes`
+    Bytes,
+    /// `bytesN`
+    FixedBytes(usize),
+    /// `T[]`
+    Array(Box<AbiType>),
+    /// `T[N]`
+    FixedArray(Box<AbiType>, usize),
+    /// `(T1,T2,...)`
+    Tuple(Vec<AbiType>),
+}
+
+impl AbiType {
+    /// Whether values of this type are encoded in tail (with offset in head)
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            Self::String | Self::Bytes | Self::Array(_) => true,
+            Self::FixedArray(inner, _) => inner.is_dynamic(),
+            Self::Tuple(items) => items.iter().any(|x| x.is_dynamic()),
+            _ => false,
+        }
+    }
+
+    /// Size of encoded value in head
+    fn head_size(&self) -> usize {
+        match self {
+            _ if self.is_dynamic() => 32,
+            Self::FixedArray(inner, size) => inner.head_size() * size,
+            Self::Tuple(items) => items.iter().map(|x| x.head_size()).sum(),
+            _ => 32,
+        }
+    }
+}
+
+impl FromStr for AbiType {
+    type Err = Error;
+
+    /// Parse canonical type (e.g. `uint256`, `(address,bytes)[2]`)
+    fn from_str(kind: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidAbiType(kind.to_string());
+        if let Some(inner) = kind.strip_suffix(']') {
+            let (inner, size) = inner.rsplit_once('[').ok_or_else(invalid)?;
+            let inner = Box::new(inner.parse()?);
+            return match size {
+                "" => Ok(Self::Array(inner)),
+                size => Ok(Self::FixedArray(
+                    inner,
+                    size.parse().map_err(|_| invalid())?,
+                )),
+            };
+        }
+        if let Some(inner) = kind.strip_prefix('(').and_then(|x| x.strip_suffix(')')) {
+            return Ok(Self::Tuple(
+                split_tuple(inner)
+                    .ok_or_else(invalid)?
+                    .into_iter()
+                    .map(str::parse)
+                    .collect::<Result<_, _>>()?,
+            ));
+        }
+        let bits = |s: &str| match s {
+            "" => Ok(256),
+            s => s
+                .parse::<usize>()
+                .ok()
+                .filter(|x| *x > 0 && *x <= 256 && x % 8 == 0)
+                .ok_or_else(invalid),
+        };
+        Ok(match kind {
+            "address" => Self::Address,
+            "trcToken" => Self::Uint(256),
+            "bool" => Self::Bool,
+            "string" => Self::String,
+            "bytes" => Self::Bytes,
+            kind => {
+                if let Some(size) = kind.strip_prefix("bytes") {
+                    Self::FixedBytes(
+                        size.parse()
+                            .ok()
+                            .filter(|x| (1..=32).contains(x))
+                            .ok_or_else(invalid)?,
+                    )
+                } else if let Some(size) = kind.strip_prefix("uint") {
+                    Self::Uint(bits(size)?)
+                } else if let Some(size) = kind.strip_prefix("int") {
+                    Self::Int(bits(size)?)
+                } else {
+                    return Err(invalid());
+                }
+            }
+        })
+    }
+}
+
+/// Canonical type (as used in signatures)
+impl Display for AbiType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Address => write!(f, "address"),
+            Self::Uint(bits) => write!(f, "uint{}", bits),
+            Self::Int(bits) => write!(f, "int{}", bits),
+            Self::Bool => write!(f, "bool"),
+            Self::String => write!(f, "string"),
+            Self::Bytes => write!(f, "bytes"),
+            Self::FixedBytes(size) => write!(f, "bytes{}", size),
+            Self::Array(inner) => write!(f, "{}[]", inner),
+            Self::FixedArray(inner, size) => write!(f, "{}[{}]", inner, size),
+            Self::Tuple(items) => {
+                f.write_char('(')?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                f.write_char(')')
+            }
+        }
+    }
+}
+
+/// Split canonical tuple components on top-level commas, None if parentheses are unbalanced
+fn split_tuple(inner: &str) -> Option<Vec<&str>> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0usize, 0);
+    for (i, c) in inner.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.checked_sub(1)?,
+            ',' if depth == 0 => {
+                parts.push(&inner[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        return None;
+    }
+    if !inner.is_empty() {
+        parts.push(&inner[start..]);
+    }
+    Some(parts)
+}
+
+/// ABI value
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AbiValue {
+    /// `address`
+    Address(Address),
+    /// `uintN`
+    Uint(U256),
+    /// `intN`
+    Int(I256),
+    /// `bool`
+    Bool(bool),
+    /// `bytesN` (also hash of indexed dynamic event param)
+    FixedBytes(Vec<u8>),
+    /// `bytes`
+    Bytes(Vec<u8>),
+    /// `string`
+    String(String),
+    /// `T[]`
+    Array(Vec<AbiValue>),
+    /// `T[N]`
+    FixedArray(Vec<AbiValue>),
+    /// `(T1,T2,...)`
+    Tuple(Vec<AbiValue>),
+}
+
+impl AbiValue {
+    /// Address value
+    pub fn as_address(&self) -> Option<Address> {
+        match self {
+            Self::Address(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    /// Unsigned integer value
+    pub fn as_uint(&self) -> Option<U256> {
+        match self {
+            Self::Uint(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    /// Signed integer value
+    pub fn as_int(&self) -> Option<I256> {
+        match self {
+            Self::Int(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    /// Boolean value
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    /// String value
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Bytes value (`bytes` or `bytesN`)
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Bytes(x) | Self::FixedBytes(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Elements of array or tuple
+    pub fn as_slice(&self) -> Option<&[AbiValue]> {
+        match self {
+            Self::Array(x) | Self::FixedArray(x) | Self::Tuple(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Convert into JSON (integers as decimal strings, bytes as hex, addresses in base58)
+    pub fn to_json(&self) -> serde_json::Value {
+        use serde_json::Value;
+        match self {
+            Self::Address(x) => Value::String(x.as_base58()),
+            Self::Uint(x) => Value::String(x.to_string()),
+            Self::Int(x) => Value::String(x.to_string()),
+            Self::Bool(x) => Value::Bool(*x),
+            Self::FixedBytes(x) | Self::Bytes(x) => Value::String(hex::encode(x)),
+            Self::String(x) => Value::String(x.clone()),
+            Self::Array(x) | Self::FixedArray(x) | Self::Tuple(x) => {
+                Value::Array(x.iter().map(|x| x.to_json()).collect())
+            }
+        }
+    }
+
+    /// Whether value can be encoded as given type (integer ranges are not checked)
+    pub fn is_of(&self, kind: &AbiType) -> bool {
+        match (self, kind) {
+            (Self::Address(_), AbiType::Address)
+            | (Self::Uint(_), AbiType::Uint(_))
+            | (Self::Int(_), AbiType::Int(_))
+            | (Self::Bool(_), AbiType::Bool)
+            | (Self::String(_), AbiType::String)
+            | (Self::Bytes(_), AbiType::Bytes) => true,
+            (Self::FixedBytes(x), AbiType::FixedBytes(size)) => x.len() == *size,
+            (Self::Array(items), AbiType::Array(inner)) => items.iter().all(|x| x.is_of(inner)),
+            (Self::FixedArray(items), AbiType::FixedArray(inner, size)) => {
+                items.len() == *size && items.iter().all(|x| x.is_of(inner))
+            }
+            (Self::Tuple(items), AbiType::Tuple(kinds)) => {
+                items.len() == kinds.len() && items.iter().zip(kinds).all(|(x, k)| x.is_of(k))
+            }
+            _ => false,
+        }
+    }
+
+    fn is_dynamic(&self) -> bool {
+        match self {
+            Self::String(_) | Self::Bytes(_) | Self::Array(_) => true,
+            Self::FixedArray(items) | Self::Tuple(items) => items.iter().any(|x| x.is_dynamic()),
+            _ => false,
+        }
+    }
+}
+
+impl From<Address> for AbiValue {
+    fn from(value: Address) -> Self {
+        Self::Address(value)
+    }
+}
+
+impl From<U256> for AbiValue {
+    fn from(value: U256) -> Self {
+        Self::Uint(value)
+    }
+}
+
+impl From<u64> for AbiValue {
+    fn from(value: u64) -> Self {
+        Self::Uint(U256::from(value))
+    }
+}
+
+impl From<I256> for AbiValue {
+    fn from(value: I256) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<bool> for AbiValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<&str> for AbiValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<String> for AbiValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+/// ABI encode values as tuple (e.g. as contract call parameter)
+pub fn encode(values: &[AbiValue]) -> Vec<u8> {
+    let mut out = Vec::new();
+    encode_tuple(values, &mut out);
+    out
+}
+
+fn encode_tuple(values: &[AbiValue], out: &mut Vec<u8>) {
+    let head_size: usize = values
+        .iter()
+        .map(|x| match x.is_dynamic() {
+            true => 32,
+            false => encoded_size(x),
+        })
+        .sum();
+    let mut tail = Vec::new();
+    for value in values {
+        if value.is_dynamic() {
+            out.extend_from_slice(&U256::from(head_size + tail.len()).to_be_bytes::<32>());
+            encode_value(value, &mut tail);
+        } else {
+            encode_value(value, out);
+        }
+    }
+    out.extend_from_slice(&tail);
+}
+
+/// Size of encoded static value
+fn encoded_size(value: &AbiValue) -> usize {
+    match value {
+        AbiValue::FixedArray(items) | AbiValue::Tuple(items) => {
+            items.iter().map(encoded_size).sum()
+        }
+        _ => 32,
+    }
+}
+
+fn encode_bytes(bytes: &[u8], out: &mut Vec<u8>) {
+    out.extend_from_slice(bytes);
+    out.resize(out.len() + (32 - bytes.len() % 32) % 32, 0);
+}
+
+fn encode_value(value: &AbiValue, out: &mut Vec<u8>) {
+    match value {
+        AbiValue::Address(x) => {
+            out.extend_from_slice(&[0; 12]);
+            out.extend_from_slice(&x.as_bytes()[1..]);
+        }
+        AbiValue::Uint(x) => out.extend_from_slice(&x.to_be_bytes::<32>()),
+        AbiValue::Int(x) => out.extend_from_slice(&x.into_raw().to_be_bytes::<32>()),
+        AbiValue::Bool(x) => out.extend_from_slice(&U256::from(*x as u8).to_be_bytes::<32>()),
+        AbiValue::FixedBytes(x) => encode_bytes(x, out),
+        AbiValue::Bytes(x) => {
+            out.extend_from_slice(&U256::from(x.len()).to_be_bytes::<32>());
+            encode_bytes(x, out);
+        }
+        AbiValue::String(x) => {
+            out.extend_from_slice(&U256::from(x.len()).to_be_bytes::<32>());
+            encode_bytes(x.as_bytes(), out);
+        }
+        AbiValue::Array(items) => {
+            out.extend_from_slice(&U256::from(items.len()).to_be_bytes::<32>());
+            encode_tuple(items, out);
+        }
+        AbiValue::FixedArray(items) | AbiValue::Tuple(items) => encode_tuple(items, out),
+    }
+}
+
+/// Decode ABI encoded tuple of given types
+pub fn decode(types: &[AbiType], data: &[u8]) -> Result<Vec<AbiValue>, Error> {
+    decode_tuple(types.iter(), data)
+}
+
+fn invalid_data(message: &str) -> Error {
+    Error::InvalidAbiData(message.to_string())
+}
+
+fn word(data: &[u8], offset: usize) -> Result<&[u8], Error> {
+    offset
+        .checked_add(32)
+        .and_then(|end| data.get(offset..end))
+        .ok_or_else(|| invalid_data("data too short"))
+}
+
+/// Read offset or length word
+fn read_usize(data: &[u8], offset: usize) -> Result<usize, Error> {
+    let value = U256::from_be_slice(word(data, offset)?);
+    usize::try_from(value)
+        .ok()
+        .filter(|x| *x <= data.len())
+        .ok_or_else(|| invalid_data("offset or length out of bounds"))
+}
+
+fn decode_tuple<'a>(
+    types: impl Iterator<Item = &'a AbiType>,
+    data: &[u8],
+) -> Result<Vec<AbiValue>, Error> {
+    let mut values = Vec::new();
+    let mut head = 0;
+    for kind in types {
+        if kind.is_dynamic() {
+            let offset = read_usize(data, head)?;
+            values.push(decode_value(kind, &data[offset..])?);
+        } else {
+            values.push(decode_value(kind, data.get(head..).unwrap_or_default())?);
+        }
+        head += kind.head_size();
+    }
+    Ok(values)
+}
+
+fn decode_bytes(data: &[u8]) -> Result<Vec<u8>, Error> {
+    let len = read_usize(data, 0)?;
+    data.get(32..32 + len)
+        .map(|x| x.to_vec())
+        .ok_or_else(|| invalid_data("bytes out of bounds"))
+}
+
+fn decode_value(kind: &AbiType, data: &[u8]) -> Result<AbiValue, Error> {
+    Ok(match kind {
+        AbiType::Address => {
+            let mut buf = [0x41; 21];
+            buf[1..].copy_from_slice(&word(data, 0)?[12..]);
+            AbiValue::Address(Address::new(buf)?)
+        }
+        AbiType::Uint(_) => AbiValue::Uint(U256::from_be_slice(word(data, 0)?)),
+        AbiType::Int(_) => AbiValue::Int(I256::from_raw(U256::from_be_slice(word(data, 0)?))),
+        AbiType::Bool => AbiValue::Bool(word(data, 0)?.iter().any(|x| *x != 0)),
+        AbiType::FixedBytes(size) => AbiValue::FixedBytes(word(data, 0)?[..*size].to_vec()),
+        AbiType::Bytes => AbiValue::Bytes(decode_bytes(data)?),
+        AbiType::String => AbiValue::String(
+            String::from_utf8(decode_bytes(data)?)
+                .map_err(|e| invalid_data(&format!("invalid utf8 {}", e)))?,
+        ),
+        AbiType::Array(inner) => {
+            let len = read_usize(data, 0)?;
+            // each element takes at least one word
+            if len > data.len() / 32 {
+                return Err(invalid_data("array length out of bounds"));
+            }
+            AbiValue::Array(decode_tuple(
+                core::iter::repeat_n(&**inner, len),
+                &data[32..],
+            )?)
+        }
+        AbiType::FixedArray(inner, size) => {
+            AbiValue::FixedArray(decode_tuple(core::iter::repeat_n(&**inner, *size), data)?)
+        }
+        AbiType::Tuple(items) => AbiValue::Tuple(decode_tuple(items.iter(), data)?),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec;
+
+    use super::*;
+
+    fn types(types: &[&str]) -> Vec<AbiType> {
+        types.iter().map(|x| x.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_parse_type() {
+        for kind in [
+            "uint256",
+            "(address,bytes[])[2]",
+            "((bool,int8),string)",
+            "bytes32[][]",
+        ] {
+            assert_eq!(kind.parse::<AbiType>().unwrap().to_string(), kind);
+        }
+        assert_eq!("trcToken".parse::<AbiType>().unwrap(), AbiType::Uint(256));
+        for kind in ["uint7", "bytes33", "(uint256", "address]", "foo", "int0"] {
+            assert!(kind.parse::<AbiType>().is_err(), "{}", kind);
+        }
+    }
+
+    #[test]
+    fn test_encode_static() {
+        let to: Address = "TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqeL".parse().unwrap();
+        let data = encode(&[to.into(), 1000u64.into()]);
+        assert_eq!(
+            data,
+            hex_literal::hex!(
+                "0000000000000000000000008840e6c55b9ada326d211d818c34a994aeced808"
+                "00000000000000000000000000000000000000000000000000000000000003e8"
+            )
+        );
+        assert_eq!(
+            decode(&types(&["address", "uint256"]), &data).unwrap(),
+            [to.into(), 1000u64.into()]
+        );
+    }
+
+    #[test]
+    fn test_encode_dynamic() {
+        // f(uint256,uint32[],bytes10,bytes) example from Solidity ABI spec
+        let values = [
+            AbiValue::Uint(U256::from(0x123)),
+            AbiValue::Array(vec![0x456u64.into(), 0x789u64.into()]),
+            AbiValue::FixedBytes(b"1234567890".to_vec()),
+            AbiValue::Bytes(b"Hello, world!".to_vec()),
+        ];
+        let data = encode(&values);
+        assert_eq!(
+            data,
+            hex_literal::hex!(
+                "0000000000000000000000000000000000000000000000000000000000000123"
+                "0000000000000000000000000000000000000000000000000000000000000080"
+                "3132333435363738393000000000000000000000000000000000000000000000"
+                "00000000000000000000000000000000000000000000000000000000000000e0"
+                "0000000000000000000000000000000000000000000000000000000000000002"
+                "0000000000000000000000000000000000000000000000000000000000000456"
+                "0000000000000000000000000000000000000000000000000000000000000789"
+                "000000000000000000000000000000000000000000000000000000000000000d"
+                "48656c6c6f2c20776f726c642100000000000000000000000000000000000000"
+            )
+        );
+        let decoded = decode(&types(&["uint256", "uint32[]", "bytes10", "bytes"]), &data);
+        assert_eq!(decoded.unwrap(), values);
+    }
+
+    #[test]
+    fn test_nested() {
+        let values = [
+            AbiValue::Tuple(vec![
+                AbiValue::Int(I256::MINUS_ONE),
+                AbiValue::Array(vec![AbiValue::String("a".into()), "".into()]),
+            ]),
+            AbiValue::FixedArray(vec![true.into(), false.into()]),
+            AbiValue::Array(vec![]),
+        ];
+        let data = encode(&values);
+        let types = types(&["(int32,string[])", "bool[2]", "address[]"]);
+        assert_eq!(decode(&types, &data).unwrap(), values);
+
+        // truncated data and out of bounds offset
+        assert!(decode(&types, &data[..data.len() - 1]).is_err());
+        let mut corrupted = data.clone();
+        corrupted[30] = 0xff;
+        assert!(decode(&types, &corrupted).is_err());
+    }
+
+    #[test]
+    fn test_is_of() {
+        let value = AbiValue::Tuple(vec![
+            "TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqeL"
+                .parse::<Address>()
+                .unwrap()
+                .into(),
+            AbiValue::FixedArray(vec![1u64.into(), 2u64.into()]),
+            AbiValue::FixedBytes(vec![0; 4]),
+        ]);
+        assert!(value.is_of(&"(address,uint8[2],bytes4)".parse().unwrap()));
+        for kind in [
+            "(address,uint8[3],bytes4)",
+            "(address,int8[2],bytes4)",
+            "(address,uint8[2],bytes5)",
+            "(address,uint8[2])",
+        ] {
+            assert!(!value.is_of(&kind.parse().unwrap()), "{}", kind);
+        }
+    }
+}
@@ -82,22 +82,6 @@ impl<'de> Deserialize<'de> for Address {
     }
 }
 
-#[cfg(feature = "ethabi_compat")]
-impl From<ethabi::Address> for Address {
-    fn from(address: ethabi::Address) -> Self {
-        let mut buf = [0x41; 21];
-        buf[1..].copy_from_slice(address.as_bytes());
-        Self(buf)
-    }
-}
-
-#[cfg(feature = "ethabi_compat")]
-impl From<Address> for ethabi::Address {
-    fn from(address: Address) -> Self {
-        Self(address.0[1..].try_into().expect("Always 20 bytes"))
-    }
-}
-
 impl From<alloy_primitives::Address> for Address {
     fn from(address: alloy_primitives::Address) -> Self {
         let mut buf = [0x41; 21];
@@ -1,3 +1,5 @@
+use alloc::string::String;
+
 /// Error type
 #[derive(Debug, Clone)]
 pub enum Error {
@@ -7,6 +9,10 @@ pub enum Error {
     InvalidTransactionId,
     /// Invalid block id
     InvalidBlockId,
+    /// Invalid ABI type string
+    InvalidAbiType(String),
+    /// ABI encoded data cannot be decoded as requested types
+    InvalidAbiData(String),
 }
 
 impl core::fmt::Display for Error {
@@ -5,6 +5,7 @@
 
 extern crate alloc;
 
+pub mod abi;
 mod address;
 pub mod block;
 pub mod transaction;
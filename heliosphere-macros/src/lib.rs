@@ -0,0 +1,660 @@
+//! Typed contract bindings for [heliosphere](https://crates.io/crates/heliosphere)
+#![forbid(unsafe_code)]
+#![deny(missing_docs)]
+
+use std::{collections::HashMap, path::PathBuf};
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use serde::Deserialize;
+use sha3::{Digest, Keccak256};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, Ident, LitStr, Token, Visibility,
+};
+
+/// Generate typed binding of contract from ABI JSON file (path is relative to crate root)
+///
+/// ```ignore
+/// heliosphere::contract!(pub Token, "abi/token.json");
+///
+/// let token = Token::new(client, address);
+/// let balance = token.balance_of(owner).await?;
+/// let tx = token.transfer(&owner, recipient, amount).build().await?;
+/// ```
+///
+/// - view and pure functions become async methods returning decoded outputs
+/// - other functions become methods taking caller address and returning `ContractTxBuilder`
+/// - events become `{Contract}{Event}` structs with `decode(&Log)`, and `{Contract}Event` enum of all of them
+///
+/// Names are converted to snake case, overloads get numeric suffix (`safe_transfer_from_1`).
+/// `uint8`..`uint128` and `int8`..`int128` map to native integers, wider ones to `U256`/`I256`.
+/// Indexed dynamic event parameters are stored as their 32-byte hash. Anonymous events are skipped.
+#[proc_macro]
+pub fn contract(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as ContractInput);
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+struct ContractInput {
+    vis: Visibility,
+    name: Ident,
+    path: LitStr,
+}
+
+impl Parse for ContractInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis = input.parse()?;
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let path = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Self { vis, name, path })
+    }
+}
+
+#[derive(Deserialize)]
+struct Param {
+    #[serde(default)]
+    name: String,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    components: Vec<Param>,
+    #[serde(default)]
+    indexed: bool,
+}
+
+#[derive(Deserialize)]
+struct Entry {
+    #[serde(rename = "type", default)]
+    kind: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    inputs: Vec<Param>,
+    #[serde(default)]
+    outputs: Vec<Param>,
+    #[serde(default)]
+    anonymous: bool,
+    #[serde(rename = "stateMutability", default)]
+    state_mutability: Option<String>,
+    #[serde(default)]
+    constant: bool,
+}
+
+impl Entry {
+    fn is_view(&self) -> bool {
+        match &self.state_mutability {
+            Some(x) => matches!(x.to_lowercase().as_str(), "view" | "pure"),
+            None => self.constant,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AbiJson {
+    Entries(Vec<Entry>),
+    Tron { entrys: Vec<Entry> },
+}
+
+/// Solidity type
+#[derive(Clone)]
+enum Kind {
+    Address,
+    Bool,
+    String,
+    Bytes,
+    FixedBytes(usize),
+    Uint(usize),
+    Int(usize),
+    Array(Box<Kind>),
+    FixedArray(Box<Kind>, usize),
+    Tuple(Vec<Kind>),
+}
+
+impl Kind {
+    fn parse(kind: &str, components: &[Param]) -> Result<Self, String> {
+        let invalid = || format!("invalid type {}", kind);
+        if let Some(inner) = kind.strip_suffix(']') {
+            let (inner, size) = inner.rsplit_once('[').ok_or_else(invalid)?;
+            let inner = Box::new(Self::parse(inner, components)?);
+            return match size {
+                "" => Ok(Self::Array(inner)),
+                size => Ok(Self::FixedArray(
+                    inner,
+                    size.parse().map_err(|_| invalid())?,
+                )),
+            };
+        }
+        let bits = |s: &str| match s {
+            "" => Ok(256),
+            s => s
+                .parse::<usize>()
+                .ok()
+                .filter(|x| *x > 0 && *x <= 256 && x % 8 == 0)
+                .ok_or_else(invalid),
+        };
+        Ok(match kind {
+            "address" => Self::Address,
+            "trcToken" => Self::Uint(256),
+            "bool" => Self::Bool,
+            "string" => Self::String,
+            "bytes" => Self::Bytes,
+            "tuple" => Self::Tuple(
+                components
+                    .iter()
+                    .map(|x| Self::parse(&x.kind, &x.components))
+                    .collect::<Result<_, _>>()?,
+            ),
+            kind => {
+                if let Some(size) = kind.strip_prefix("bytes") {
+                    Self::FixedBytes(
+                        size.parse()
+                            .ok()
+                            .filter(|x| (1..=32).contains(x))
+                            .ok_or_else(invalid)?,
+                    )
+                } else if let Some(size) = kind.strip_prefix("uint") {
+                    Self::Uint(bits(size)?)
+                } else if let Some(size) = kind.strip_prefix("int") {
+                    Self::Int(bits(size)?)
+                } else {
+                    return Err(invalid());
+                }
+            }
+        })
+    }
+
+    /// Whether indexed event parameter of this type is stored as hash
+    fn is_hashed(&self) -> bool {
+        matches!(
+            self,
+            Self::String | Self::Bytes | Self::Array(_) | Self::FixedArray(..) | Self::Tuple(_)
+        )
+    }
+
+    /// Native integer type for standard sizes up to 128 bits
+    fn native_int(bits: usize, signed: bool) -> Option<Ident> {
+        matches!(bits, 8 | 16 | 32 | 64 | 128).then(|| {
+            let prefix = if signed { "i" } else { "u" };
+            format_ident!("{}{}", prefix, bits)
+        })
+    }
+
+    /// Rust type
+    fn rust_type(&self) -> TokenStream {
+        match self {
+            Self::Address => quote!(::heliosphere::core::Address),
+            Self::Bool => quote!(bool),
+            Self::String => quote!(::std::string::String),
+            Self::Bytes => quote!(::std::vec::Vec<u8>),
+            Self::FixedBytes(n) => quote!([u8; #n]),
+            Self::Uint(bits) => match Self::native_int(*bits, false) {
+                Some(ty) => quote!(#ty),
+                None => quote!(::heliosphere::U256),
+            },
+            Self::Int(bits) => match Self::native_int(*bits, true) {
+                Some(ty) => quote!(#ty),
+                None => quote!(::heliosphere::I256),
+            },
+            Self::Array(inner) => {
+                let inner = inner.rust_type();
+                quote!(::std::vec::Vec<#inner>)
+            }
+            Self::FixedArray(inner, n) => {
+                let inner = inner.rust_type();
+                quote!([#inner; #n])
+            }
+            Self::Tuple(items) => {
+                let items = items.iter().map(|x| x.rust_type());
+                quote!((#(#items,)*))
+            }
+        }
+    }
+
+    /// Expression converting Rust value `value` into `AbiValue`
+    fn encode_value(&self, value: TokenStream) -> TokenStream {
+        let abi = quote!(::heliosphere::abi::AbiValue);
+        match self {
+            Self::Address => quote!(#abi::Address(#value)),
+            Self::Bool => quote!(#abi::Bool(#value)),
+            Self::String => quote!(#abi::String(#value)),
+            Self::Bytes => quote!(#abi::Bytes(#value)),
+            Self::FixedBytes(_) => quote!(#abi::FixedBytes(#value.to_vec())),
+            Self::Uint(bits) => match Self::native_int(*bits, false) {
+                Some(_) => quote!(#abi::Uint(::heliosphere::U256::from(#value))),
+                None => quote!(#abi::Uint(#value)),
+            },
+            Self::Int(bits) => match Self::native_int(*bits, true) {
+                Some(_) => quote!(#abi::Int(
+                    ::heliosphere::I256::try_from(#value).expect("Fits in int256")
+                )),
+                None => quote!(#abi::Int(#value)),
+            },
+            Self::Array(inner) | Self::FixedArray(inner, _) => {
+                let variant = match self {
+                    Self::Array(_) => quote!(Array),
+                    _ => quote!(FixedArray),
+                };
+                let inner = inner.encode_value(quote!(x));
+                quote!(#abi::#variant(#value.into_iter().map(|x| #inner).collect()))
+            }
+            Self::Tuple(items) => {
+                let names: Vec<_> = (0..items.len()).map(|i| format_ident!("t{}", i)).collect();
+                let values = items
+                    .iter()
+                    .zip(&names)
+                    .map(|(kind, name)| kind.encode_value(quote!(#name)));
+                quote!({
+                    let (#(#names,)*) = #value;
+                    #abi::Tuple(::std::vec![#(#values),*])
+                })
+            }
+        }
+    }
+
+    /// Expression converting `AbiValue` into Rust value, returns `heliosphere::Error` on mismatch
+    fn decode_value(&self, value: TokenStream) -> TokenStream {
+        let abi = quote!(::heliosphere::abi::AbiValue);
+        let expected = format!("unexpected ABI value, expected {}", self.rust_type());
+        let err = quote!(::heliosphere::Error::AbiError(#expected.into()));
+        let (variant, conv) = match self {
+            Self::Address => (quote!(Address), quote!(x)),
+            Self::Bool => (quote!(Bool), quote!(x)),
+            Self::String => (quote!(String), quote!(x)),
+            Self::Bytes => (quote!(Bytes), quote!(x)),
+            Self::FixedBytes(n) => (
+                quote!(FixedBytes),
+                quote!(<[u8; #n]>::try_from(x.as_slice()).map_err(|_| #err)?),
+            ),
+            Self::Uint(bits) | Self::Int(bits) => {
+                let signed = matches!(self, Self::Int(_));
+                let variant = match signed {
+                    true => quote!(Int),
+                    false => quote!(Uint),
+                };
+                match Self::native_int(*bits, signed) {
+                    Some(ty) => (variant, quote!(#ty::try_from(x).map_err(|_| #err)?)),
+                    None => (variant, quote!(x)),
+                }
+            }
+            Self::Array(inner) | Self::FixedArray(inner, _) => {
+                let ty = inner.rust_type();
+                let item = inner.decode_value(quote!(x));
+                let items = quote!(x
+                    .into_iter()
+                    .map(|x| -> ::std::result::Result<#ty, ::heliosphere::Error> { Ok(#item) })
+                    .collect::<::std::result::Result<::std::vec::Vec<_>, _>>()?);
+                match self {
+                    Self::FixedArray(_, n) => (
+                        quote!(FixedArray),
+                        quote!(<[#ty; #n]>::try_from(#items).map_err(|_| #err)?),
+                    ),
+                    _ => (quote!(Array), items),
+                }
+            }
+            Self::Tuple(items) => {
+                let n = items.len();
+                let names: Vec<_> = (0..n).map(|i| format_ident!("t{}", i)).collect();
+                let values = items
+                    .iter()
+                    .zip(&names)
+                    .map(|(kind, name)| kind.decode_value(quote!(#name)));
+                (
+                    quote!(Tuple),
+                    quote!({
+                        let [#(#names),*]: [#abi; #n] = x.try_into().map_err(|_| #err)?;
+                        (#(#values,)*)
+                    }),
+                )
+            }
+        };
+        quote!(match #value {
+            #abi::#variant(x) => #conv,
+            _ => return Err(#err),
+        })
+    }
+}
+
+impl quote::ToTokens for Kind {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(self.rust_type())
+    }
+}
+
+fn canonical_type(param: &Param) -> String {
+    match param.kind.strip_prefix("tuple") {
+        Some(suffix) => format!(
+            "({}){}",
+            param
+                .components
+                .iter()
+                .map(canonical_type)
+                .collect::<Vec<_>>()
+                .join(","),
+            suffix
+        ),
+        None => param.kind.clone(),
+    }
+}
+
+fn signature(name: &str, params: &[Param]) -> String {
+    format!(
+        "{}({})",
+        name,
+        params
+            .iter()
+            .map(canonical_type)
+            .collect::<Vec<_>>()
+            .join(",")
+    )
+}
+
+/// Convert camelCase or SCREAMING_CASE name into snake case identifier
+fn snake_case(name: &str, fallback: &str) -> Ident {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|x| x.is_lowercase());
+            if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower) {
+                out.push('_');
+            }
+        }
+        out.extend(c.to_lowercase());
+    }
+    ident(out.trim_matches('_'), fallback)
+}
+
+/// Convert name into PascalCase identifier
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .filter(|x| !x.is_empty())
+        .map(|x| {
+            let mut chars = x.chars();
+            chars
+                .next()
+                .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Identifier with given name, suffixed with `_` if it is a keyword
+fn ident(name: &str, fallback: &str) -> Ident {
+    let name = match name.is_empty() {
+        true => fallback,
+        false => name,
+    };
+    syn::parse_str::<Ident>(name).unwrap_or_else(|_| format_ident!("{}_", name))
+}
+
+/// Unique identifiers for overloaded names
+#[derive(Default)]
+struct Names(HashMap<String, usize>);
+
+impl Names {
+    fn reserve(&mut self, name: &str) {
+        self.0.insert(name.to_owned(), 1);
+    }
+
+    fn unique(&mut self, ident: Ident) -> Ident {
+        let count = self.0.entry(ident.to_string()).or_default();
+        *count += 1;
+        match *count {
+            1 => ident,
+            n => format_ident!("{}_{}", ident, n - 1),
+        }
+    }
+}
+
+fn param_names(params: &[Param], reserved: &[&str]) -> Vec<Ident> {
+    let mut names = Names::default();
+    for name in reserved {
+        names.reserve(name);
+    }
+    params
+        .iter()
+        .enumerate()
+        .map(|(i, x)| names.unique(snake_case(&x.name, &format!("arg{}", i))))
+        .collect()
+}
+
+fn parse_params(params: &[Param]) -> Result<Vec<Kind>, String> {
+    params
+        .iter()
+        .map(|x| Kind::parse(&x.kind, &x.components))
+        .collect()
+}
+
+fn expand(input: ContractInput) -> syn::Result<TokenStream> {
+    let err = |msg: String| syn::Error::new(input.path.span(), msg);
+    let root = std::env::var("CARGO_MANIFEST_DIR").map_err(|e| err(e.to_string()))?;
+    let path = PathBuf::from(root).join(input.path.value());
+    let json = std::fs::read_to_string(&path)
+        .map_err(|e| err(format!("failed to read {}: {}", path.display(), e)))?;
+    let entries = match serde_json::from_str(&json).map_err(|e| err(e.to_string()))? {
+        AbiJson::Entries(entries) => entries,
+        AbiJson::Tron { entrys } => entrys,
+    };
+
+    let ContractInput { vis, name, .. } = &input;
+    let path = path.display().to_string();
+    let mut methods = Vec::new();
+    let mut events = Vec::new();
+    let mut method_names = Names::default();
+    method_names.reserve("new");
+    method_names.reserve("address");
+    let mut event_names = Names::default();
+
+    for entry in &entries {
+        match entry.kind.to_lowercase().as_str() {
+            "function" | "" => {
+                let method = method_names.unique(snake_case(&entry.name, "call"));
+                methods.push(function(entry, &method).map_err(err)?);
+            }
+            "event" if !entry.anonymous => {
+                let ident = event_names.unique(ident(&pascal_case(&entry.name), "Event"));
+                events.push((ident.clone(), event(&input, entry, &ident).map_err(err)?));
+            }
+            _ => {} // constructor, fallback, receive, error and anonymous events
+        }
+    }
+
+    let doc = format!("Binding of `{}` contract generated from ABI", name);
+    let event_enum = (!events.is_empty()).then(|| {
+        let enum_name = format_ident!("{}Event", name);
+        let enum_doc = format!("Any event of `{}` contract", name);
+        let variants = events.iter().map(|(variant, _)| {
+            let ty = format_ident!("{}{}", name, variant);
+            let doc = format!("`{}` event", variant);
+            quote!(#[doc = #doc] #variant(#ty))
+        });
+        let decoders = events.iter().map(|(variant, _)| {
+            let ty = format_ident!("{}{}", name, variant);
+            quote!(if let Some(event) = #ty::decode(log)? {
+                return Ok(Some(Self::#variant(event)));
+            })
+        });
+        quote! {
+            #[doc = #enum_doc]
+            #[derive(Debug, Clone, PartialEq, Eq)]
+            #vis enum #enum_name {
+                #(#variants,)*
+            }
+
+            impl #enum_name {
+                /// Decode any known event, returns None for logs of other events
+                pub fn decode(log: &::heliosphere::Log) -> ::std::result::Result<Option<Self>, ::heliosphere::Error> {
+                    #(#decoders)*
+                    Ok(None)
+                }
+            }
+        }
+    });
+    let events = events.into_iter().map(|(_, tokens)| tokens);
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Clone)]
+        #vis struct #name {
+            client: ::heliosphere::RpcClient,
+            address: ::heliosphere::core::Address,
+        }
+
+        // rebuild when ABI changes
+        const _: &str = include_str!(#path);
+
+        impl #name {
+            /// Create new binding of contract at given address
+            pub fn new(client: ::heliosphere::RpcClient, address: ::heliosphere::core::Address) -> Self {
+                Self { client, address }
+            }
+
+            /// Contract address
+            pub fn address(&self) -> ::heliosphere::core::Address {
+                self.address
+            }
+
+            #(#methods)*
+        }
+
+        #(#events)*
+
+        #event_enum
+    })
+}
+
+fn function(entry: &Entry, method: &Ident) -> Result<TokenStream, String> {
+    let selector = signature(&entry.name, &entry.inputs);
+    let inputs = parse_params(&entry.inputs)?;
+    let names = param_names(&entry.inputs, &["caller"]);
+    let args = inputs
+        .iter()
+        .zip(&names)
+        .map(|(kind, name)| quote!(#name: #kind));
+    let values = inputs
+        .iter()
+        .zip(&names)
+        .map(|(kind, name)| kind.encode_value(quote!(#name)));
+
+    if entry.is_view() {
+        let outputs = parse_params(&entry.outputs)?;
+        let output_types = entry.outputs.iter().map(canonical_type);
+        let n = outputs.len();
+        let out_names: Vec<_> = (0..n).map(|i| format_ident!("o{}", i)).collect();
+        let out_values = outputs
+            .iter()
+            .zip(&out_names)
+            .map(|(kind, name)| kind.decode_value(quote!(#name)));
+        let (ret, result) = match n {
+            1 => (quote!(#(#outputs)*), quote!(#(#out_values)*)),
+            _ => (quote!((#(#outputs,)*)), quote!((#(#out_values,)*))),
+        };
+        let doc = format!("Query `{}` view function", selector);
+        Ok(quote! {
+            #[doc = #doc]
+            pub async fn #method(&self, #(#args),*) -> ::std::result::Result<#ret, ::heliosphere::Error> {
+                let values = self
+                    .client
+                    .query_values(&self.address, #selector, &[#(#values),*], &[#(#output_types),*])
+                    .await?;
+                let [#(#out_names),*]: [::heliosphere::abi::AbiValue; #n] = values
+                    .try_into()
+                    .map_err(|_| ::heliosphere::Error::AbiError("unexpected number of outputs".into()))?;
+                Ok(#result)
+            }
+        })
+    } else {
+        let doc = format!("Build `{}` call sent by `caller`", selector);
+        Ok(quote! {
+            #[doc = #doc]
+            pub fn #method(&self, caller: &::heliosphere::core::Address, #(#args),*) -> ::heliosphere::ContractTxBuilder {
+                ::heliosphere::ContractTxBuilder::new(
+                    self.client.clone(),
+                    *caller,
+                    self.address,
+                    #selector,
+                    ::heliosphere::abi::encode(&[#(#values),*]),
+                )
+            }
+        })
+    }
+}
+
+fn event(input: &ContractInput, entry: &Entry, ident: &Ident) -> Result<TokenStream, String> {
+    let ContractInput { vis, name, .. } = input;
+    let struct_name = format_ident!("{}{}", name, ident);
+    let signature = signature(&entry.name, &entry.inputs);
+    let topic: [u8; 32] = Keccak256::digest(signature.as_bytes()).into();
+    let kinds = parse_params(&entry.inputs)?;
+    let names = param_names(&entry.inputs, &[]);
+    let topic_count = entry.inputs.iter().filter(|x| x.indexed).count() + 1;
+    let data_types = entry
+        .inputs
+        .iter()
+        .filter(|x| !x.indexed)
+        .map(canonical_type);
+
+    let mut fields = Vec::new();
+    let mut values = Vec::new();
+    let mut topic_index = 1usize;
+    for ((param, kind), field) in entry.inputs.iter().zip(&kinds).zip(&names) {
+        let doc = format!("`{}` {}", canonical_type(param), param.name);
+        if param.indexed && kind.is_hashed() {
+            fields.push(quote!(#[doc = #doc] pub #field: [u8; 32]));
+            values.push(quote!(#field: log.topics[#topic_index]));
+            topic_index += 1;
+        } else if param.indexed {
+            let ty = canonical_type(param);
+            let value = kind.decode_value(
+                quote!(::heliosphere::abi::decode(&[#ty], &log.topics[#topic_index])?.remove(0)),
+            );
+            fields.push(quote!(#[doc = #doc] pub #field: #kind));
+            values.push(quote!(#field: #value));
+            topic_index += 1;
+        } else {
+            let value = kind.decode_value(quote!(data.next().expect("Decoded all params")));
+            fields.push(quote!(#[doc = #doc] pub #field: #kind));
+            values.push(quote!(#field: #value));
+        }
+    }
+
+    let doc = format!("`{}` event of `{}` contract", signature, name);
+    let topic_doc = format!("Signature hash of `{}`", signature);
+    let span = Span::call_site();
+    let topic = topic
+        .iter()
+        .map(|x| syn::LitInt::new(&format!("{:#04x}", x), span));
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #vis struct #struct_name {
+            #(#fields,)*
+        }
+
+        impl #struct_name {
+            #[doc = #topic_doc]
+            pub const TOPIC: [u8; 32] = [#(#topic),*];
+
+            /// Decode event from log, returns None for logs of other events
+            /// (including same signature with different indexed params, e.g. TRC721 `Transfer`)
+            pub fn decode(log: &::heliosphere::Log) -> ::std::result::Result<Option<Self>, ::heliosphere::Error> {
+                if log.topics.first() != Some(&Self::TOPIC) || log.topics.len() != #topic_count {
+                    return Ok(None);
+                }
+                #[allow(unused_mut, unused_variables)]
+                let mut data = ::heliosphere::abi::decode(&[#(#data_types),*], &log.data)?.into_iter();
+                Ok(Some(Self {
+                    #(#values,)*
+                }))
+            }
+        }
+    })
+}
@@ -0,0 +1,420 @@
+//! Contract ABI JSON definitions and ABI encoding (see `heliosphere_core::abi`)
+use alloy_primitives::{I256, U256};
+use heliosphere_core::Address;
+use serde::Deserialize;
+
+pub use heliosphere_core::abi::{encode, AbiType, AbiValue};
+
+/// Function or event parameter
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Hash)]
+pub struct AbiParam {
+    /// Parameter name (may be empty)
+    #[serde(default)]
+    pub name: String,
+    /// Solidity type (e.g. `uint256`, `address[]`, `tuple`)
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Tuple components
+    #[serde(default)]
+    pub components: Vec<AbiParam>,
+    /// Whether event parameter is stored in topics
+    #[serde(default)]
+    pub indexed: bool,
+}
+
+impl AbiParam {
+    /// Canonical type used in signatures (tuples are expanded)
+    pub fn canonical_type(&self) -> String {
+        match self.kind.strip_prefix("tuple") {
+            Some(suffix) => format!(
+                "({}){}",
+                self.components
+                    .iter()
+                    .map(|x| x.canonical_type())
+                    .collect::<Vec<_>>()
+                    .join(","),
+                suffix
+            ),
+            None => self.kind.clone(),
+        }
+    }
+
+    /// Parsed parameter type
+    pub fn param_type(&self) -> Result<AbiType, crate::Error> {
+        self.canonical_type().parse().map_err(abi_error)
+    }
+
+    /// Convert JSON value into ABI value of this parameter type
+    ///
+    /// Integers are accepted as numbers or decimal (or `0x` / `-0x` hex) strings,
+    /// bytes as hex strings, addresses in base58, `41` prefixed hex or `0x` prefixed 20-byte hex form,
+    /// tuples as arrays or objects keyed by component names.
+    pub fn value_from_json(&self, value: &serde_json::Value) -> Result<AbiValue, crate::Error> {
+        value_from_json(&self.kind, &self.components, value)
+    }
+}
+
+fn value_from_json(
+    kind: &str,
+    components: &[AbiParam],
+    value: &serde_json::Value,
+) -> Result<AbiValue, crate::Error> {
+    use serde_json::Value;
+    let invalid = || crate::Error::AbiError(format!("invalid {} value {}", kind, value));
+    if let Some(inner) = kind.strip_suffix(']') {
+        let (inner, size) = inner.rsplit_once('[').ok_or_else(invalid)?;
+        let items = value
+            .as_array()
+            .ok_or_else(invalid)?
+            .iter()
+            .map(|x| value_from_json(inner, components, x))
+            .collect::<Result<Vec<_>, _>>()?;
+        return match size {
+            "" => Ok(AbiValue::Array(items)),
+            size if size.parse() == Ok(items.len()) => Ok(AbiValue::FixedArray(items)),
+            _ => Err(invalid()),
+        };
+    }
+    if kind == "tuple" {
+        let items = match value {
+            Value::Array(items) if items.len() == components.len() => {
+                components.iter().zip(items).collect::<Vec<_>>()
+            }
+            Value::Object(fields) => components
+                .iter()
+                .map(|x| Ok((x, fields.get(&x.name).ok_or_else(invalid)?)))
+                .collect::<Result<_, crate::Error>>()?,
+            _ => return Err(invalid()),
+        };
+        return Ok(AbiValue::Tuple(
+            items
+                .into_iter()
+                .map(|(param, value)| param.value_from_json(value))
+                .collect::<Result<_, _>>()?,
+        ));
+    }
+    let text = match value {
+        Value::String(x) => x.trim().to_owned(),
+        Value::Number(x) => x.to_string(),
+        Value::Bool(x) => x.to_string(),
+        _ => return Err(invalid()),
+    };
+    let hex_bytes = || hex::decode(text.trim_start_matches("0x")).map_err(|_| invalid());
+    Ok(match kind.parse().map_err(abi_error)? {
+        AbiType::Address => match text.strip_prefix("0x") {
+            Some(evm) => {
+                let mut buf = [0x41; 21];
+                hex::decode_to_slice(evm, &mut buf[1..]).map_err(|_| invalid())?;
+                AbiValue::Address(Address::new(buf).expect("Valid prefix"))
+            }
+            None => AbiValue::Address(text.parse().map_err(|_| invalid())?),
+        },
+        AbiType::Uint(bits) => {
+            let x = match text.strip_prefix("0x") {
+                Some(hex) => U256::from_str_radix(hex, 16),
+                None => U256::from_str_radix(&text, 10),
+            }
+            .map_err(|_| invalid())?;
+            if x.bit_len() > bits {
+                return Err(invalid());
+            }
+            AbiValue::Uint(x)
+        }
+        AbiType::Int(bits) => {
+            let x = match text.trim_start_matches('-').starts_with("0x") {
+                true => I256::from_hex_str(&text),
+                false => I256::from_dec_str(&text),
+            }
+            .map_err(|_| invalid())?;
+            if x.bits() as usize > bits {
+                return Err(invalid());
+            }
+            AbiValue::Int(x)
+        }
+        AbiType::Bool => AbiValue::Bool(text.parse().map_err(|_| invalid())?),
+        AbiType::String => AbiValue::String(value.as_str().ok_or_else(invalid)?.to_owned()),
+        AbiType::Bytes => AbiValue::Bytes(hex_bytes()?),
+        AbiType::FixedBytes(size) => match hex_bytes()? {
+            bytes if bytes.len() == size => AbiValue::FixedBytes(bytes),
+            _ => return Err(invalid()),
+        },
+        _ => return Err(invalid()),
+    })
+}
+
+fn abi_error(error: heliosphere_core::Error) -> crate::Error {
+    crate::Error::AbiError(match error {
+        heliosphere_core::Error::InvalidAbiType(kind) => format!("invalid type {}", kind),
+        heliosphere_core::Error::InvalidAbiData(message) => message,
+        error => error.to_string(),
+    })
+}
+
+/// Function state mutability
+#[derive(Debug, Default, Clone, Copy, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum StateMutability {
+    /// Does not read state
+    #[serde(alias = "Pure")]
+    Pure,
+    /// Reads but does not modify state
+    #[serde(alias = "View")]
+    View,
+    /// Modifies state
+    #[default]
+    #[serde(alias = "Nonpayable")]
+    Nonpayable,
+    /// Modifies state and accepts TRX
+    #[serde(alias = "Payable")]
+    Payable,
+}
+
+/// Contract function
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AbiFunction {
+    /// Function name
+    pub name: String,
+    /// Arguments
+    pub inputs: Vec<AbiParam>,
+    /// Return values
+    pub outputs: Vec<AbiParam>,
+    /// State mutability
+    pub state_mutability: StateMutability,
+}
+
+impl AbiFunction {
+    /// Function signature (e.g. `transfer(address,uint256)`), as used in `MethodCall::selector`
+    pub fn signature(&self) -> String {
+        signature(&self.name, &self.inputs)
+    }
+
+    /// First 4 bytes of signature hash
+    pub fn selector(&self) -> [u8; 4] {
+        alloy_primitives::keccak256(self.signature().as_bytes())[..4]
+            .try_into()
+            .expect("Always 4 bytes")
+    }
+
+    /// ABI encode JSON arguments (see `AbiParam::value_from_json`)
+    pub fn encode_json_args(&self, args: &[serde_json::Value]) -> Result<Vec<u8>, crate::Error> {
+        if args.len() != self.inputs.len() {
+            return Err(crate::Error::AbiError(format!(
+                "{} expects {} arguments, got {}",
+                self.signature(),
+                self.inputs.len(),
+                args.len()
+            )));
+        }
+        let values = self
+            .inputs
+            .iter()
+            .zip(args)
+            .map(|(param, arg)| param.value_from_json(arg))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(encode(&values))
+    }
+
+    /// Decode ABI encoded return values
+    pub fn decode_output(&self, data: &[u8]) -> Result<Vec<AbiValue>, crate::Error> {
+        let types = self
+            .outputs
+            .iter()
+            .map(|x| x.param_type())
+            .collect::<Result<Vec<_>, _>>()?;
+        heliosphere_core::abi::decode(&types, data).map_err(abi_error)
+    }
+
+    /// Whether function does not modify state (can be queried with `query_contract`)
+    pub fn is_view(&self) -> bool {
+        matches!(
+            self.state_mutability,
+            StateMutability::Pure | StateMutability::View
+        )
+    }
+}
+
+/// Contract event
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AbiEvent {
+    /// Event name
+    pub name: String,
+    /// Parameters
+    pub inputs: Vec<AbiParam>,
+    /// Whether signature hash is omitted from topics
+    pub anonymous: bool,
+}
+
+impl AbiEvent {
+    /// Event signature (e.g. `Transfer(address,address,uint256)`)
+    pub fn signature(&self) -> String {
+        signature(&self.name, &self.inputs)
+    }
+
+    /// Signature hash (topic 0)
+    pub fn topic(&self) -> [u8; 32] {
+        alloy_primitives::keccak256(self.signature().as_bytes()).0
+    }
+
+    /// Number of log topics (indexed params and signature hash of non-anonymous event)
+    pub fn topic_count(&self) -> usize {
+        self.inputs.iter().filter(|x| x.indexed).count() + !self.anonymous as usize
+    }
+
+    /// Decode log topics and data into parameter values (in declaration order)
+    pub fn decode(&self, topics: &[[u8; 32]], data: &[u8]) -> Result<Vec<AbiValue>, crate::Error> {
+        let topics = match self.anonymous {
+            true => topics,
+            false => topics.get(1..).unwrap_or_default(),
+        };
+        let (indexed, plain): (Vec<_>, Vec<_>) = self.inputs.iter().partition(|x| x.indexed);
+        if topics.len() != indexed.len() {
+            return Err(crate::Error::AbiError(format!(
+                "{} expects {} indexed params, got {} topics",
+                self.name,
+                indexed.len(),
+                topics.len()
+            )));
+        }
+        let plain_types = plain
+            .iter()
+            .map(|x| x.param_type())
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut plain = heliosphere_core::abi::decode(&plain_types, data)
+            .map_err(abi_error)?
+            .into_iter();
+        let mut topics = topics.iter();
+        self.inputs
+            .iter()
+            .map(|param| match param.indexed {
+                true => {
+                    let topic = topics.next().expect("Checked above");
+                    match param.param_type()? {
+                        // dynamic values are stored as keccak256 hash
+                        kind if kind.is_dynamic()
+                            || matches!(kind, AbiType::FixedArray(..) | AbiType::Tuple(_)) =>
+                        {
+                            Ok(AbiValue::FixedBytes(topic.to_vec()))
+                        }
+                        kind => Ok(heliosphere_core::abi::decode(&[kind], topic)
+                            .map_err(abi_error)?
+                            .remove(0)),
+                    }
+                }
+                false => Ok(plain.next().expect("Same length")),
+            })
+            .collect()
+    }
+}
+
+fn signature(name: &str, inputs: &[AbiParam]) -> String {
+    format!(
+        "{}({})",
+        name,
+        inputs
+            .iter()
+            .map(|x| x.canonical_type())
+            .collect::<Vec<_>>()
+            .join(",")
+    )
+}
+
+#[derive(Deserialize)]
+struct AbiEntry {
+    #[serde(rename = "type", default)]
+    kind: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    inputs: Vec<AbiParam>,
+    #[serde(default)]
+    outputs: Vec<AbiParam>,
+    #[serde(default)]
+    anonymous: bool,
+    #[serde(rename = "stateMutability", default)]
+    state_mutability: Option<StateMutability>,
+    #[serde(default)]
+    constant: bool,
+    #[serde(default)]
+    payable: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AbiJson {
+    Entries(Vec<AbiEntry>),
+    Tron {
+        // missing for contracts deployed without ABI
+        #[serde(default)]
+        entrys: Vec<AbiEntry>,
+    },
+}
+
+/// Contract ABI (functions and events)
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(from = "AbiJson")]
+pub struct ContractAbi {
+    /// Functions
+    pub functions: Vec<AbiFunction>,
+    /// Events
+    pub events: Vec<AbiEvent>,
+}
+
+impl From<AbiJson> for ContractAbi {
+    fn from(abi: AbiJson) -> Self {
+        let entries = match abi {
+            AbiJson::Entries(entries) => entries,
+            AbiJson::Tron { entrys } => entrys,
+        };
+        let mut contract = Self::default();
+        for entry in entries {
+            match entry.kind.to_lowercase().as_str() {
+                "function" | "" => contract.functions.push(AbiFunction {
+                    state_mutability: entry.state_mutability.unwrap_or(
+                        match (entry.constant, entry.payable) {
+                            (true, _) => StateMutability::View,
+                            (false, true) => StateMutability::Payable,
+                            (false, false) => StateMutability::Nonpayable,
+                        },
+                    ),
+                    name: entry.name,
+                    inputs: entry.inputs,
+                    outputs: entry.outputs,
+                }),
+                "event" => contract.events.push(AbiEvent {
+                    name: entry.name,
+                    inputs: entry.inputs,
+                    anonymous: entry.anonymous,
+                }),
+                _ => {} // constructor, fallback, receive, error
+            }
+        }
+        contract
+    }
+}
+
+impl ContractAbi {
+    /// Parse JSON ABI array (as accepted by `deploy_contract`), Tron `{"entrys": [...]}` form is accepted as well
+    pub fn from_json(abi: &str) -> Result<Self, crate::Error> {
+        serde_json::from_str(abi).map_err(|e| crate::Error::AbiError(e.to_string()))
+    }
+
+    /// Find function by name (first overload)
+    pub fn function(&self, name: &str) -> Option<&AbiFunction> {
+        self.functions.iter().find(|x| x.name == name)
+    }
+
+    /// Find event by name (first overload)
+    pub fn event(&self, name: &str) -> Option<&AbiEvent> {
+        self.events.iter().find(|x| x.name == name)
+    }
+}
+
+/// Decode ABI encoded values of given canonical types (e.g. `["uint256", "(address,bytes)[]"]`)
+pub fn decode(types: &[&str], data: &[u8]) -> Result<Vec<AbiValue>, crate::Error> {
+    let types = types
+        .iter()
+        .map(|x| x.parse())
+        .collect::<Result<Vec<AbiType>, _>>()
+        .map_err(abi_error)?;
+    heliosphere_core::abi::decode(&types, data).map_err(abi_error)
+}
@@ -0,0 +1,49 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+/// API key header used by TronGrid
+pub(crate) const API_KEY_HEADER: &str = "TRON-PRO-API-KEY";
+
+/// Set of API keys rotated when current one hits its quota, shared between client clones
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ApiKeys {
+    keys: Arc<Vec<String>>,
+    current: Arc<AtomicUsize>,
+}
+
+impl ApiKeys {
+    pub fn new(keys: Vec<String>) -> Self {
+        Self {
+            keys: Arc::new(keys),
+            current: Default::default(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Current key and its index
+    pub fn current(&self) -> Option<(usize, &str)> {
+        if self.keys.is_empty() {
+            return None;
+        }
+        let index = self.current.load(Ordering::Relaxed) % self.keys.len();
+        Some((index, &self.keys[index]))
+    }
+
+    /// Switch to next key (no-op if other request already rotated away from `index`)
+    pub fn rotate(&self, index: usize) {
+        let next = (index + 1) % self.keys.len().max(1);
+        let _ = self
+            .current
+            .compare_exchange(index, next, Ordering::Relaxed, Ordering::Relaxed);
+    }
+}
+
+/// Whether HTTP status indicates that API key quota is exceeded
+pub(crate) fn is_quota_exceeded(status: u16) -> bool {
+    matches!(status, 403 | 429)
+}
@@ -0,0 +1,219 @@
+use std::{collections::VecDeque, time::Duration};
+
+use futures::Stream;
+use heliosphere_core::block::{Block, BlockBy};
+
+use super::{Consistency, RpcClient};
+
+/// Block stream options
+#[derive(Debug, Clone)]
+pub struct BlockStreamOptions {
+    start: Option<u64>,
+    solidified: bool,
+    concurrency: usize,
+    max_reorg_depth: usize,
+    poll_interval: Option<Duration>,
+}
+
+impl Default for BlockStreamOptions {
+    fn default() -> Self {
+        Self {
+            start: None,
+            solidified: false,
+            concurrency: 8,
+            max_reorg_depth: 32,
+            poll_interval: None,
+        }
+    }
+}
+
+impl BlockStreamOptions {
+    /// Start from given block number (default: current head)
+    pub fn with_start(mut self, number: u64) -> Self {
+        self.start = Some(number);
+        self
+    }
+
+    /// Follow solidified blocks only (no reorgs)
+    pub fn solidified(mut self) -> Self {
+        self.solidified = true;
+        self
+    }
+
+    /// Max number of blocks fetched in parallel while backfilling (default 8)
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Number of recent blocks kept to resolve reorgs (default 32)
+    pub fn with_max_reorg_depth(mut self, depth: usize) -> Self {
+        self.max_reorg_depth = depth.max(1);
+        self
+    }
+
+    /// Set custom head poll interval (default is client poll interval)
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = Some(interval);
+        self
+    }
+
+    fn consistency(&self) -> Consistency {
+        if self.solidified {
+            Consistency::Solidified
+        } else {
+            Consistency::Latest
+        }
+    }
+}
+
+/// Block stream event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockEvent {
+    /// Next block on current branch
+    Block(Block),
+    /// Fullnode switched branch: `dropped` blocks were replaced by `new` ones (both ascending)
+    Reorg {
+        /// Previously emitted blocks which are no longer on the main branch
+        dropped: Vec<Block>,
+        /// Blocks of the new branch, starting from the fork point
+        new: Vec<Block>,
+    },
+}
+
+struct BlockFollower {
+    client: RpcClient,
+    options: BlockStreamOptions,
+    poll_interval: Duration,
+    next_number: Option<u64>,
+    recent: VecDeque<Block>,
+    queue: VecDeque<BlockEvent>,
+}
+
+impl BlockFollower {
+    async fn next_event(&mut self) -> Result<BlockEvent, crate::Error> {
+        loop {
+            if let Some(event) = self.queue.pop_front() {
+                return Ok(event);
+            }
+            if !self.poll().await? {
+                tokio::time::sleep(self.poll_interval).await;
+            }
+        }
+    }
+
+    /// Fetch next batch of blocks, returns false if there are no new blocks
+    async fn poll(&mut self) -> Result<bool, crate::Error> {
+        let head = self
+            .client
+            .get_head_block(self.options.consistency())
+            .await?
+            .block_number();
+        let start = *self.next_number.get_or_insert(head);
+        if start > head {
+            return Ok(false);
+        }
+        let end = head.min(start + self.options.concurrency as u64 * 100 - 1);
+        let blocks = self
+            .client
+            .get_block_range_with(
+                start..end + 1,
+                self.options.consistency(),
+                self.options.concurrency,
+            )
+            .await?;
+        for block in blocks {
+            if !self.push(block).await? {
+                break; // reorg, rest of the batch may belong to old branch
+            }
+        }
+        Ok(true)
+    }
+
+    async fn fetch(&self, number: u64) -> Result<Block, crate::Error> {
+        let block = self
+            .client
+            .get_block_with(BlockBy::Number(number), self.options.consistency())
+            .await?;
+        if block.block_number() != number {
+            return Err(crate::Error::UnknownResponse(format!(
+                "requested block {} got {}",
+                number,
+                block.block_number()
+            )));
+        }
+        Ok(block)
+    }
+
+    /// Append block to current branch or resolve reorg, returns false on reorg.
+    /// Fails if fork point is older than remembered blocks (they are forgotten to follow new branch)
+    async fn push(&mut self, block: Block) -> Result<bool, crate::Error> {
+        let extends = self
+            .recent
+            .back()
+            .map(|last| block.block_header.raw_data.parent_hash == last.block_id.0)
+            .unwrap_or(true);
+        if extends {
+            self.next_number = Some(block.block_number() + 1);
+            self.remember(block.clone());
+            self.queue.push_back(BlockEvent::Block(block));
+            return Ok(true);
+        }
+        // remembered blocks are only replaced once the whole new branch is fetched
+        let mut new = vec![block];
+        let mut fork = None;
+        for (index, last) in self.recent.iter().enumerate().rev() {
+            let candidate = new.last().expect("Never empty");
+            if candidate.block_header.raw_data.parent_hash == last.block_id.0 {
+                fork = Some(index);
+                break;
+            }
+            new.push(self.fetch(last.block_number()).await?);
+        }
+        let Some(fork) = fork else {
+            let depth = self.recent.len();
+            self.recent.clear();
+            return Err(crate::Error::ReorgTooDeep(depth));
+        };
+        let dropped = self.recent.drain(fork + 1..).collect();
+        new.reverse();
+        self.next_number = new.last().map(|x| x.block_number() + 1);
+        for block in &new {
+            self.remember(block.clone());
+        }
+        self.queue.push_back(BlockEvent::Reorg { dropped, new });
+        Ok(false)
+    }
+
+    fn remember(&mut self, block: Block) {
+        self.recent.push_back(block);
+        while self.recent.len() > self.options.max_reorg_depth {
+            self.recent.pop_front();
+        }
+    }
+}
+
+impl RpcClient {
+    /** Stream of blocks starting from given height, following the chain head.
+     ** Gaps are backfilled with bounded concurrency; branch switches are reported as `BlockEvent::Reorg`,
+     ** deeper ones than max reorg depth as `Error::ReorgTooDeep` (stream continues on new branch).
+     ** RPC errors are yielded as stream items, polling continues on next poll.
+     */
+    pub fn block_stream(
+        &self,
+        options: BlockStreamOptions,
+    ) -> impl Stream<Item = Result<BlockEvent, crate::Error>> + Send + 'static {
+        let follower = BlockFollower {
+            client: self.clone(),
+            poll_interval: options.poll_interval.unwrap_or(self.poll_interval),
+            next_number: options.start,
+            options,
+            recent: VecDeque::new(),
+            queue: VecDeque::new(),
+        };
+        futures::stream::unfold(follower, |mut follower| async move {
+            let event = follower.next_event().await;
+            Some((event, follower))
+        })
+    }
+}
@@ -0,0 +1,204 @@
+use std::time::Duration;
+
+use futures::{Stream, StreamExt};
+use heliosphere_core::transaction::{Transaction, TransactionId};
+use tokio::time::Instant;
+
+use super::{Consistency, RpcClient, TransactionReceipt};
+
+/// Level of confirmation to wait for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConfirmationLevel {
+    /// Transaction is included in a block known to the fullnode
+    Included,
+    /// Given number of blocks (including the one with transaction) are built on fullnode
+    Depth(u64),
+    /// Transaction is included in a solidified (irreversible) block
+    Solidified,
+}
+
+/// Transaction confirmation tracking options
+#[derive(Debug, Clone)]
+pub struct ConfirmationOptions {
+    level: ConfirmationLevel,
+    timeout: Option<Duration>,
+    poll_interval: Option<Duration>,
+}
+
+impl ConfirmationOptions {
+    /// Create new instance (no timeout, client poll interval)
+    pub fn new(level: ConfirmationLevel) -> Self {
+        Self {
+            level,
+            timeout: None,
+            poll_interval: None,
+        }
+    }
+
+    /// Set overall deadline for confirmation
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set custom poll interval (default is client poll interval)
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = Some(interval);
+        self
+    }
+}
+
+/// Transaction confirmation progress event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfirmationEvent {
+    /// Transaction is included in block (receipt from fullnode)
+    SeenInBlock(TransactionReceipt),
+    /// Number of blocks built on top of (and including) the transaction block
+    Confirmations(u64),
+    /// Requested confirmation level is reached, stream ends
+    Finalized(TransactionReceipt),
+}
+
+struct Tracker {
+    client: RpcClient,
+    txid: TransactionId,
+    expiration: u64,
+    level: ConfirmationLevel,
+    poll_interval: Duration,
+    deadline: Option<Instant>,
+    seen: Option<TransactionReceipt>,
+    confirmations: u64,
+    pending: Option<ConfirmationEvent>,
+}
+
+impl Tracker {
+    async fn next_event(&mut self) -> Result<ConfirmationEvent, crate::Error> {
+        if let Some(event) = self.pending.take() {
+            return Ok(event);
+        }
+        match self.deadline {
+            Some(deadline) => tokio::time::timeout_at(deadline, self.poll())
+                .await
+                .map_err(|_| crate::Error::Timeout)?,
+            None => self.poll().await,
+        }
+    }
+
+    async fn poll(&mut self) -> Result<ConfirmationEvent, crate::Error> {
+        loop {
+            if let Some(event) = self.poll_once().await? {
+                return Ok(event);
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    async fn poll_once(&mut self) -> Result<Option<ConfirmationEvent>, crate::Error> {
+        let head = self.client.get_head_block(Consistency::Latest).await?;
+        let receipt = self
+            .client
+            .get_tx_receipt_with(self.txid, Consistency::Latest)
+            .await?;
+        let receipt = match receipt {
+            Some(receipt) => receipt,
+            None if head.block_header.raw_data.timestamp > self.expiration => {
+                return Err(crate::Error::TxExpired(self.txid));
+            }
+            None => {
+                self.seen = None; // not included yet or dropped by reorg
+                return Ok(None);
+            }
+        };
+        if self.seen.is_none() {
+            self.seen = Some(receipt.clone());
+            self.confirmations = 0;
+            if self.level == ConfirmationLevel::Included {
+                self.pending = Some(self.finalize(receipt.clone())?);
+            }
+            return Ok(Some(ConfirmationEvent::SeenInBlock(receipt)));
+        }
+        let confirmations = (head.block_number() + 1).saturating_sub(receipt.block_number);
+        let reached = match self.level {
+            ConfirmationLevel::Included => true,
+            ConfirmationLevel::Depth(depth) => confirmations >= depth,
+            ConfirmationLevel::Solidified => {
+                let solid = self
+                    .client
+                    .get_tx_receipt_with(self.txid, Consistency::Solidified)
+                    .await?;
+                match solid {
+                    Some(solid) => return Ok(Some(self.finalize(solid)?)),
+                    None => false,
+                }
+            }
+        };
+        if reached {
+            return Ok(Some(self.finalize(receipt)?));
+        }
+        if confirmations != self.confirmations {
+            self.confirmations = confirmations;
+            return Ok(Some(ConfirmationEvent::Confirmations(confirmations)));
+        }
+        Ok(None)
+    }
+
+    fn finalize(&self, receipt: TransactionReceipt) -> Result<ConfirmationEvent, crate::Error> {
+        if receipt.is_success() {
+            Ok(ConfirmationEvent::Finalized(receipt))
+        } else {
+            Err(crate::Error::TxFailed(Box::new(receipt)))
+        }
+    }
+}
+
+impl RpcClient {
+    /** Track confirmation progress of broadcasted transaction.
+     ** Stream ends after `ConfirmationEvent::Finalized` or first error:
+     ** `Error::TxExpired` once head block passes tx expiration without inclusion,
+     ** `Error::Timeout` once deadline passes, `Error::TxFailed` if execution failed.
+     */
+    pub fn confirmation_stream(
+        &self,
+        tx: &Transaction,
+        options: ConfirmationOptions,
+    ) -> impl Stream<Item = Result<ConfirmationEvent, crate::Error>> + Send + 'static {
+        let tracker = Tracker {
+            client: self.clone(),
+            txid: tx.tx_id,
+            expiration: tx.raw_data.expiration,
+            level: options.level,
+            poll_interval: options.poll_interval.unwrap_or(self.poll_interval),
+            deadline: options.timeout.map(|timeout| Instant::now() + timeout),
+            seen: None,
+            confirmations: 0,
+            pending: None,
+        };
+        futures::stream::unfold(Some(tracker), |tracker| async move {
+            let mut tracker = tracker?;
+            match tracker.next_event().await {
+                Ok(event) => {
+                    let done = matches!(event, ConfirmationEvent::Finalized(_));
+                    Some((Ok(event), (!done).then_some(tracker)))
+                }
+                Err(e) => Some((Err(e), None)),
+            }
+        })
+    }
+
+    /// Await transaction confirmation with given level and deadline
+    pub async fn await_confirmation_with(
+        &self,
+        tx: &Transaction,
+        options: ConfirmationOptions,
+    ) -> Result<TransactionReceipt, crate::Error> {
+        let mut events = std::pin::pin!(self.confirmation_stream(tx, options));
+        while let Some(event) = events.next().await {
+            if let ConfirmationEvent::Finalized(receipt) = event? {
+                return Ok(receipt);
+            }
+        }
+        Err(crate::Error::UnknownResponse(
+            "confirmation stream ended".to_owned(),
+        ))
+    }
+}
@@ -0,0 +1,268 @@
+use heliosphere_core::{transaction::Transaction, util::as_hex_buffer, Address};
+use serde::Deserialize;
+
+use super::{MethodCall, RpcClient};
+use crate::abi::{self, AbiFunction, AbiValue, ContractAbi};
+
+/// Deployed smart contract (as returned by getcontract)
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Hash)]
+pub struct SmartContract {
+    /// Contract address
+    pub contract_address: Address,
+    /// Deployer, pays for energy share not covered by callers
+    pub origin_address: Address,
+    /// Contract name
+    #[serde(default)]
+    pub name: String,
+    /// Contract ABI (empty if deployed without one)
+    #[serde(default)]
+    pub abi: ContractAbi,
+    /// Deployment bytecode
+    #[serde(deserialize_with = "as_hex_buffer::deserialize", default)]
+    pub bytecode: Vec<u8>,
+    /// Percentage of energy paid by caller (the rest is paid by origin account)
+    #[serde(default)]
+    pub consume_user_resource_percent: u64,
+    /// Maximum energy paid by origin account per call
+    #[serde(default)]
+    pub origin_energy_limit: u64,
+    /// Hash of runtime code
+    #[serde(deserialize_with = "as_hex_buffer::deserialize", default)]
+    pub code_hash: Vec<u8>,
+}
+
+/// Dynamic energy state of contract
+#[derive(Debug, Default, Clone, Copy, Deserialize, PartialEq, Eq, Hash)]
+pub struct ContractState {
+    /// Energy used in current maintenance cycle
+    #[serde(default)]
+    pub energy_usage: u64,
+    /// Energy penalty factor (scaled by 10,000, 0 means no penalty)
+    #[serde(default)]
+    pub energy_factor: u64,
+    /// Maintenance cycle of last update
+    #[serde(default)]
+    pub update_cycle: u64,
+}
+
+/// Smart contract with runtime code and energy state (as returned by getcontractinfo)
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Hash)]
+pub struct ContractInfo {
+    /// Contract definition
+    pub smart_contract: SmartContract,
+    /// Deployed runtime code
+    #[serde(
+        rename = "runtimecode",
+        deserialize_with = "as_hex_buffer::deserialize",
+        default
+    )]
+    pub runtime_code: Vec<u8>,
+    /// Dynamic energy state
+    #[serde(default)]
+    pub contract_state: ContractState,
+}
+
+/// Builder of contract call transaction (fee limit is estimated unless set)
+pub struct ContractTxBuilder {
+    client: RpcClient,
+    caller: Address,
+    contract: Address,
+    selector: String,
+    parameter: Vec<u8>,
+    call_value: u64,
+    fee_limit: Option<u64>,
+}
+
+impl ContractTxBuilder {
+    /// Create builder of call with ABI encoded `parameter`
+    pub fn new(
+        client: RpcClient,
+        caller: Address,
+        contract: Address,
+        selector: impl Into<String>,
+        parameter: Vec<u8>,
+    ) -> Self {
+        Self {
+            client,
+            caller,
+            contract,
+            selector: selector.into(),
+            parameter,
+            call_value: 0,
+            fee_limit: None,
+        }
+    }
+
+    /// Amount of TRX in SUN sent along with the call
+    pub fn with_call_value(mut self, call_value: u64) -> Self {
+        self.call_value = call_value;
+        self
+    }
+
+    /// Set fee limit in SUN instead of estimating it
+    pub fn with_fee_limit(mut self, fee_limit: u64) -> Self {
+        self.fee_limit = Some(fee_limit);
+        self
+    }
+
+    /// Build unsigned transaction
+    pub async fn build(self) -> Result<Transaction, crate::Error> {
+        self.client
+            .trigger_contract(
+                &MethodCall {
+                    caller: &self.caller,
+                    contract: &self.contract,
+                    selector: &self.selector,
+                    parameter: (&self.parameter).into(),
+                },
+                self.call_value,
+                self.fee_limit,
+            )
+            .await
+    }
+}
+
+/// Contract handle encoding calls from JSON arguments using ABI known at runtime
+#[derive(Clone)]
+pub struct DynamicContract {
+    client: RpcClient,
+    address: Address,
+    abi: ContractAbi,
+}
+
+impl DynamicContract {
+    /// Create new handle of contract at given address with given ABI
+    pub fn new(client: RpcClient, address: Address, abi: ContractAbi) -> Self {
+        Self {
+            client,
+            address,
+            abi,
+        }
+    }
+
+    /// Contract address
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Contract ABI
+    pub fn abi(&self) -> &ContractAbi {
+        &self.abi
+    }
+
+    /// Find function by signature (e.g. `transfer(address,uint256)`) or by name and number of arguments
+    pub fn function(&self, method: &str, arg_count: usize) -> Result<&AbiFunction, crate::Error> {
+        let mut candidates = self
+            .abi
+            .functions
+            .iter()
+            .filter(|x| match method.contains('(') {
+                true => x.signature() == method,
+                false => x.name == method && x.inputs.len() == arg_count,
+            });
+        match (candidates.next(), candidates.next()) {
+            (Some(function), None) => Ok(function),
+            (Some(_), Some(_)) => Err(crate::Error::AbiError(format!(
+                "ambiguous overloads of {}, use full signature",
+                method
+            ))),
+            (None, _) => Err(crate::Error::AbiError(format!(
+                "function {} with {} arguments not found",
+                method, arg_count
+            ))),
+        }
+    }
+
+    /// Query view method with JSON arguments (see `AbiParam::value_from_json`), returns decoded outputs
+    pub async fn query(
+        &self,
+        method: &str,
+        args: &[serde_json::Value],
+    ) -> Result<Vec<AbiValue>, crate::Error> {
+        let function = self.function(method, args.len())?;
+        let parameter = function.encode_json_args(args)?;
+        let resp = self
+            .client
+            .query_contract(&MethodCall {
+                caller: &self.address,
+                contract: &self.address,
+                selector: &function.signature(),
+                parameter: parameter.into(),
+            })
+            .await?;
+        function.decode_output(&resp.constant_result(0)?)
+    }
+
+    /// Build call of method with JSON arguments (see `AbiParam::value_from_json`) sent by `caller`
+    pub fn call(
+        &self,
+        caller: &Address,
+        method: &str,
+        args: &[serde_json::Value],
+    ) -> Result<ContractTxBuilder, crate::Error> {
+        let function = self.function(method, args.len())?;
+        Ok(ContractTxBuilder::new(
+            self.client.clone(),
+            *caller,
+            self.address,
+            function.signature(),
+            function.encode_json_args(args)?,
+        ))
+    }
+}
+
+impl RpcClient {
+    /// Query deployed smart contract. Returns `Error::ContractNotFound` if there is no contract at address
+    pub async fn get_contract(&self, contract: &Address) -> Result<SmartContract, crate::Error> {
+        let res: serde_json::Value = self
+            .api_post(
+                "/wallet/getcontract",
+                &serde_json::json!({ "value": contract.as_hex() }),
+            )
+            .await?;
+        if res.get("contract_address").is_none() {
+            return Err(crate::Error::ContractNotFound);
+        } // node returns empty object for unknown contracts
+        serde_json::from_value(res).map_err(|e| crate::Error::UnknownResponse(e.to_string()))
+    }
+
+    /// Query deployed smart contract with runtime code and energy state
+    pub async fn get_contract_info(
+        &self,
+        contract: &Address,
+    ) -> Result<ContractInfo, crate::Error> {
+        let res: serde_json::Value = self
+            .api_post(
+                "/wallet/getcontractinfo",
+                &serde_json::json!({ "value": contract.as_hex() }),
+            )
+            .await?;
+        if res.get("smart_contract").is_none() {
+            return Err(crate::Error::ContractNotFound);
+        }
+        serde_json::from_value(res).map_err(|e| crate::Error::UnknownResponse(e.to_string()))
+    }
+
+    /// Handle of contract using its on-chain ABI
+    pub async fn dynamic_contract(
+        &self,
+        contract: &Address,
+    ) -> Result<DynamicContract, crate::Error> {
+        let abi = self.get_contract(contract).await?.abi;
+        Ok(DynamicContract::new(self.clone(), *contract, abi))
+    }
+
+    /// Query view method with ABI values as arguments, decoding output of given canonical types
+    pub async fn query_values(
+        &self,
+        contract: &Address,
+        selector: &str,
+        args: &[AbiValue],
+        outputs: &[&str],
+    ) -> Result<Vec<AbiValue>, crate::Error> {
+        let resp = self
+            .query_contract(&MethodCall::new(contract, contract, selector, args)?)
+            .await?;
+        abi::decode(outputs, &resp.constant_result(0)?)
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+
+use heliosphere_core::{transaction::Transaction, util::as_hex_buffer, Address};
+use serde::Deserialize;
+
+use super::RpcClient;
+
+/// Bandwidth reserved for contract result of each contract in transaction
+const MAX_RESULT_SIZE_IN_TX: u64 = 64;
+/// Serialized size of signature (field tag, length and 65 bytes)
+const SIGNATURE_SIZE: u64 = 67;
+
+/// Predicted transaction fees in SUN (burned from sender balance)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CostPreview {
+    /// Bandwidth consumed by transaction (bytes)
+    pub bandwidth: u64,
+    /// Bandwidth not covered by staked or free bandwidth (account creation fee if recipient is new)
+    pub bandwidth_fee: u64,
+    /// Energy paid by sender
+    pub energy: u64,
+    /// Energy not covered by staked energy
+    pub energy_fee: u64,
+    /// Activation fee of new recipient account
+    pub activation_fee: u64,
+    /// Memo fee
+    pub memo_fee: u64,
+}
+
+impl CostPreview {
+    /// Total SUN burned
+    pub fn total(&self) -> u64 {
+        self.bandwidth_fee
+            .saturating_add(self.energy_fee)
+            .saturating_add(self.activation_fee)
+            .saturating_add(self.memo_fee)
+    }
+}
+
+/// Contract parameter fields relevant for fees
+#[derive(Deserialize)]
+pub(super) struct ContractValue {
+    pub owner_address: Address,
+    #[serde(default)]
+    pub to_address: Option<Address>,
+    #[serde(default)]
+    pub contract_address: Option<Address>,
+    #[serde(deserialize_with = "as_hex_buffer::deserialize", default)]
+    pub data: Vec<u8>,
+    #[serde(default)]
+    pub amount: u64,
+    #[serde(default)]
+    pub call_value: u64,
+}
+
+/// Type and parameter of first transaction contract
+pub(super) fn contract_value(tx: &Transaction) -> Result<(&str, ContractValue), crate::Error> {
+    let Some(contract) = tx.raw_data.contract.first() else {
+        return Err(crate::Error::UnknownResponse(
+            "transaction has no contract".to_owned(),
+        ));
+    };
+    let value = serde_json::from_value(contract.parameter["value"].clone())
+        .map_err(|e| crate::Error::UnknownResponse(e.to_string()))?;
+    Ok((&contract.r#type, value))
+}
+
+impl RpcClient {
+    /** Predict fees burned by transaction
+     ** tx: Transaction to broadcast (unsigned transaction is counted with one signature)
+     */
+    pub async fn preview_cost(&self, tx: &Transaction) -> Result<CostPreview, crate::Error> {
+        self.transaction_cost(tx, true).await
+    }
+
+    /// Predict fees, energy of contract call is only estimated if `with_energy` is set
+    pub(super) async fn transaction_cost(
+        &self,
+        tx: &Transaction,
+        with_energy: bool,
+    ) -> Result<CostPreview, crate::Error> {
+        let (kind, value) = contract_value(tx)?;
+        let params = self.get_chain_parameters().await?;
+        let resources = self.get_account_resources(&value.owner_address).await?;
+        let mut cost = CostPreview {
+            bandwidth: bandwidth(tx),
+            ..Default::default()
+        };
+
+        let staked_bandwidth = resources.net_limit.saturating_sub(resources.net_used);
+        let free_bandwidth = resources
+            .free_net_limit
+            .saturating_sub(resources.free_net_used);
+        let creates_account = match (kind, &value.to_address) {
+            ("TransferContract" | "TransferAssetContract", Some(to)) => {
+                match self.get_account(to).await {
+                    Ok(_) => false,
+                    Err(crate::Error::AccountNotFound) => true,
+                    Err(e) => return Err(e),
+                }
+            }
+            _ => false,
+        };
+        if creates_account {
+            // free bandwidth can not be used for account creation
+            if staked_bandwidth < cost.bandwidth {
+                cost.bandwidth_fee = chain_parameter(&params, "getCreateAccountFee")?;
+            }
+            cost.activation_fee =
+                chain_parameter(&params, "getCreateNewAccountFeeInSystemContract")?;
+        } else if staked_bandwidth < cost.bandwidth && free_bandwidth < cost.bandwidth {
+            cost.bandwidth_fee = cost.bandwidth * chain_parameter(&params, "getTransactionFee")?;
+        }
+
+        if tx.raw_data.data.as_ref().is_some_and(|x| !x.is_empty()) {
+            cost.memo_fee = chain_parameter(&params, "getMemoFee")?;
+        }
+
+        if let ("TriggerSmartContract", Some(contract), true) =
+            (kind, &value.contract_address, with_energy)
+        {
+            let estimate = self
+                .contract_energy(
+                    contract,
+                    &serde_json::json!({
+                        "owner_address": value.owner_address.as_hex(),
+                        "contract_address": contract.as_hex(),
+                        "data": hex::encode(&value.data),
+                        "call_value": value.call_value,
+                    }),
+                )
+                .await?;
+            cost.energy = estimate.caller_energy;
+            cost.energy_fee = cost
+                .energy
+                .saturating_sub(resources.energy_limit.saturating_sub(resources.energy_used))
+                * estimate.energy_fee;
+        }
+        Ok(cost)
+    }
+}
+
+/// Bandwidth of transaction: serialized size without result, signatures and reserved result size
+fn bandwidth(tx: &Transaction) -> u64 {
+    let raw_size = tx.raw_data_bytes.len() as u64;
+    let signatures = tx.signature.len().max(1) as u64;
+    1 + varint_size(raw_size)
+        + raw_size
+        + signatures * SIGNATURE_SIZE
+        + tx.raw_data.contract.len() as u64 * MAX_RESULT_SIZE_IN_TX
+}
+
+fn varint_size(value: u64) -> u64 {
+    (64 - value.max(1).leading_zeros() as u64).div_ceil(7)
+}
+
+fn chain_parameter(params: &BTreeMap<String, i64>, key: &str) -> Result<u64, crate::Error> {
+    params
+        .get(key)
+        .map(|x| *x as u64)
+        .ok_or_else(|| crate::Error::UnknownResponse(format!("{} not found", key)))
+}
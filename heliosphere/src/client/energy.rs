@@ -0,0 +1,223 @@
+use std::collections::BTreeMap;
+
+use heliosphere_core::Address;
+
+use super::{
+    types::{result_message, EstimateEnergyResponse},
+    ContractInfo, MethodCall, RpcClient, SmartContract,
+};
+
+/// Energy penalty factor precision (`ContractState::energy_factor` of 10,000 doubles energy)
+const ENERGY_FACTOR_PRECISION: u64 = 10_000;
+/// Error message of nodes with estimateenergy disabled (`vm.estimateEnergy` config)
+pub(crate) const ESTIMATE_ENERGY_DISABLED: &str = "does not support estimate energy";
+
+/// Source of estimated energy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EnergySource {
+    /// `/wallet/estimateenergy` (includes dynamic energy penalty)
+    EstimateEnergy,
+    /// `energy_used` of constant call, dynamic energy penalty is applied from contract state
+    /// unless reported by node
+    ConstantCall,
+}
+
+/// Energy estimate of contract call with its split between caller and contract origin
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EnergyEstimate {
+    /// How energy was estimated
+    pub source: EnergySource,
+    /// Total energy consumed by call (including dynamic energy penalty)
+    pub energy_required: u64,
+    /// Contract energy penalty factor (scaled by 10,000)
+    pub energy_factor: u64,
+    /// Energy paid by caller (staked energy or burned TRX)
+    pub caller_energy: u64,
+    /// Energy paid by contract origin (limited by `origin_energy_limit` and its staked energy)
+    pub origin_energy: u64,
+    /// Price of energy in SUN (`getEnergyFee` chain parameter)
+    pub energy_fee: u64,
+    /// Fee limit covering caller energy with safety margin, capped by `getMaxFeeLimit`
+    pub fee_limit: u64,
+}
+
+impl RpcClient {
+    /** Estimate energy required by given smart contract call
+     ** method_call: Call parameters
+     */
+    pub async fn estimate_energy(&self, method_call: &MethodCall<'_>) -> Result<u64, crate::Error> {
+        match self.required_energy(&call_payload(method_call, 0)).await? {
+            (energy, 0, EnergySource::ConstantCall) => {
+                let info = self.get_contract_info(method_call.contract).await?;
+                Ok(apply_energy_factor(
+                    energy,
+                    info.contract_state.energy_factor,
+                ))
+            }
+            (energy, _, _) => Ok(energy),
+        }
+    }
+
+    /** Estimate energy of given smart contract call split between caller and contract origin
+     ** method_call: Call parameters
+     ** call_value: Amount of TRX in SUN sent along with method call
+     */
+    pub async fn estimate_contract_energy(
+        &self,
+        method_call: &MethodCall<'_>,
+        call_value: u64,
+    ) -> Result<EnergyEstimate, crate::Error> {
+        self.contract_energy(method_call.contract, &call_payload(method_call, call_value))
+            .await
+    }
+
+    /** Estimate fee limit of given smart contract call (caller energy cost with safety margin)
+     ** method_call: Call parameters
+     */
+    pub async fn estimate_fee_limit(
+        &self,
+        method_call: &MethodCall<'_>,
+    ) -> Result<u64, crate::Error> {
+        Ok(self
+            .estimate_contract_energy(method_call, 0)
+            .await?
+            .fee_limit)
+    }
+
+    /// Energy estimate of call given by estimateenergy/triggerconstantcontract request payload
+    pub(super) async fn contract_energy(
+        &self,
+        contract: &Address,
+        payload: &serde_json::Value,
+    ) -> Result<EnergyEstimate, crate::Error> {
+        let info = self.get_contract_info(contract).await?;
+        let required = self.required_energy(payload).await?;
+        let origin_limit = self.origin_energy_limit(&info).await?;
+        let params = self.get_chain_parameters().await?;
+        split_energy(
+            required,
+            &info,
+            origin_limit,
+            &params,
+            self.fee_limit_margin,
+        )
+    }
+
+    /// Required energy, included penalty and source; falls back to constant call
+    /// if node does not support estimateenergy (disabled by default)
+    async fn required_energy(
+        &self,
+        payload: &serde_json::Value,
+    ) -> Result<(u64, u64, EnergySource), crate::Error> {
+        let resp: EstimateEnergyResponse =
+            match self.api_post("/wallet/estimateenergy", payload).await {
+                Ok(resp) => resp,
+                // API predating estimateenergy
+                Err(crate::Error::HttpStatus(404 | 405, _)) => {
+                    return self.constant_call_energy(payload).await
+                }
+                Err(e) => return Err(e),
+            };
+        match resp.result.code {
+            None => Ok((resp.energy_required, 0, EnergySource::EstimateEnergy)),
+            Some(code) => {
+                let message = result_message(&resp.result.message);
+                if message.contains(ESTIMATE_ENERGY_DISABLED) {
+                    self.constant_call_energy(payload).await
+                } else {
+                    Err(crate::Error::ContractQueryFailed(code, message))
+                }
+            }
+        }
+    }
+
+    /// Energy used by constant call and included penalty
+    async fn constant_call_energy(
+        &self,
+        payload: &serde_json::Value,
+    ) -> Result<(u64, u64, EnergySource), crate::Error> {
+        let resp = self
+            .constant_call("/wallet/triggerconstantcontract", payload)
+            .await?;
+        Ok((
+            resp.energy_used,
+            resp.energy_penalty,
+            EnergySource::ConstantCall,
+        ))
+    }
+
+    /// Energy contract origin is able to pay per call
+    async fn origin_energy_limit(&self, info: &ContractInfo) -> Result<u64, crate::Error> {
+        let contract = &info.smart_contract;
+        if !origin_pays(contract) {
+            return Ok(0);
+        }
+        let resources = self.get_account_resources(&contract.origin_address).await?;
+        Ok(resources
+            .energy_limit
+            .saturating_sub(resources.energy_used)
+            .min(contract.origin_energy_limit))
+    }
+}
+
+fn call_payload(method_call: &MethodCall<'_>, call_value: u64) -> serde_json::Value {
+    serde_json::json!({
+        "owner_address": method_call.caller.as_hex(),
+        "contract_address": method_call.contract.as_hex(),
+        "function_selector": method_call.selector,
+        "parameter": hex::encode(&method_call.parameter),
+        "call_value": call_value,
+    })
+}
+
+/// Whether contract origin pays share of call energy
+pub(crate) fn origin_pays(contract: &SmartContract) -> bool {
+    contract.consume_user_resource_percent < 100 && contract.origin_energy_limit > 0
+}
+
+/// Energy estimate from required energy (with included penalty and source), contract info,
+/// energy origin is able to pay and chain parameters
+pub(crate) fn split_energy(
+    (energy, penalty, source): (u64, u64, EnergySource),
+    info: &ContractInfo,
+    origin_limit: u64,
+    params: &BTreeMap<String, i64>,
+    fee_limit_margin: u64,
+) -> Result<EnergyEstimate, crate::Error> {
+    let energy_factor = info.contract_state.energy_factor;
+    let energy_required = match (penalty, source) {
+        (0, EnergySource::ConstantCall) => apply_energy_factor(energy, energy_factor),
+        _ => energy,
+    };
+    let origin_energy = energy_required
+        .saturating_mul(100 - info.smart_contract.consume_user_resource_percent.min(100))
+        / 100;
+    let origin_energy = origin_energy.min(origin_limit);
+    let caller_energy = energy_required - origin_energy;
+
+    let energy_fee = *params
+        .get("getEnergyFee")
+        .ok_or_else(|| crate::Error::UnknownResponse("getEnergyFee not found".to_owned()))?
+        as u64;
+    let fee_limit = caller_energy
+        .saturating_mul(energy_fee)
+        .saturating_mul(100 + fee_limit_margin)
+        / 100;
+    let fee_limit = match params.get("getMaxFeeLimit") {
+        Some(max_fee_limit) => fee_limit.min(*max_fee_limit as u64),
+        None => fee_limit,
+    };
+    Ok(EnergyEstimate {
+        source,
+        energy_required,
+        energy_factor,
+        caller_energy,
+        origin_energy,
+        energy_fee,
+        fee_limit,
+    })
+}
+
+pub(crate) fn apply_energy_factor(energy: u64, energy_factor: u64) -> u64 {
+    energy.saturating_mul(ENERGY_FACTOR_PRECISION + energy_factor) / ENERGY_FACTOR_PRECISION
+}
@@ -0,0 +1,491 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use heliosphere_core::{
+    block::BlockId,
+    transaction::TransactionId,
+    util::{as_hex_array, as_hex_buffer, as_hex_number},
+    Address,
+};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{ContractResult, MethodCall, RevertReason, RpcClient};
+
+/// Block selector of eth-compatible JSON-RPC
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EthBlockTag {
+    /// Latest block
+    #[default]
+    Latest,
+    /// Genesis block
+    Earliest,
+    /// Pending state
+    Pending,
+    /// Block by number
+    Number(u64),
+}
+
+impl Serialize for EthBlockTag {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Latest => serializer.serialize_str("latest"),
+            Self::Earliest => serializer.serialize_str("earliest"),
+            Self::Pending => serializer.serialize_str("pending"),
+            Self::Number(number) => serializer.serialize_str(&format!("{:#x}", number)),
+        }
+    }
+}
+
+impl From<u64> for EthBlockTag {
+    fn from(number: u64) -> Self {
+        Self::Number(number)
+    }
+}
+
+/// Ser/De Tron address as 20-byte eth address (via alloy conversions)
+mod as_eth_address {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(address: &Address, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&alloy_primitives::Address::from(*address).to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Address, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse::<alloy_primitives::Address>()
+            .map(Into::into)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Ser/De optional Tron address as 20-byte eth address
+mod as_eth_address_opt {
+    use super::*;
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Address>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|s| {
+                s.parse::<alloy_primitives::Address>()
+                    .map(Into::into)
+                    .map_err(serde::de::Error::custom)
+            })
+            .transpose()
+    }
+}
+
+/// Deserialize list of 32-byte hex topics
+fn de_topics<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<[u8; 32]>, D::Error> {
+    #[derive(Deserialize)]
+    struct Topic(#[serde(with = "as_hex_array")] [u8; 32]);
+    Ok(Vec::<Topic>::deserialize(deserializer)?
+        .into_iter()
+        .map(|x| x.0)
+        .collect())
+}
+
+/// Log filter of `eth_getLogs`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LogFilter {
+    from_block: Option<EthBlockTag>,
+    to_block: Option<EthBlockTag>,
+    block_hash: Option<BlockId>,
+    addresses: Vec<Address>,
+    topics: Vec<Option<Vec<[u8; 32]>>>,
+}
+
+impl LogFilter {
+    /// Create new empty filter (latest block, any contract)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set first block of range
+    pub fn from_block(mut self, block: impl Into<EthBlockTag>) -> Self {
+        self.from_block = Some(block.into());
+        self
+    }
+
+    /// Set last block of range (inclusive)
+    pub fn to_block(mut self, block: impl Into<EthBlockTag>) -> Self {
+        self.to_block = Some(block.into());
+        self
+    }
+
+    /// Filter logs of single block (exclusive with block range)
+    pub fn block_hash(mut self, block_hash: BlockId) -> Self {
+        self.block_hash = Some(block_hash);
+        self
+    }
+
+    /// Add emitting contract address (logs of any added contract match)
+    pub fn address(mut self, address: Address) -> Self {
+        self.addresses.push(address);
+        self
+    }
+
+    /// Filter by event signature hash (topic 0)
+    pub fn event(self, signature_hash: [u8; 32]) -> Self {
+        self.topic(0, signature_hash)
+    }
+
+    /// Add accepted value of topic at given position (any added value matches)
+    pub fn topic(mut self, index: usize, value: [u8; 32]) -> Self {
+        if self.topics.len() <= index {
+            self.topics.resize(index + 1, None);
+        }
+        self.topics[index].get_or_insert_with(Vec::new).push(value);
+        self
+    }
+}
+
+impl Serialize for LogFilter {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let hex = |x: &[u8]| format!("0x{}", hex::encode(x));
+        let mut filter = serde_json::Map::new();
+        if let Some(block) = self.from_block {
+            filter.insert("fromBlock".into(), serde_json::json!(block));
+        }
+        if let Some(block) = self.to_block {
+            filter.insert("toBlock".into(), serde_json::json!(block));
+        }
+        if let Some(block_hash) = self.block_hash {
+            filter.insert("blockHash".into(), hex(&block_hash.0).into());
+        }
+        match self.addresses.as_slice() {
+            [] => {}
+            [address] => {
+                filter.insert(
+                    "address".into(),
+                    alloy_primitives::Address::from(*address).to_string().into(),
+                );
+            }
+            addresses => {
+                filter.insert(
+                    "address".into(),
+                    addresses
+                        .iter()
+                        .map(|x| alloy_primitives::Address::from(*x).to_string())
+                        .collect(),
+                );
+            }
+        }
+        if !self.topics.is_empty() {
+            filter.insert(
+                "topics".into(),
+                self.topics
+                    .iter()
+                    .map(|topic| match topic.as_deref() {
+                        None => serde_json::Value::Null,
+                        Some([value]) => hex(value).into(),
+                        Some(values) => values.iter().map(|x| hex(x)).collect(),
+                    })
+                    .collect(),
+            );
+        }
+        filter.serialize(serializer)
+    }
+}
+
+/// Event log returned by eth-compatible JSON-RPC
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct EthLog {
+    /// Emitting contract
+    #[serde(with = "as_eth_address")]
+    pub address: Address,
+    /// Indexed topics (topic 0 is event signature hash)
+    #[serde(deserialize_with = "de_topics")]
+    pub topics: Vec<[u8; 32]>,
+    /// Non-indexed data
+    #[serde(with = "as_hex_buffer")]
+    pub data: Vec<u8>,
+    /// Block number
+    #[serde(with = "as_hex_number")]
+    pub block_number: u64,
+    /// Block id
+    pub block_hash: BlockId,
+    /// Transaction id
+    pub transaction_hash: TransactionId,
+    /// Transaction index in block
+    #[serde(with = "as_hex_number")]
+    pub transaction_index: u64,
+    /// Log index in block
+    #[serde(with = "as_hex_number")]
+    pub log_index: u64,
+    /// Whether log was removed due to reorg
+    #[serde(default)]
+    pub removed: bool,
+}
+
+/// Block header returned by eth-compatible JSON-RPC (with transaction ids)
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct EthBlock {
+    /// Block number
+    #[serde(with = "as_hex_number")]
+    pub number: u64,
+    /// Block id
+    pub hash: BlockId,
+    /// Parent block id
+    pub parent_hash: BlockId,
+    /// Block timestamp (seconds)
+    #[serde(with = "as_hex_number")]
+    pub timestamp: u64,
+    /// Block producer
+    #[serde(with = "as_eth_address")]
+    pub miner: Address,
+    /// Energy used by block transactions
+    #[serde(with = "as_hex_number", default)]
+    pub gas_used: u64,
+    /// Block energy limit
+    #[serde(with = "as_hex_number", default)]
+    pub gas_limit: u64,
+    /// Transaction ids
+    #[serde(default)]
+    pub transactions: Vec<TransactionId>,
+}
+
+/// Transaction receipt returned by eth-compatible JSON-RPC
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct EthReceipt {
+    /// Transaction id
+    pub transaction_hash: TransactionId,
+    /// Transaction index in block
+    #[serde(with = "as_hex_number")]
+    pub transaction_index: u64,
+    /// Block id
+    pub block_hash: BlockId,
+    /// Block number
+    #[serde(with = "as_hex_number")]
+    pub block_number: u64,
+    /// Sender
+    #[serde(with = "as_eth_address")]
+    pub from: Address,
+    /// Recipient (None for contract creation)
+    #[serde(deserialize_with = "as_eth_address_opt::deserialize", default)]
+    pub to: Option<Address>,
+    /// Created contract
+    #[serde(deserialize_with = "as_eth_address_opt::deserialize", default)]
+    pub contract_address: Option<Address>,
+    /// Energy used by transaction
+    #[serde(with = "as_hex_number")]
+    pub gas_used: u64,
+    /// Energy used in block up to and including this transaction
+    #[serde(with = "as_hex_number")]
+    pub cumulative_gas_used: u64,
+    /// Energy price in SUN
+    #[serde(with = "as_hex_number", default)]
+    pub effective_gas_price: u64,
+    /// Execution status (`0x1` - success)
+    #[serde(with = "as_hex_number")]
+    pub status: u64,
+    /// Event logs
+    #[serde(default)]
+    pub logs: Vec<EthLog>,
+}
+
+impl EthReceipt {
+    /// Whether transaction succeeded
+    pub fn is_success(&self) -> bool {
+        self.status == 1
+    }
+}
+
+#[derive(Serialize)]
+struct Request<'a, P> {
+    jsonrpc: &'static str,
+    id: u64,
+    method: &'a str,
+    params: P,
+}
+
+#[derive(Deserialize)]
+struct ResponseError {
+    code: i64,
+    message: String,
+    #[serde(default)]
+    data: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct Response<R> {
+    #[serde(default = "Option::default")]
+    result: Option<R>,
+    #[serde(default)]
+    error: Option<ResponseError>,
+}
+
+/// Call object of `eth_call` and `eth_estimateGas`
+#[derive(Serialize)]
+struct CallRequest {
+    #[serde(with = "as_eth_address")]
+    from: Address,
+    #[serde(with = "as_eth_address")]
+    to: Address,
+    #[serde(with = "as_hex_buffer")]
+    data: Vec<u8>,
+    #[serde(with = "as_hex_number")]
+    value: u64,
+}
+
+impl CallRequest {
+    fn new(method_call: &MethodCall<'_>, value: u64) -> Self {
+        let mut data = alloy_primitives::keccak256(method_call.selector.as_bytes())[..4].to_vec();
+        data.extend_from_slice(&method_call.parameter);
+        Self {
+            from: *method_call.caller,
+            to: *method_call.contract,
+            data,
+            value,
+        }
+    }
+}
+
+/// Client of eth-compatible JSON-RPC served by java-tron (`/jsonrpc`)
+#[derive(Clone)]
+pub struct JsonRpcClient {
+    client: RpcClient,
+    path: String,
+    next_id: Arc<AtomicU64>,
+}
+
+impl JsonRpcClient {
+    /// Create new instance sending requests to `/jsonrpc` of given client
+    pub fn new(client: RpcClient) -> Self {
+        Self {
+            client,
+            path: "/jsonrpc".to_owned(),
+            next_id: Arc::new(AtomicU64::new(1)),
+        }
+    }
+
+    /// Set custom endpoint path (default `/jsonrpc`)
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Send JSON-RPC request, `Error::JsonRpcError` is returned for error responses
+    pub async fn request<P, R>(&self, method: &str, params: P) -> Result<R, crate::Error>
+    where
+        P: Serialize,
+        R: DeserializeOwned,
+    {
+        let resp: Response<R> = self.send(method, params).await?;
+        match (resp.result, resp.error) {
+            (_, Some(error)) => Err(crate::Error::JsonRpcError(error.code, error.message)),
+            (Some(result), None) => Ok(result),
+            (None, None) => serde_json::from_value(serde_json::Value::Null)
+                .map_err(|e| crate::Error::UnknownResponse(e.to_string())),
+        }
+    }
+
+    async fn send<P, R>(&self, method: &str, params: P) -> Result<Response<R>, crate::Error>
+    where
+        P: Serialize,
+        R: DeserializeOwned,
+    {
+        self.client
+            .api_post(
+                &self.path,
+                &Request {
+                    jsonrpc: "2.0",
+                    id: self.next_id.fetch_add(1, Ordering::Relaxed),
+                    method,
+                    params,
+                },
+            )
+            .await
+    }
+
+    /// Latest block number
+    pub async fn block_number(&self) -> Result<u64, crate::Error> {
+        let number: String = self.request("eth_blockNumber", ()).await?;
+        parse_hex_number(&number)
+    }
+
+    /** Execute view method call (`eth_call`), returns raw output.
+     ** Revert is reported as `Error::ContractExecutionFailed` with decoded reason
+     */
+    pub async fn call(
+        &self,
+        method_call: &MethodCall<'_>,
+        block: EthBlockTag,
+    ) -> Result<Vec<u8>, crate::Error> {
+        let resp: Response<String> = self
+            .send("eth_call", (CallRequest::new(method_call, 0), block))
+            .await?;
+        match (resp.result, resp.error) {
+            (_, Some(error)) => {
+                let data = error
+                    .data
+                    .as_ref()
+                    .and_then(|x| x.as_str())
+                    .and_then(|x| hex::decode(x.trim_start_matches("0x")).ok());
+                match data {
+                    Some(data) => Err(crate::Error::ContractExecutionFailed(
+                        ContractResult::Revert,
+                        RevertReason::decode(&data),
+                    )),
+                    None => Err(crate::Error::JsonRpcError(error.code, error.message)),
+                }
+            }
+            (Some(result), None) => hex::decode(result.trim_start_matches("0x"))
+                .map_err(|e| crate::Error::UnknownResponse(e.to_string())),
+            (None, None) => Err(crate::Error::UnknownResponse(
+                "empty eth_call result".to_owned(),
+            )),
+        }
+    }
+
+    /// Estimate energy of method call with `value` SUN attached (`eth_estimateGas`)
+    pub async fn estimate_gas(
+        &self,
+        method_call: &MethodCall<'_>,
+        value: u64,
+    ) -> Result<u64, crate::Error> {
+        let gas: String = self
+            .request("eth_estimateGas", (CallRequest::new(method_call, value),))
+            .await?;
+        parse_hex_number(&gas)
+    }
+
+    /// Get event logs matching filter (`eth_getLogs`)
+    pub async fn get_logs(&self, filter: &LogFilter) -> Result<Vec<EthLog>, crate::Error> {
+        self.request("eth_getLogs", (filter,)).await
+    }
+
+    /// Get block header with transaction ids (None if block does not exist yet)
+    pub async fn get_block_by_number(
+        &self,
+        block: EthBlockTag,
+    ) -> Result<Option<EthBlock>, crate::Error> {
+        self.request("eth_getBlockByNumber", (block, false)).await
+    }
+
+    /// Get transaction receipt (None if transaction is unknown or not included yet)
+    pub async fn get_transaction_receipt(
+        &self,
+        txid: TransactionId,
+    ) -> Result<Option<EthReceipt>, crate::Error> {
+        self.request("eth_getTransactionReceipt", (format!("0x{}", txid),))
+            .await
+    }
+}
+
+fn parse_hex_number(s: &str) -> Result<u64, crate::Error> {
+    u64::from_str_radix(s.trim_start_matches("0x"), 16)
+        .map_err(|e| crate::Error::UnknownResponse(e.to_string()))
+}
+
+impl RpcClient {
+    /// Client of eth-compatible JSON-RPC (`/jsonrpc`) sharing this client's transport and settings
+    pub fn json_rpc(&self) -> JsonRpcClient {
+        JsonRpcClient::new(self.clone())
+    }
+}
@@ -1,21 +1,67 @@
-use std::{collections::BTreeMap, time::Duration};
+use std::{borrow::Cow, collections::BTreeMap, ops::Range, sync::Arc, time::Duration};
 
+use futures::{StreamExt, TryStreamExt};
 use heliosphere_core::{
     block::{Block, BlockBy, BlockHeader},
     transaction::{Transaction, TransactionId},
     Address,
 };
 use heliosphere_signer::signer::Signer;
-use reqwest::{Client, IntoUrl, Url};
 use serde::{de::DeserializeOwned, Serialize};
+use url::Url;
+
+use crate::abi::{self, AbiType, AbiValue};
 
 use self::types::{
-    AccountBalanceResponse, BroadcastTxResponse, ChainParametersResponse, QueryContractResponse,
+    BlockListResponse, BroadcastTxResponse, ChainParametersResponse, QueryContractResponse,
     TransactionInfo, TriggerContractResponse,
 };
 
-mod types;
-pub use types::{AccountResources, ResourceType};
+mod api_key;
+use api_key::{ApiKeys, API_KEY_HEADER};
+mod block_stream;
+pub use block_stream::{BlockEvent, BlockStreamOptions};
+mod confirmation;
+pub use confirmation::{ConfirmationEvent, ConfirmationLevel, ConfirmationOptions};
+mod contract;
+pub use contract::{
+    ContractInfo, ContractState, ContractTxBuilder, DynamicContract, SmartContract,
+};
+mod cost;
+pub use cost::CostPreview;
+pub(crate) mod energy;
+pub use energy::{EnergyEstimate, EnergySource};
+mod json_rpc;
+pub use json_rpc::{EthBlock, EthBlockTag, EthLog, EthReceipt, JsonRpcClient, LogFilter};
+mod nft;
+pub use nft::{Trc1155, Trc1155Transfer, Trc721, Trc721Transfer};
+mod pool;
+pub use pool::{EndpointStatus, RoutingStrategy, RpcPool, RpcPoolBuilder};
+mod preflight;
+pub use preflight::{PreflightIssue, PreflightReport};
+mod quorum;
+pub use quorum::{EndpointAnswer, QuorumClient};
+mod retry;
+pub use retry::{RateLimiter, RetryPolicy};
+mod transport;
+#[cfg(feature = "reqwest")]
+pub use transport::ReqwestTransport;
+pub use transport::{HttpMethod, HttpRequest, HttpResponse, MockTransport, Transport};
+mod trc20;
+pub use trc20::{TokenAmount, Trc20, Trc20Amount, Trc20TxBuilder};
+mod trongrid;
+pub use trongrid::{
+    AccountTransaction, ContractEvent, InternalTransactionData, TokenInfo, Trc20Balance,
+    Trc20Transfer, TronGridAccount, TronGridClient, TronGridFilter, TronGridInternalTransaction,
+    TronGridPage, TronGridTransaction,
+};
+pub(crate) mod types;
+pub use types::{
+    Account, AccountResource, AccountResources, AssetBalance, BroadcastError, BroadcastErrorCode,
+    CallValueInfo, ContractResult, FrozenV2, InternalTransaction, Log, Permission, PermissionKey,
+    PermissionType, ResourceReceipt, ResourceType, RevertReason, TransactionReceipt,
+    TransactionResult, TransactionRet, UnfrozenV2, Vote,
+};
 
 /// Method call params
 pub struct MethodCall<'a> {
@@ -25,33 +71,115 @@ pub struct MethodCall<'a> {
     pub contract: &'a Address,
     /// Method signature string e.g. `transfer(address,uint256)`
     pub selector: &'a str,
-    /// ABI encoded arguments (e.g. with ethabi crate)
-    pub parameter: &'a [u8],
+    /// ABI encoded arguments (e.g. with `abi::encode`)
+    pub parameter: Cow<'a, [u8]>,
+}
+
+impl<'a> MethodCall<'a> {
+    /// Create method call with arguments encoded according to types of `selector`
+    pub fn new(
+        caller: &'a Address,
+        contract: &'a Address,
+        selector: &'a str,
+        args: &[AbiValue],
+    ) -> Result<Self, crate::Error> {
+        let kinds = selector
+            .find('(')
+            .and_then(|i| selector[i..].parse::<AbiType>().ok())
+            .ok_or_else(|| crate::Error::AbiError(format!("invalid selector {}", selector)))?;
+        if !AbiValue::Tuple(args.to_vec()).is_of(&kinds) {
+            return Err(crate::Error::AbiError(format!(
+                "arguments do not match selector {}",
+                selector
+            )));
+        }
+        Ok(Self {
+            caller,
+            contract,
+            selector,
+            parameter: Cow::Owned(abi::encode(args)),
+        })
+    }
+}
+
+/// Consistency level of queried state
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Consistency {
+    /// Latest state known to fullnode (`/wallet/*` API)
+    #[default]
+    Latest,
+    /// Solidified (irreversible) state from solidity node (`/walletsolidity/*` API)
+    Solidified,
+}
+
+impl Consistency {
+    /// API path of given method e.g. `/walletsolidity/getaccount`
+    pub fn path(&self, method: &str) -> String {
+        match self {
+            Self::Latest => format!("/wallet/{}", method),
+            Self::Solidified => format!("/walletsolidity/{}", method),
+        }
+    }
 }
 
 /// Builder struct for RpcClient
 pub struct RpcClientBuilder {
-    client: Option<Client>,
+    transport: Option<Arc<dyn Transport>>,
     poll_interval: Duration,
+    max_concurrency: usize,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+    timeout: Option<Duration>,
+    headers: Vec<(String, String)>,
+    api_keys: Vec<String>,
+    fee_limit_margin: u64,
+    preflight: bool,
     rpc_url: Url,
+    solidity_url: Option<Url>,
 }
 
 impl RpcClientBuilder {
     /// Create new instance
     pub fn new<U>(rpc_url: U) -> Result<Self, crate::Error>
     where
-        U: IntoUrl,
+        U: AsRef<str>,
     {
         Ok(Self {
-            client: None,
+            transport: None,
             poll_interval: Duration::from_secs(5),
-            rpc_url: rpc_url.into_url().map_err(|_| crate::Error::InvalidUrl)?,
+            max_concurrency: 8,
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: None,
+            timeout: None,
+            headers: Vec::new(),
+            api_keys: Vec::new(),
+            fee_limit_margin: 20,
+            preflight: false,
+            rpc_url: Url::parse(rpc_url.as_ref()).map_err(|_| crate::Error::InvalidUrl)?,
+            solidity_url: None,
         })
     }
 
+    /// Set separate solidity node url serving `/walletsolidity/*` API (default is fullnode url)
+    pub fn with_solidity_url<U>(mut self, solidity_url: U) -> Result<Self, crate::Error>
+    where
+        U: AsRef<str>,
+    {
+        self.solidity_url =
+            Some(Url::parse(solidity_url.as_ref()).map_err(|_| crate::Error::InvalidUrl)?);
+        Ok(self)
+    }
+
     /// Set custom reqwest::Client instance
-    pub fn with_client(mut self, client: Client) -> Self {
-        self.client = Some(client);
+    #[cfg(feature = "reqwest")]
+    pub fn with_client(mut self, client: reqwest::Client) -> Self {
+        self.transport = Some(Arc::new(ReqwestTransport::new(client)));
+        self
+    }
+
+    /// Set custom HTTP transport (default is `ReqwestTransport`, required when `reqwest` feature is disabled)
+    pub fn with_transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
         self
     }
 
@@ -61,47 +189,150 @@ impl RpcClientBuilder {
         self
     }
 
+    /// Set max number of parallel requests made by batch methods e.g. `get_block_range` (default 8)
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.max(1);
+        self
+    }
+
+    /// Set retry policy for failed requests and retryable broadcast errors (default `RetryPolicy::default()`)
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Limit request rate to `rate` requests per second with bursts of up to `burst` requests
+    /// (zero, negative or NaN rate removes limit)
+    pub fn with_rate_limit(mut self, rate: f64, burst: u32) -> Self {
+        self.rate_limiter = (rate > 0.0).then(|| RateLimiter::new(rate, burst));
+        self
+    }
+
+    /// Set per-request timeout (each retry attempt has its own timeout)
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set TronGrid API key (sent as `TRON-PRO-API-KEY` header)
+    pub fn with_api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_keys = vec![key.into()];
+        self
+    }
+
+    /// Set several TronGrid API keys, next key is used when current one hits its quota (HTTP 403/429)
+    pub fn with_api_keys<K>(mut self, keys: impl IntoIterator<Item = K>) -> Self
+    where
+        K: Into<String>,
+    {
+        self.api_keys = keys.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Add custom header sent with every request
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Set safety margin in percent added to estimated fee limit of contract calls (default 20)
+    pub fn with_fee_limit_margin(mut self, percent: u64) -> Self {
+        self.fee_limit_margin = percent;
+        self
+    }
+
+    /// Run `preflight` check before each broadcast, failing with `Error::PreflightFailed`
+    /// when problems are found (default false)
+    pub fn with_preflight(mut self, enabled: bool) -> Self {
+        self.preflight = enabled;
+        self
+    }
+
     /// Build new RpcClient instance
     pub fn build(self) -> RpcClient {
         RpcClient {
+            solidity_url: self.solidity_url.unwrap_or_else(|| self.rpc_url.clone()),
             rpc_url: self.rpc_url,
-            client: self.client.unwrap_or_default(),
+            transport: self.transport.unwrap_or_else(default_transport),
             poll_interval: self.poll_interval,
+            max_concurrency: self.max_concurrency,
+            retry_policy: self.retry_policy,
+            rate_limiter: self.rate_limiter,
+            timeout: self.timeout,
+            headers: Arc::new(self.headers),
+            api_keys: ApiKeys::new(self.api_keys),
+            fee_limit_margin: self.fee_limit_margin,
+            preflight: self.preflight,
         }
     }
 }
 
+#[cfg(feature = "reqwest")]
+fn default_transport() -> Arc<dyn Transport> {
+    Arc::new(ReqwestTransport::default())
+}
+
+#[cfg(not(feature = "reqwest"))]
+fn default_transport() -> Arc<dyn Transport> {
+    Arc::new(transport::NoTransport)
+}
+
 /// RpcClient for creating and broadcasting transaction or interaction with smart contracts
 #[derive(Clone)]
 pub struct RpcClient {
     rpc_url: Url,
-    client: Client,
+    solidity_url: Url,
+    transport: Arc<dyn Transport>,
     poll_interval: Duration,
+    max_concurrency: usize,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+    timeout: Option<Duration>,
+    headers: Arc<Vec<(String, String)>>,
+    api_keys: ApiKeys,
+    fee_limit_margin: u64,
+    preflight: bool,
 }
 
 impl RpcClient {
     /// Create new RpcClient with default params
     pub fn new<U>(rpc_url: U) -> Result<Self, crate::Error>
     where
-        U: IntoUrl,
+        U: AsRef<str>,
     {
         Ok(RpcClientBuilder::new(rpc_url)?.build())
     }
 
+    /// Fullnode url
+    pub fn rpc_url(&self) -> &Url {
+        &self.rpc_url
+    }
+
+    /// Solidity node url
+    pub fn solidity_url(&self) -> &Url {
+        &self.solidity_url
+    }
+
+    /// Full url of API method, `/walletsolidity/*` methods are routed to solidity node
+    fn method_url(&self, method: &str) -> String {
+        let method = method.trim_start_matches('/');
+        let url = if method.starts_with("walletsolidity/") {
+            &self.solidity_url
+        } else {
+            &self.rpc_url
+        };
+        format!("{}/{}", url.as_str().trim_end_matches('/'), method)
+    }
+
     /// Send a POST request with json-serializable payload
     pub async fn api_post<P, R>(&self, method: &str, payload: &P) -> Result<R, crate::Error>
     where
         P: Serialize,
         R: DeserializeOwned,
     {
-        Ok(self
-            .client
-            .post(format!("{}/{}", self.rpc_url, method))
-            .json(payload)
-            .send()
-            .await?
-            .json()
-            .await?)
+        let body =
+            serde_json::to_value(payload).map_err(|e| crate::Error::ApiError(e.to_string()))?;
+        self.execute(HttpMethod::Post, method, Some(body)).await
     }
 
     /// Send a GET request
@@ -109,30 +340,118 @@ impl RpcClient {
     where
         R: DeserializeOwned,
     {
-        Ok(self
-            .client
-            .get(format!("{}/{}", self.rpc_url, method))
-            .send()
-            .await?
-            .json()
-            .await?)
+        self.execute(HttpMethod::Get, method, None).await
     }
 
-    /// Broadcast signed transaction
+    /// Send request applying headers, rate limit, timeout and retry policy
+    async fn execute<R>(
+        &self,
+        http_method: HttpMethod,
+        method: &str,
+        body: Option<serde_json::Value>,
+    ) -> Result<R, crate::Error>
+    where
+        R: DeserializeOwned,
+    {
+        let url = self.method_url(method);
+        let mut attempt = 1;
+        let mut rotations = 0;
+        loop {
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire().await;
+            }
+            let mut headers = self.headers.as_ref().clone();
+            let api_key = self.api_keys.current();
+            if let Some((_, key)) = api_key {
+                headers.push((API_KEY_HEADER.to_owned(), key.to_owned()));
+            }
+            let request = HttpRequest {
+                method: http_method,
+                url: url.clone(),
+                headers,
+                body: body.clone(),
+                timeout: self.timeout,
+            };
+            let (result, retry_after) = match self.transport.send(request).await {
+                Ok(resp) if resp.is_success() => (
+                    serde_json::from_slice(&resp.body)
+                        .map_err(|e| crate::Error::UnknownResponse(e.to_string())),
+                    None,
+                ),
+                Ok(resp) => {
+                    let retry_after = resp
+                        .header("Retry-After")
+                        .and_then(|x| x.parse().ok())
+                        .map(Duration::from_secs);
+                    let body = String::from_utf8_lossy(&resp.body).into_owned();
+                    (
+                        Err(crate::Error::HttpStatus(resp.status, body)),
+                        retry_after,
+                    )
+                }
+                Err(e) => (Err(e), None),
+            };
+            match (result, api_key) {
+                (Err(crate::Error::HttpStatus(status, _)), Some((index, _)))
+                    if api_key::is_quota_exceeded(status)
+                        && rotations + 1 < self.api_keys.len() =>
+                {
+                    self.api_keys.rotate(index);
+                    rotations += 1;
+                }
+                (Err(e), _)
+                    if attempt < self.retry_policy.max_attempts()
+                        && self.retry_policy.should_retry(&e) =>
+                {
+                    let backoff = self.retry_policy.backoff(attempt);
+                    tokio::time::sleep(retry_after.map_or(backoff, |x| x.max(backoff))).await;
+                    attempt += 1;
+                }
+                (result, _) => return result,
+            }
+        }
+    }
+
+    /// Broadcast signed transaction (already known transaction is treated as success)
     pub async fn broadcast_transaction(
         &self,
         tx: &Transaction,
+    ) -> Result<TransactionId, crate::Error> {
+        if self.preflight {
+            let report = self.preflight(tx).await?;
+            if !report.is_ok() {
+                return Err(crate::Error::PreflightFailed(Box::new(report)));
+            }
+        }
+        let mut attempt = 1;
+        loop {
+            match self.try_broadcast_transaction(tx).await {
+                Err(crate::Error::BroadcastFailed(e))
+                    if e.is_retryable() && attempt < self.retry_policy.max_attempts() =>
+                {
+                    tokio::time::sleep(self.retry_policy.backoff(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn try_broadcast_transaction(
+        &self,
+        tx: &Transaction,
     ) -> Result<TransactionId, crate::Error> {
         let resp: BroadcastTxResponse = self.api_post("/wallet/broadcasttransaction", tx).await?;
         match resp.code {
-            Some(err) => Err(crate::Error::TxConstructionFailed(
-                err,
-                hex::decode(&resp.message)
+            Some(BroadcastErrorCode::DupTransactionError) => Ok(tx.tx_id),
+            Some(code) => Err(crate::Error::BroadcastFailed(BroadcastError {
+                code,
+                message: hex::decode(&resp.message)
                     .ok()
                     .and_then(|x| String::from_utf8(x).ok())
                     .unwrap_or(resp.message),
-            )),
-            None => Ok(resp.txid),
+            })),
+            None => Ok(resp.txid.unwrap_or(tx.tx_id)),
         }
     }
 
@@ -144,8 +463,17 @@ impl RpcClient {
 
     /// Get block by id or number
     pub async fn get_block(&self, by: BlockBy) -> Result<Block, crate::Error> {
+        self.get_block_with(by, Consistency::Latest).await
+    }
+
+    /// Get block by id or number with given consistency level
+    pub async fn get_block_with(
+        &self,
+        by: BlockBy,
+        consistency: Consistency,
+    ) -> Result<Block, crate::Error> {
         self.api_post(
-            "/wallet/getblock",
+            &consistency.path("getblock"),
             &serde_json::json!({
                 "id_or_num": by.id_or_num(),
                 "detail": true,
@@ -166,6 +494,98 @@ impl RpcClient {
         .await
     }
 
+    /// Get latest `count` blocks (ascending)
+    pub async fn get_latest_blocks(&self, count: u64) -> Result<Vec<Block>, crate::Error> {
+        let resp: BlockListResponse = self
+            .api_post(
+                "/wallet/getblockbylatestnum",
+                &serde_json::json!({ "num": count }),
+            )
+            .await?;
+        let mut blocks = resp.block;
+        blocks.sort_by_key(|x| x.block_number());
+        Ok(blocks)
+    }
+
+    /// Get blocks in range (ascending, without gaps)
+    pub async fn get_block_range(&self, range: Range<u64>) -> Result<Vec<Block>, crate::Error> {
+        self.get_block_range_with(range, Consistency::Latest, self.max_concurrency)
+            .await
+    }
+
+    /** Get blocks in range with given consistency level, making up to `concurrency` parallel requests.
+     ** Fetched in chunks via getblockbylimitnext, missing blocks (or all blocks if node does not
+     ** support getblockbylimitnext) are fetched one by one via getblock.
+     */
+    pub async fn get_block_range_with(
+        &self,
+        range: Range<u64>,
+        consistency: Consistency,
+        concurrency: usize,
+    ) -> Result<Vec<Block>, crate::Error> {
+        const MAX_BLOCKS_PER_REQUEST: u64 = 100;
+        let method = &consistency.path("getblockbylimitnext");
+        let chunks = range
+            .clone()
+            .step_by(MAX_BLOCKS_PER_REQUEST as usize)
+            .map(|start| start..range.end.min(start + MAX_BLOCKS_PER_REQUEST));
+        let chunks: Vec<Vec<Block>> = futures::stream::iter(chunks)
+            .map(|chunk| async move {
+                let resp: Result<BlockListResponse, _> = self
+                    .api_post(
+                        method,
+                        &serde_json::json!({ "startNum": chunk.start, "endNum": chunk.end }),
+                    )
+                    .await;
+                match resp {
+                    Ok(resp) => Ok(resp.block),
+                    // API without getblockbylimitnext, fetched one by one below
+                    Err(crate::Error::HttpStatus(404 | 405, _)) => Ok(Vec::new()),
+                    Err(e) => Err(e),
+                }
+            })
+            .buffered(concurrency.max(1))
+            .try_collect()
+            .await?;
+        let mut blocks: BTreeMap<u64, Block> = chunks
+            .into_iter()
+            .flatten()
+            .filter(|block| range.contains(&block.block_number()))
+            .map(|block| (block.block_number(), block))
+            .collect();
+        let missing: Vec<u64> = range.clone().filter(|x| !blocks.contains_key(x)).collect();
+        let fetched: Vec<Block> = futures::stream::iter(missing)
+            .map(|number| self.get_block_with(BlockBy::Number(number), consistency))
+            .buffered(concurrency.max(1))
+            .try_collect()
+            .await?;
+        blocks.extend(fetched.into_iter().map(|x| (x.block_number(), x)));
+        range
+            .map(|number| {
+                blocks
+                    .remove(&number)
+                    .ok_or(crate::Error::MissingBlock(number))
+            })
+            .collect()
+    }
+
+    /// Max number of parallel requests of batch queries
+    pub(crate) fn max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+
+    /// Get latest block without transactions
+    pub(crate) async fn get_head_block(
+        &self,
+        consistency: Consistency,
+    ) -> Result<Block, crate::Error> {
+        self.api_post(
+            &consistency.path("getblock"),
+            &serde_json::json!({ "detail": false }),
+        )
+        .await
+    }
+
     /// Get transaction info
     pub async fn get_tx_info_by_id(
         &self,
@@ -183,24 +603,67 @@ impl RpcClient {
         serde_json::from_value(res).map_err(|e| crate::Error::UnknownResponse(e.to_string()))
     }
 
-    /// Await transaction confirmation
+    /// Get transaction execution receipt (fee, resources, logs etc.) of confirmed transaction
+    pub async fn get_tx_receipt(
+        &self,
+        txid: TransactionId,
+    ) -> Result<Option<TransactionReceipt>, crate::Error> {
+        self.get_tx_receipt_with(txid, Consistency::Solidified)
+            .await
+    }
+
+    /// Get transaction execution receipt with given consistency level
+    pub async fn get_tx_receipt_with(
+        &self,
+        txid: TransactionId,
+        consistency: Consistency,
+    ) -> Result<Option<TransactionReceipt>, crate::Error> {
+        let res: serde_json::Value = self
+            .api_post(
+                &consistency.path("gettransactioninfobyid"),
+                &serde_json::json!({ "value": txid }),
+            )
+            .await?;
+        if res.get("id").is_none() {
+            return Ok(None);
+        } // does not exist or unconfirmed
+        serde_json::from_value(res)
+            .map(Some)
+            .map_err(|e| crate::Error::UnknownResponse(e.to_string()))
+    }
+
+    /// Get execution receipts of all transactions in block
+    pub async fn get_block_receipts(
+        &self,
+        number: u64,
+    ) -> Result<Vec<TransactionReceipt>, crate::Error> {
+        self.get_block_receipts_with(number, Consistency::Latest)
+            .await
+    }
+
+    /// Get execution receipts of all transactions in block with given consistency level
+    pub async fn get_block_receipts_with(
+        &self,
+        number: u64,
+        consistency: Consistency,
+    ) -> Result<Vec<TransactionReceipt>, crate::Error> {
+        self.api_post(
+            &consistency.path("gettransactioninfobyblocknum"),
+            &serde_json::json!({ "num": number }),
+        )
+        .await
+    }
+
+    /// Await transaction confirmation (polls solidity node without deadline, see `await_confirmation_with`)
     pub async fn await_confirmation(
         &self,
         txid: TransactionId,
-    ) -> Result<TransactionInfo, crate::Error> {
+    ) -> Result<TransactionReceipt, crate::Error> {
         loop {
-            let info = self.get_tx_info_by_id(txid).await?;
-            match info {
-                Some(x) if !x.ret.is_empty() && x.ret[0].contract_ret == "SUCCESS" => return Ok(x),
-                Some(x) => {
-                    return Err(crate::Error::TxFailed(
-                        x.ret
-                            .first()
-                            .map(|x| x.contract_ret.clone())
-                            .unwrap_or_else(|| "empty ret".to_owned()),
-                    ))
-                }
-                _ => {
+            match self.get_tx_receipt(txid).await? {
+                Some(x) if x.is_success() => return Ok(x),
+                Some(x) => return Err(crate::Error::TxFailed(Box::new(x))),
+                None => {
                     tokio::time::sleep(self.poll_interval).await;
                 }
             }
@@ -262,7 +725,11 @@ impl RpcClient {
     ) -> Result<Transaction, crate::Error> {
         let fee_limit = match fee_limit {
             Some(fee_limit) => fee_limit,
-            None => self.estimate_fee_limit(method_call).await?,
+            None => {
+                self.estimate_contract_energy(method_call, value)
+                    .await?
+                    .fee_limit
+            }
         };
         let resp: TriggerContractResponse = self
             .api_post(
@@ -271,7 +738,7 @@ impl RpcClient {
                     "owner_address": method_call.caller.as_hex(),
                     "contract_address": method_call.contract.as_hex(),
                     "function_selector": method_call.selector,
-                    "parameter": hex::encode(method_call.parameter),
+                    "parameter": hex::encode(&method_call.parameter),
                     "fee_limit": fee_limit,
                     "call_value": value
                 }),
@@ -287,25 +754,58 @@ impl RpcClient {
         &self,
         method_call: &MethodCall<'_>,
     ) -> Result<QueryContractResponse, crate::Error> {
-        let resp: QueryContractResponse = self
-            .api_post(
-                "/wallet/triggerconstantcontract",
-                &serde_json::json!({
-                    "owner_address": method_call.caller.as_hex(),
-                    "contract_address": method_call.contract.as_hex(),
-                    "function_selector": method_call.selector,
-                    "parameter": hex::encode(method_call.parameter),
-                }),
-            )
-            .await?;
+        self.query_contract_with(method_call, Consistency::Latest)
+            .await
+    }
+
+    /** Query a smart contract view method with given consistency level
+     ** method_call: Call parameters
+     */
+    pub async fn query_contract_with(
+        &self,
+        method_call: &MethodCall<'_>,
+        consistency: Consistency,
+    ) -> Result<QueryContractResponse, crate::Error> {
+        self.constant_call(
+            &consistency.path("triggerconstantcontract"),
+            &serde_json::json!({
+                "owner_address": method_call.caller.as_hex(),
+                "contract_address": method_call.contract.as_hex(),
+                "function_selector": method_call.selector,
+                "parameter": hex::encode(&method_call.parameter),
+            }),
+        )
+        .await
+    }
+
+    /// Send constant call request, mapping failures and reverts to errors
+    async fn constant_call(
+        &self,
+        path: &str,
+        payload: &serde_json::Value,
+    ) -> Result<QueryContractResponse, crate::Error> {
+        let resp: QueryContractResponse = self.api_post(path, payload).await?;
         if resp.constant_result.is_empty() && resp.code.is_none() {
             return Err(crate::Error::ContractNotFound);
         }
 
-        if let Some(code) = resp.code.as_ref() {
+        let (code, message) = match resp.code.as_ref() {
+            Some(code) => (Some(code), &resp.message),
+            None => (resp.result.code.as_ref(), &resp.result.message),
+        };
+        if let Some(code) = code {
             return Err(crate::Error::ContractQueryFailed(
                 code.to_owned(),
-                resp.message,
+                types::result_message(message),
+            ));
+        }
+        let result = resp.contract_result();
+        if !result.is_success() {
+            return Err(crate::Error::ContractExecutionFailed(
+                result,
+                resp.constant_result(0)
+                    .ok()
+                    .and_then(|data| RevertReason::decode(&data)),
             ));
         }
         Ok(resp)
@@ -340,51 +840,10 @@ impl RpcClient {
             .sign_transaction(&mut tx)
             .map_err(|e| crate::Error::SignerError(format!("{:?}", e)))?;
         let txid = self.broadcast_transaction(&tx).await?;
-        let info = self.await_confirmation(txid).await?;
-        let contract = info
-            .transaction
-            .raw_data
-            .contract
-            .first()
-            .ok_or(crate::Error::ContractNotFound)?;
-        let new_address = contract
-            .parameter
-            .get("value")
-            .ok_or_else(|| crate::Error::UnknownResponse("no value field".to_owned()))?
-            .get("new_contract")
-            .ok_or_else(|| crate::Error::UnknownResponse("no new_contract field".to_owned()))?
-            .get("contract_address")
-            .ok_or_else(|| crate::Error::UnknownResponse("no contract_address field".to_owned()))?
-            .as_str()
-            .ok_or_else(|| {
-                crate::Error::UnknownResponse("returned contract_address is not string".to_owned())
-            })?;
-        new_address
-            .parse()
-            .map_err(|_| crate::Error::UnknownResponse(format!("Invalid address: {}", new_address)))
-    }
-
-    /** Estimate energy cost of given smart contract call
-     ** method_call: Call parameters
-     */
-    pub async fn estimate_energy(&self, method_call: &MethodCall<'_>) -> Result<u64, crate::Error> {
-        let resp = self.query_contract(method_call).await?;
-        Ok(resp.energy_used)
-    }
-
-    /** Estimate fee limit of given smart contract call
-     ** method_call: Call parameters
-     */
-    pub async fn estimate_fee_limit(
-        &self,
-        method_call: &MethodCall<'_>,
-    ) -> Result<u64, crate::Error> {
-        let params = self.get_chain_parameters().await?;
-        let energy_fee = *params
-            .get("getEnergyFee")
-            .ok_or_else(|| crate::Error::UnknownResponse("getEnergyFee not found".to_owned()))?
-            as u64;
-        Ok(self.estimate_energy(method_call).await? * energy_fee)
+        let receipt = self.await_confirmation(txid).await?;
+        receipt
+            .contract_address
+            .ok_or(crate::Error::ContractNotFound)
     }
 
     /// Query the resource information of an account (bandwidth, energy, etc..)
@@ -399,15 +858,32 @@ impl RpcClient {
         .await
     }
 
-    /// Query TRX account balance (including frozen)
-    pub async fn get_account_balance(&self, account: &Address) -> Result<u64, crate::Error> {
-        let resp: AccountBalanceResponse = self
+    /// Query account info. Returns `Error::AccountNotFound` if account is not activated
+    pub async fn get_account(&self, account: &Address) -> Result<Account, crate::Error> {
+        self.get_account_with(account, Consistency::Latest).await
+    }
+
+    /// Query account info with given consistency level
+    pub async fn get_account_with(
+        &self,
+        account: &Address,
+        consistency: Consistency,
+    ) -> Result<Account, crate::Error> {
+        let res: serde_json::Value = self
             .api_post(
-                "/wallet/getaccount",
+                &consistency.path("getaccount"),
                 &serde_json::json!({ "address": account.as_hex() }),
             )
             .await?;
-        resp.balance.ok_or(crate::Error::AccountNotFound)
+        if res.get("address").is_none() {
+            return Err(crate::Error::AccountNotFound);
+        } // node returns empty object for not activated accounts
+        serde_json::from_value(res).map_err(|e| crate::Error::UnknownResponse(e.to_string()))
+    }
+
+    /// Query liquid TRX account balance (excluding staked, see `Account::total_balance`)
+    pub async fn get_account_balance(&self, account: &Address) -> Result<u64, crate::Error> {
+        Ok(self.get_account(account).await?.balance)
     }
 
     /// All parameters that the blockchain committee can set
@@ -0,0 +1,376 @@
+use alloy_primitives::U256;
+use heliosphere_core::Address;
+
+use super::{ContractTxBuilder, Log, RpcClient, TransactionReceipt};
+use crate::abi::{self, AbiValue};
+
+/// TRC721 `Transfer` has the same signature as TRC20 one, but indexed token id (4 topics)
+const TRANSFER_SIGNATURE: &str = "Transfer(address,address,uint256)";
+const TRANSFER_SINGLE_SIGNATURE: &str = "TransferSingle(address,address,address,uint256,uint256)";
+const TRANSFER_BATCH_SIGNATURE: &str = "TransferBatch(address,address,address,uint256[],uint256[])";
+
+fn topic(signature: &str) -> [u8; 32] {
+    alloy_primitives::keccak256(signature.as_bytes()).0
+}
+
+fn topic_address(topic: &[u8; 32]) -> Result<Address, crate::Error> {
+    Ok(abi::decode(&["address"], topic)?[0]
+        .as_address()
+        .expect("Decoded as address"))
+}
+
+fn uint(value: &AbiValue) -> U256 {
+    value.as_uint().expect("Decoded as uint256")
+}
+
+fn uints(value: &AbiValue) -> Vec<U256> {
+    value
+        .as_slice()
+        .expect("Decoded as uint256[]")
+        .iter()
+        .map(uint)
+        .collect()
+}
+
+/// TRC721 `Transfer` event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Trc721Transfer {
+    /// Token contract
+    pub contract: Address,
+    /// Previous owner (zero address for mint)
+    pub from: Address,
+    /// New owner (zero address for burn)
+    pub to: Address,
+    /// Token id
+    pub token_id: U256,
+}
+
+/// TRC1155 `TransferSingle` or `TransferBatch` event
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Trc1155Transfer {
+    /// Token contract
+    pub contract: Address,
+    /// Account which made the transfer
+    pub operator: Address,
+    /// Sender (zero address for mint)
+    pub from: Address,
+    /// Recipient (zero address for burn)
+    pub to: Address,
+    /// Token ids (single element for `TransferSingle`)
+    pub ids: Vec<U256>,
+    /// Transferred amount of each token id
+    pub values: Vec<U256>,
+}
+
+/// Handle of TRC721 (non-fungible token) contract
+#[derive(Clone)]
+pub struct Trc721 {
+    client: RpcClient,
+    address: Address,
+}
+
+impl Trc721 {
+    /// Create new handle of token at given address
+    pub fn new(client: RpcClient, address: Address) -> Self {
+        Self { client, address }
+    }
+
+    /// Token contract address
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Owner of token
+    pub async fn owner_of(&self, token_id: U256) -> Result<Address, crate::Error> {
+        let res = self
+            .client
+            .query_values(
+                &self.address,
+                "ownerOf(uint256)",
+                &[token_id.into()],
+                &["address"],
+            )
+            .await?;
+        Ok(res[0].as_address().expect("Decoded as address"))
+    }
+
+    /// Number of tokens owned by account
+    pub async fn balance_of(&self, owner: &Address) -> Result<U256, crate::Error> {
+        let res = self
+            .client
+            .query_values(
+                &self.address,
+                "balanceOf(address)",
+                &[(*owner).into()],
+                &["uint256"],
+            )
+            .await?;
+        Ok(uint(&res[0]))
+    }
+
+    /// Metadata URI of token
+    pub async fn token_uri(&self, token_id: U256) -> Result<String, crate::Error> {
+        let res = self
+            .client
+            .query_values(
+                &self.address,
+                "tokenURI(uint256)",
+                &[token_id.into()],
+                &["string"],
+            )
+            .await?;
+        Ok(res[0].as_str().expect("Decoded as string").to_owned())
+    }
+
+    fn builder(&self, caller: &Address, selector: &str, args: &[AbiValue]) -> ContractTxBuilder {
+        ContractTxBuilder::new(
+            self.client.clone(),
+            *caller,
+            self.address,
+            selector,
+            abi::encode(args),
+        )
+    }
+
+    /// Transfer token of `from` to `to`, sent by owner or approved `caller`
+    pub fn safe_transfer_from(
+        &self,
+        caller: &Address,
+        from: &Address,
+        to: &Address,
+        token_id: U256,
+    ) -> ContractTxBuilder {
+        self.builder(
+            caller,
+            "safeTransferFrom(address,address,uint256)",
+            &[(*from).into(), (*to).into(), token_id.into()],
+        )
+    }
+
+    /// Allow `approved` to transfer token of `owner`
+    pub fn approve(
+        &self,
+        owner: &Address,
+        approved: &Address,
+        token_id: U256,
+    ) -> ContractTxBuilder {
+        self.builder(
+            owner,
+            "approve(address,uint256)",
+            &[(*approved).into(), token_id.into()],
+        )
+    }
+
+    /// Allow or disallow `operator` to transfer all tokens of `owner`
+    pub fn set_approval_for_all(
+        &self,
+        owner: &Address,
+        operator: &Address,
+        approved: bool,
+    ) -> ContractTxBuilder {
+        self.builder(
+            owner,
+            "setApprovalForAll(address,bool)",
+            &[(*operator).into(), approved.into()],
+        )
+    }
+
+    /// Decode TRC721 `Transfer` event of any contract, returns None for other logs (including TRC20 `Transfer`)
+    pub fn decode_transfer(log: &Log) -> Result<Option<Trc721Transfer>, crate::Error> {
+        match log.topics.as_slice() {
+            [event, from, to, token_id] if *event == topic(TRANSFER_SIGNATURE) => {
+                Ok(Some(Trc721Transfer {
+                    contract: log.address,
+                    from: topic_address(from)?,
+                    to: topic_address(to)?,
+                    token_id: U256::from_be_bytes(*token_id),
+                }))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Transfers of this token in transaction receipt
+    pub fn transfers(
+        &self,
+        receipt: &TransactionReceipt,
+    ) -> Result<Vec<Trc721Transfer>, crate::Error> {
+        let mut transfers = Vec::new();
+        for log in receipt.logs.iter().filter(|x| x.address == self.address) {
+            transfers.extend(Self::decode_transfer(log)?);
+        }
+        Ok(transfers)
+    }
+}
+
+/// Handle of TRC1155 (multi token) contract
+#[derive(Clone)]
+pub struct Trc1155 {
+    client: RpcClient,
+    address: Address,
+}
+
+impl Trc1155 {
+    /// Create new handle of token at given address
+    pub fn new(client: RpcClient, address: Address) -> Self {
+        Self { client, address }
+    }
+
+    /// Token contract address
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Balance of token id owned by account
+    pub async fn balance_of(&self, owner: &Address, id: U256) -> Result<U256, crate::Error> {
+        let res = self
+            .client
+            .query_values(
+                &self.address,
+                "balanceOf(address,uint256)",
+                &[(*owner).into(), id.into()],
+                &["uint256"],
+            )
+            .await?;
+        Ok(uint(&res[0]))
+    }
+
+    /// Balances of (owner, id) pairs
+    pub async fn balance_of_batch(
+        &self,
+        owners: &[Address],
+        ids: &[U256],
+    ) -> Result<Vec<U256>, crate::Error> {
+        let res = self
+            .client
+            .query_values(
+                &self.address,
+                "balanceOfBatch(address[],uint256[])",
+                &[
+                    AbiValue::Array(owners.iter().map(|x| (*x).into()).collect()),
+                    AbiValue::Array(ids.iter().map(|x| (*x).into()).collect()),
+                ],
+                &["uint256[]"],
+            )
+            .await?;
+        Ok(uints(&res[0]))
+    }
+
+    fn builder(&self, caller: &Address, selector: &str, args: &[AbiValue]) -> ContractTxBuilder {
+        ContractTxBuilder::new(
+            self.client.clone(),
+            *caller,
+            self.address,
+            selector,
+            abi::encode(args),
+        )
+    }
+
+    /// Transfer `value` of token id from `from` to `to`, sent by owner or approved `caller`
+    pub fn safe_transfer_from(
+        &self,
+        caller: &Address,
+        from: &Address,
+        to: &Address,
+        id: U256,
+        value: U256,
+        data: &[u8],
+    ) -> ContractTxBuilder {
+        self.builder(
+            caller,
+            "safeTransferFrom(address,address,uint256,uint256,bytes)",
+            &[
+                (*from).into(),
+                (*to).into(),
+                id.into(),
+                value.into(),
+                AbiValue::Bytes(data.to_vec()),
+            ],
+        )
+    }
+
+    /// Transfer multiple token ids from `from` to `to`, sent by owner or approved `caller`
+    pub fn safe_batch_transfer_from(
+        &self,
+        caller: &Address,
+        from: &Address,
+        to: &Address,
+        ids: &[U256],
+        values: &[U256],
+        data: &[u8],
+    ) -> ContractTxBuilder {
+        self.builder(
+            caller,
+            "safeBatchTransferFrom(address,address,uint256[],uint256[],bytes)",
+            &[
+                (*from).into(),
+                (*to).into(),
+                AbiValue::Array(ids.iter().map(|x| (*x).into()).collect()),
+                AbiValue::Array(values.iter().map(|x| (*x).into()).collect()),
+                AbiValue::Bytes(data.to_vec()),
+            ],
+        )
+    }
+
+    /// Allow or disallow `operator` to transfer all tokens of `owner`
+    pub fn set_approval_for_all(
+        &self,
+        owner: &Address,
+        operator: &Address,
+        approved: bool,
+    ) -> ContractTxBuilder {
+        self.builder(
+            owner,
+            "setApprovalForAll(address,bool)",
+            &[(*operator).into(), approved.into()],
+        )
+    }
+
+    /// Decode TRC1155 `TransferSingle` or `TransferBatch` event of any contract, returns None for other logs
+    pub fn decode_transfer(log: &Log) -> Result<Option<Trc1155Transfer>, crate::Error> {
+        let [event, operator, from, to] = log.topics.as_slice() else {
+            return Ok(None);
+        };
+        let (ids, values) = if *event == topic(TRANSFER_SINGLE_SIGNATURE) {
+            let res = abi::decode(&["uint256", "uint256"], &log.data)?;
+            (vec![uint(&res[0])], vec![uint(&res[1])])
+        } else if *event == topic(TRANSFER_BATCH_SIGNATURE) {
+            let res = abi::decode(&["uint256[]", "uint256[]"], &log.data)?;
+            (uints(&res[0]), uints(&res[1]))
+        } else {
+            return Ok(None);
+        };
+        Ok(Some(Trc1155Transfer {
+            contract: log.address,
+            operator: topic_address(operator)?,
+            from: topic_address(from)?,
+            to: topic_address(to)?,
+            ids,
+            values,
+        }))
+    }
+
+    /// Transfers of this token in transaction receipt
+    pub fn transfers(
+        &self,
+        receipt: &TransactionReceipt,
+    ) -> Result<Vec<Trc1155Transfer>, crate::Error> {
+        let mut transfers = Vec::new();
+        for log in receipt.logs.iter().filter(|x| x.address == self.address) {
+            transfers.extend(Self::decode_transfer(log)?);
+        }
+        Ok(transfers)
+    }
+}
+
+impl RpcClient {
+    /// Handle of TRC721 token at given address
+    pub fn trc721(&self, token: Address) -> Trc721 {
+        Trc721::new(self.clone(), token)
+    }
+
+    /// Handle of TRC1155 token at given address
+    pub fn trc1155(&self, token: Address) -> Trc1155 {
+        Trc1155::new(self.clone(), token)
+    }
+}
@@ -0,0 +1,285 @@
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, RwLock,
+    },
+    time::Duration,
+};
+
+use tokio::time::Instant;
+use url::Url;
+
+use super::{Consistency, RpcClient};
+
+/// Endpoint selection strategy
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoutingStrategy {
+    /// Always use first healthy endpoint in the order they were added
+    #[default]
+    PrimaryFallback,
+    /// Rotate between healthy endpoints
+    RoundRobin,
+    /// Use healthy endpoint with lowest observed latency
+    LowestLatency,
+}
+
+/// Endpoint health status
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointStatus {
+    /// Endpoint url
+    pub url: Url,
+    /// Whether endpoint receives requests
+    pub healthy: bool,
+    /// Last known head block number
+    pub head: Option<u64>,
+    /// Smoothed request latency
+    pub latency: Option<Duration>,
+}
+
+struct NodeSet {
+    clients: Vec<RpcClient>,
+    status: RwLock<Vec<EndpointStatus>>,
+    consistency: Consistency,
+    next: AtomicUsize,
+}
+
+impl NodeSet {
+    fn new(clients: Vec<RpcClient>, consistency: Consistency) -> Self {
+        let status = clients
+            .iter()
+            .map(|client| EndpointStatus {
+                url: match consistency {
+                    Consistency::Latest => client.rpc_url().clone(),
+                    Consistency::Solidified => client.solidity_url().clone(),
+                },
+                healthy: true,
+                head: None,
+                latency: None,
+            })
+            .collect();
+        Self {
+            clients,
+            status: RwLock::new(status),
+            consistency,
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// Endpoint indices in order of preference, healthy ones first
+    fn candidates(&self, strategy: RoutingStrategy) -> Vec<usize> {
+        let status = self.status.read().expect("Not poisoned");
+        let (mut healthy, unhealthy): (Vec<usize>, Vec<usize>) =
+            (0..status.len()).partition(|i| status[*i].healthy);
+        match strategy {
+            RoutingStrategy::PrimaryFallback => (),
+            RoutingStrategy::RoundRobin if !healthy.is_empty() => {
+                let shift = self.next.fetch_add(1, Ordering::Relaxed) % healthy.len();
+                healthy.rotate_left(shift);
+            }
+            RoutingStrategy::RoundRobin => (),
+            RoutingStrategy::LowestLatency => {
+                healthy.sort_by_key(|i| status[*i].latency.unwrap_or(Duration::MAX))
+            }
+        }
+        healthy.extend(unhealthy);
+        healthy
+    }
+
+    fn healthy(&self, strategy: RoutingStrategy) -> Option<RpcClient> {
+        let index = *self.candidates(strategy).first()?;
+        let healthy = self.status.read().expect("Not poisoned")[index].healthy;
+        healthy.then(|| self.clients[index].clone())
+    }
+
+    fn record(&self, index: usize, latency: Duration, healthy: bool) {
+        let mut status = self.status.write().expect("Not poisoned");
+        let status = &mut status[index];
+        status.latency = Some(match status.latency {
+            Some(prev) => (prev * 4 + latency) / 5,
+            None => latency,
+        });
+        status.healthy &= healthy;
+    }
+
+    async fn call<F, Fut, T>(&self, strategy: RoutingStrategy, f: F) -> Result<T, crate::Error>
+    where
+        F: Fn(RpcClient) -> Fut,
+        Fut: Future<Output = Result<T, crate::Error>>,
+    {
+        let mut last_error = crate::Error::NoHealthyEndpoint;
+        for index in self.candidates(strategy) {
+            let started = Instant::now();
+            match f(self.clients[index].clone()).await {
+                Err(e) if is_endpoint_failure(&e) => {
+                    self.record(index, started.elapsed(), false);
+                    last_error = e;
+                }
+                result => {
+                    self.record(index, started.elapsed(), true);
+                    return result;
+                }
+            }
+        }
+        Err(last_error)
+    }
+
+    async fn health_check(&self, max_head_lag: u64) {
+        let heads = futures::future::join_all(self.clients.iter().map(|client| async move {
+            let started = Instant::now();
+            let head = client.get_head_block(self.consistency).await;
+            (head.map(|x| x.block_number()).ok(), started.elapsed())
+        }))
+        .await;
+        let best = heads.iter().filter_map(|(head, _)| *head).max();
+        let mut status = self.status.write().expect("Not poisoned");
+        for (status, (head, latency)) in status.iter_mut().zip(heads) {
+            status.healthy =
+                matches!((head, best), (Some(head), Some(best)) if best - head <= max_head_lag);
+            status.head = head.or(status.head);
+            if head.is_some() {
+                status.latency = Some(latency);
+            }
+        }
+    }
+}
+
+/// Whether error indicates endpoint problem (so request should go to other endpoint)
+fn is_endpoint_failure(error: &crate::Error) -> bool {
+    match error {
+        crate::Error::HttpStatus(_, _) | crate::Error::Transport(_) => true,
+        #[cfg(feature = "reqwest")]
+        crate::Error::RpcError(_) => true,
+        e => e.is_retryable(),
+    }
+}
+
+/// Builder struct for RpcPool
+#[derive(Default)]
+pub struct RpcPoolBuilder {
+    fullnodes: Vec<RpcClient>,
+    solidity_nodes: Vec<RpcClient>,
+    strategy: RoutingStrategy,
+    max_head_lag: u64,
+}
+
+impl RpcPoolBuilder {
+    /// Create new instance
+    pub fn new() -> Self {
+        Self {
+            max_head_lag: 5,
+            ..Default::default()
+        }
+    }
+
+    /// Add fullnode endpoint (serving `/wallet/*`)
+    pub fn with_fullnode(mut self, client: RpcClient) -> Self {
+        self.fullnodes.push(client);
+        self
+    }
+
+    /// Add solidity node endpoint (serving `/walletsolidity/*`)
+    pub fn with_solidity_node(mut self, client: RpcClient) -> Self {
+        self.solidity_nodes.push(client);
+        self
+    }
+
+    /// Set endpoint selection strategy (default `RoutingStrategy::PrimaryFallback`)
+    pub fn with_strategy(mut self, strategy: RoutingStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Max number of blocks endpoint may lag behind the best one before being ejected (default 5)
+    pub fn with_max_head_lag(mut self, max_head_lag: u64) -> Self {
+        self.max_head_lag = max_head_lag;
+        self
+    }
+
+    /// Build new RpcPool instance
+    pub fn build(self) -> RpcPool {
+        RpcPool {
+            fullnodes: Arc::new(NodeSet::new(self.fullnodes, Consistency::Latest)),
+            solidity_nodes: Arc::new(NodeSet::new(self.solidity_nodes, Consistency::Solidified)),
+            strategy: self.strategy,
+            max_head_lag: self.max_head_lag,
+        }
+    }
+}
+
+/// Pool of fullnode and solidity node endpoints with health checks and failover
+#[derive(Clone)]
+pub struct RpcPool {
+    fullnodes: Arc<NodeSet>,
+    solidity_nodes: Arc<NodeSet>,
+    strategy: RoutingStrategy,
+    max_head_lag: u64,
+}
+
+impl RpcPool {
+    /// Healthy fullnode client selected by routing strategy
+    pub fn client(&self) -> Result<RpcClient, crate::Error> {
+        self.fullnodes
+            .healthy(self.strategy)
+            .ok_or(crate::Error::NoHealthyEndpoint)
+    }
+
+    /// Healthy solidity node client selected by routing strategy
+    pub fn solidity_client(&self) -> Result<RpcClient, crate::Error> {
+        self.solidity_nodes
+            .healthy(self.strategy)
+            .ok_or(crate::Error::NoHealthyEndpoint)
+    }
+
+    /** Run request on fullnodes, failing over to next endpoint on transport errors.
+     ** Failed endpoints are ejected until next successful health check.
+     ** `pool.call(|client| async move { client.get_latest_block().await })`
+     */
+    pub async fn call<F, Fut, T>(&self, f: F) -> Result<T, crate::Error>
+    where
+        F: Fn(RpcClient) -> Fut,
+        Fut: Future<Output = Result<T, crate::Error>>,
+    {
+        self.fullnodes.call(self.strategy, f).await
+    }
+
+    /// Run request on solidity nodes, see `call`
+    pub async fn call_solidity<F, Fut, T>(&self, f: F) -> Result<T, crate::Error>
+    where
+        F: Fn(RpcClient) -> Fut,
+        Fut: Future<Output = Result<T, crate::Error>>,
+    {
+        self.solidity_nodes.call(self.strategy, f).await
+    }
+
+    /// Query head block of every endpoint, eject lagging or failing ones and re-add recovered
+    pub async fn health_check(&self) {
+        futures::future::join(
+            self.fullnodes.health_check(self.max_head_lag),
+            self.solidity_nodes.health_check(self.max_head_lag),
+        )
+        .await;
+    }
+
+    /// Run health checks forever with given interval (e.g. in spawned task)
+    pub async fn run_health_checks(self, interval: Duration) {
+        loop {
+            self.health_check().await;
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    /// Fullnode endpoints status
+    pub fn fullnode_status(&self) -> Vec<EndpointStatus> {
+        self.fullnodes.status.read().expect("Not poisoned").clone()
+    }
+
+    /// Solidity node endpoints status
+    pub fn solidity_status(&self) -> Vec<EndpointStatus> {
+        self.solidity_nodes
+            .status
+            .read()
+            .expect("Not poisoned")
+            .clone()
+    }
+}
@@ -0,0 +1,206 @@
+use heliosphere_core::transaction::Transaction;
+
+use super::{cost::contract_value, ContractResult, CostPreview, RevertReason, RpcClient};
+
+/// Maximum time between head block and transaction expiration accepted by nodes (24 hours)
+const MAX_EXPIRATION_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// Problem found by pre-flight check
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PreflightIssue {
+    /// Sender account does not exist on chain
+    SenderNotActivated,
+    /// Liquid balance does not cover transferred amount plus fees (in SUN)
+    InsufficientBalance {
+        /// Amount plus predicted fees
+        required: u64,
+        /// Liquid balance of sender
+        balance: u64,
+    },
+    /// Expiration is not after head block timestamp (ms)
+    Expired {
+        /// Transaction expiration
+        expiration: u64,
+        /// Head block timestamp
+        head_timestamp: u64,
+    },
+    /// Expiration is more than 24 hours after head block timestamp (ms)
+    ExpirationTooFar {
+        /// Transaction expiration
+        expiration: u64,
+        /// Latest accepted expiration
+        max_expiration: u64,
+    },
+    /// Fee limit exceeds `getMaxFeeLimit` chain parameter (in SUN)
+    FeeLimitTooHigh {
+        /// Transaction fee limit
+        fee_limit: u64,
+        /// Maximum fee limit
+        max_fee_limit: u64,
+    },
+    /// Simulated contract call failed
+    ContractFailed(ContractResult, Option<RevertReason>),
+    /// Simulated contract call was rejected by node (code and message)
+    ContractRejected(String, String),
+}
+
+impl core::fmt::Display for PreflightIssue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::SenderNotActivated => write!(f, "sender account is not activated"),
+            Self::InsufficientBalance { required, balance } => write!(
+                f,
+                "insufficient balance: {} SUN required, {} SUN available",
+                required, balance
+            ),
+            Self::Expired {
+                expiration,
+                head_timestamp,
+            } => write!(
+                f,
+                "transaction expired at {} (head block at {})",
+                expiration, head_timestamp
+            ),
+            Self::ExpirationTooFar {
+                expiration,
+                max_expiration,
+            } => write!(
+                f,
+                "expiration {} is later than {} (24 hours after head block)",
+                expiration, max_expiration
+            ),
+            Self::FeeLimitTooHigh {
+                fee_limit,
+                max_fee_limit,
+            } => write!(
+                f,
+                "fee limit {} SUN exceeds maximum of {} SUN",
+                fee_limit, max_fee_limit
+            ),
+            Self::ContractFailed(result, Some(reason)) => {
+                write!(f, "contract call fails with {:?}: {}", result, reason)
+            }
+            Self::ContractFailed(result, None) => {
+                write!(f, "contract call fails with {:?}", result)
+            }
+            Self::ContractRejected(code, message) => {
+                write!(f, "contract call rejected with {}: {}", code, message)
+            }
+        }
+    }
+}
+
+/// Result of pre-flight check
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PreflightReport {
+    /// Predicted fees (None if sender is not activated, whole fee limit as energy fee if contract
+    /// call fails)
+    pub cost: Option<CostPreview>,
+    /// Found problems
+    pub issues: Vec<PreflightIssue>,
+}
+
+impl PreflightReport {
+    /// Whether no problems were found
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl RpcClient {
+    /** Check transaction before broadcasting: sender activation and balance, expiration window,
+     ** fee limit and simulated contract call
+     ** tx: Transaction to broadcast
+     */
+    pub async fn preflight(&self, tx: &Transaction) -> Result<PreflightReport, crate::Error> {
+        let (kind, value) = contract_value(tx)?;
+        let mut issues = Vec::new();
+
+        let head_timestamp = self
+            .get_latest_block()
+            .await?
+            .block_header
+            .raw_data
+            .timestamp;
+        let expiration = tx.raw_data.expiration;
+        if expiration <= head_timestamp {
+            issues.push(PreflightIssue::Expired {
+                expiration,
+                head_timestamp,
+            });
+        } else if expiration > head_timestamp + MAX_EXPIRATION_MS {
+            issues.push(PreflightIssue::ExpirationTooFar {
+                expiration,
+                max_expiration: head_timestamp + MAX_EXPIRATION_MS,
+            });
+        }
+
+        let params = self.get_chain_parameters().await?;
+        if let Some(max_fee_limit) = params.get("getMaxFeeLimit").map(|x| *x as u64) {
+            if tx.raw_data.fee_limit > max_fee_limit {
+                issues.push(PreflightIssue::FeeLimitTooHigh {
+                    fee_limit: tx.raw_data.fee_limit,
+                    max_fee_limit,
+                });
+            }
+        }
+
+        let account = match self.get_account(&value.owner_address).await {
+            Ok(account) => account,
+            Err(crate::Error::AccountNotFound) => {
+                issues.push(PreflightIssue::SenderNotActivated);
+                return Ok(PreflightReport { cost: None, issues });
+            }
+            Err(e) => return Err(e),
+        };
+
+        let amount = match kind {
+            "TransferContract" => value.amount,
+            "TriggerSmartContract" => value.call_value,
+            _ => 0,
+        };
+        let mut call_ok = true;
+        if let ("TriggerSmartContract", Some(contract)) = (kind, &value.contract_address) {
+            let simulation = self
+                .constant_call(
+                    "/wallet/triggerconstantcontract",
+                    &serde_json::json!({
+                        "owner_address": value.owner_address.as_hex(),
+                        "contract_address": contract.as_hex(),
+                        "data": hex::encode(&value.data),
+                        "call_value": value.call_value,
+                    }),
+                )
+                .await;
+            match simulation {
+                Ok(_) => {}
+                Err(crate::Error::ContractExecutionFailed(result, reason)) => {
+                    issues.push(PreflightIssue::ContractFailed(result, reason));
+                    call_ok = false;
+                }
+                Err(crate::Error::ContractQueryFailed(code, message)) => {
+                    issues.push(PreflightIssue::ContractRejected(code, message));
+                    call_ok = false;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        // energy of failing call can not be estimated, worst case burns whole fee limit
+        let mut cost = self.transaction_cost(tx, call_ok).await?;
+        if !call_ok {
+            cost.energy_fee = tx.raw_data.fee_limit;
+        }
+        let required = amount.saturating_add(cost.total());
+        if account.balance < required {
+            issues.push(PreflightIssue::InsufficientBalance {
+                required,
+                balance: account.balance,
+            });
+        }
+        Ok(PreflightReport {
+            cost: Some(cost),
+            issues,
+        })
+    }
+}
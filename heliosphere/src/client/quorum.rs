@@ -0,0 +1,140 @@
+use std::future::Future;
+
+use futures::{stream::FuturesUnordered, StreamExt};
+use heliosphere_core::{
+    block::{BlockBy, BlockId},
+    transaction::TransactionId,
+    Address,
+};
+use serde::Serialize;
+
+use super::{Account, RpcClient, TransactionReceipt};
+
+/// Answer of a single endpoint in quorum read
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndpointAnswer {
+    /// Endpoint url
+    pub endpoint: String,
+    /// Returned value (as json) or error message
+    pub answer: Result<serde_json::Value, String>,
+}
+
+/// Client querying several endpoints, returning result only when enough of them agree
+#[derive(Clone)]
+pub struct QuorumClient {
+    clients: Vec<RpcClient>,
+    threshold: usize,
+}
+
+impl QuorumClient {
+    /// Create new instance requiring `threshold` of `clients` to agree
+    pub fn new(clients: Vec<RpcClient>, threshold: usize) -> Result<Self, crate::Error> {
+        if threshold == 0 || threshold > clients.len() {
+            return Err(crate::Error::InvalidQuorum(threshold, clients.len()));
+        }
+        Ok(Self { clients, threshold })
+    }
+
+    /** Run read on all endpoints concurrently and return value returned by at least `threshold` of them.
+     ** Errors describing chain state (e.g. `Error::AccountNotFound`) vote like values and are returned
+     ** on agreement. Returns `Error::QuorumDivergence` with every endpoint answer if quorum is not reached.
+     */
+    pub async fn read<F, Fut, T>(&self, f: F) -> Result<T, crate::Error>
+    where
+        F: Fn(RpcClient) -> Fut,
+        Fut: Future<Output = Result<T, crate::Error>>,
+        T: PartialEq + Serialize,
+    {
+        let mut pending: FuturesUnordered<_> = self
+            .clients
+            .iter()
+            .map(|client| {
+                let endpoint = client.rpc_url().to_string();
+                let fut = f(client.clone());
+                async move { (endpoint, fut.await) }
+            })
+            .collect();
+        let mut answers: Vec<(String, Result<T, crate::Error>)> = Vec::new();
+        let mut groups: Vec<(usize, usize)> = Vec::new(); // (answer index, votes)
+        while let Some((endpoint, answer)) = pending.next().await {
+            let index = answers.len();
+            if answer.as_ref().map_or_else(is_state_error, |_| true) {
+                let group = groups
+                    .iter_mut()
+                    .find(|(i, _)| same_answer(&answers[*i].1, &answer));
+                match group {
+                    Some((i, votes)) => {
+                        *votes += 1;
+                        if *votes >= self.threshold {
+                            let i = *i;
+                            return answers.swap_remove(i).1;
+                        }
+                    }
+                    None if self.threshold == 1 => return answer,
+                    None => groups.push((index, 1)),
+                }
+            }
+            answers.push((endpoint, answer));
+        }
+        Err(crate::Error::QuorumDivergence(
+            answers
+                .into_iter()
+                .map(|(endpoint, answer)| EndpointAnswer {
+                    endpoint,
+                    answer: answer
+                        .map_err(|e| e.to_string())
+                        .and_then(|value| serde_json::to_value(value).map_err(|e| e.to_string())),
+                })
+                .collect(),
+        ))
+    }
+
+    /// Query account info
+    pub async fn get_account(&self, account: &Address) -> Result<Account, crate::Error> {
+        self.read(|client| async move { client.get_account(account).await })
+            .await
+    }
+
+    /// Query liquid TRX account balance
+    pub async fn get_account_balance(&self, account: &Address) -> Result<u64, crate::Error> {
+        self.read(|client| async move { client.get_account_balance(account).await })
+            .await
+    }
+
+    /// Get transaction execution receipt of confirmed transaction
+    pub async fn get_tx_receipt(
+        &self,
+        txid: TransactionId,
+    ) -> Result<Option<TransactionReceipt>, crate::Error> {
+        self.read(|client| async move { client.get_tx_receipt(txid).await })
+            .await
+    }
+
+    /// Get block id (hash) by number
+    pub async fn get_block_id(&self, number: u64) -> Result<BlockId, crate::Error> {
+        self.read(|client| async move {
+            let block = client.get_block(BlockBy::Number(number)).await?;
+            Ok(block.block_id)
+        })
+        .await
+    }
+}
+
+/// Errors returned by every synced node for the same state (unlike transport failures)
+fn is_state_error(error: &crate::Error) -> bool {
+    matches!(
+        error,
+        crate::Error::AccountNotFound
+            | crate::Error::ContractNotFound
+            | crate::Error::ContractQueryFailed(..)
+            | crate::Error::ContractExecutionFailed(..)
+    )
+}
+
+fn same_answer<T: PartialEq>(a: &Result<T, crate::Error>, b: &Result<T, crate::Error>) -> bool {
+    match (a, b) {
+        (Ok(a), Ok(b)) => a == b,
+        (Err(a), Err(b)) => is_state_error(a) && a.to_string() == b.to_string(),
+        _ => false,
+    }
+}
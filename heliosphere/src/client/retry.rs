@@ -0,0 +1,144 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use rand::Rng;
+use tokio::time::Instant;
+
+/// Retry policy for failed requests (transport errors and retryable HTTP statuses)
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: f64,
+    jitter: bool,
+    retry_on_status: Vec<u16>,
+}
+
+impl Default for RetryPolicy {
+    /// 3 attempts, 500ms..10s exponential backoff with jitter, retry on 429, 502, 503 and 504
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: true,
+            retry_on_status: vec![429, 502, 503, 504],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Default policy with custom max number of attempts (including the first one)
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            ..Default::default()
+        }
+    }
+
+    /// Never retry
+    pub fn none() -> Self {
+        Self::new(1)
+    }
+
+    /// Set initial and max backoff
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+
+    /// Set backoff multiplier (default 2)
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier.max(1.0);
+        self
+    }
+
+    /// Enable or disable random jitter (enabled by default)
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Set HTTP statuses which should be retried
+    pub fn with_retry_on_status(mut self, statuses: impl IntoIterator<Item = u16>) -> Self {
+        self.retry_on_status = statuses.into_iter().collect();
+        self
+    }
+
+    /// Max number of attempts (including the first one)
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Whether request failed with given error should be retried
+    pub fn should_retry(&self, error: &crate::Error) -> bool {
+        match error {
+            crate::Error::HttpStatus(status, _) => self.retry_on_status.contains(status),
+            e => e.is_retryable(),
+        }
+    }
+
+    /// Delay before given retry (1-based)
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exp = self.multiplier.powi(retry.saturating_sub(1).min(64) as i32);
+        let delay = self
+            .initial_backoff
+            .mul_f64(exp.min(u32::MAX as f64))
+            .min(self.max_backoff);
+        if self.jitter {
+            delay.mul_f64(rand::thread_rng().gen_range(0.5..=1.0))
+        } else {
+            delay
+        }
+    }
+}
+
+/// Token bucket rate limiter, shared between client clones
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    rate: f64,
+    burst: f64,
+    state: Arc<Mutex<(f64, Instant)>>,
+}
+
+impl RateLimiter {
+    /// Allow `rate` requests per second on average with bursts of up to `burst` requests
+    /// (zero, negative or NaN rate does not limit requests)
+    pub fn new(rate: f64, burst: u32) -> Self {
+        let burst = burst.max(1) as f64;
+        Self {
+            rate,
+            burst,
+            state: Arc::new(Mutex::new((burst, Instant::now()))),
+        }
+    }
+
+    /// Wait until request is allowed
+    pub async fn acquire(&self) {
+        if self.rate.is_nan() || self.rate <= 0.0 {
+            return;
+        }
+        loop {
+            let wait = {
+                let mut state = self.state.lock().expect("Not poisoned");
+                let (tokens, last) = &mut *state;
+                let now = Instant::now();
+                *tokens =
+                    (*tokens + now.duration_since(*last).as_secs_f64() * self.rate).min(self.burst);
+                *last = now;
+                if *tokens >= 1.0 {
+                    *tokens -= 1.0;
+                    return;
+                }
+                // tiny rates overflow Duration
+                Duration::try_from_secs_f64((1.0 - *tokens) / self.rate).unwrap_or(Duration::MAX)
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
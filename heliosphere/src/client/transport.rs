@@ -0,0 +1,230 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use url::Url;
+
+/// HTTP request method
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HttpMethod {
+    /// GET request
+    Get,
+    /// POST request with json body
+    Post,
+}
+
+/// HTTP request issued by RpcClient
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    /// Request method
+    pub method: HttpMethod,
+    /// Full request url
+    pub url: String,
+    /// Request headers (custom headers and API key)
+    pub headers: Vec<(String, String)>,
+    /// Json body (POST requests only)
+    pub body: Option<serde_json::Value>,
+    /// Request timeout
+    pub timeout: Option<Duration>,
+}
+
+impl HttpRequest {
+    /// Url path e.g. `/wallet/getaccount`
+    pub fn path(&self) -> String {
+        Url::parse(&self.url)
+            .map(|x| x.path().to_owned())
+            .unwrap_or_default()
+    }
+
+    /// Value of header with given (case-insensitive) name
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+}
+
+/// HTTP response returned by transport
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    /// Status code
+    pub status: u16,
+    /// Response headers
+    pub headers: Vec<(String, String)>,
+    /// Raw response body
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Create new response with given status and body
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Create `200 OK` response with json body
+    pub fn json(body: &serde_json::Value) -> Self {
+        Self::new(200, body.to_string())
+    }
+
+    /// Add response header
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Value of header with given (case-insensitive) name
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+
+    /// Whether status is 2xx
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+fn find_header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+/// HTTP transport used by RpcClient to send json requests.
+/// Connection level failures should be returned as errors, unsuccessful statuses as responses.
+#[async_trait]
+pub trait Transport: Send + Sync {
+    /// Send request and return response
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, crate::Error>;
+}
+
+/// Default transport backed by reqwest::Client
+#[cfg(feature = "reqwest")]
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "reqwest")]
+impl ReqwestTransport {
+    /// Create new transport from reqwest::Client instance
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[cfg(feature = "reqwest")]
+#[async_trait]
+impl Transport for ReqwestTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, crate::Error> {
+        let mut builder = match request.method {
+            HttpMethod::Get => self.client.get(&request.url),
+            HttpMethod::Post => self.client.post(&request.url),
+        };
+        for (name, value) in request.headers.iter() {
+            builder = builder.header(name, value);
+        }
+        if let Some(body) = &request.body {
+            builder = builder.json(body);
+        }
+        if let Some(timeout) = request.timeout {
+            builder = builder.timeout(timeout);
+        }
+        let resp = builder.send().await?;
+        let status = resp.status().as_u16();
+        let headers = resp
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_owned())))
+            .collect();
+        let body = resp.bytes().await?.to_vec();
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+/// Transport used when no transport is configured (reqwest feature disabled)
+#[cfg(not(feature = "reqwest"))]
+pub(crate) struct NoTransport;
+
+#[cfg(not(feature = "reqwest"))]
+#[async_trait]
+impl Transport for NoTransport {
+    async fn send(&self, _: HttpRequest) -> Result<HttpResponse, crate::Error> {
+        Err(crate::Error::Transport(
+            "no transport configured".to_owned(),
+        ))
+    }
+}
+
+type Handler = Arc<dyn Fn(&HttpRequest) -> Result<HttpResponse, crate::Error> + Send + Sync>;
+
+/// In-memory transport answering requests by url path, useful for tests.
+/// Unknown paths are answered with `404 Not Found`. Clones share routes and recorded requests.
+#[derive(Clone, Default)]
+pub struct MockTransport {
+    routes: Arc<Mutex<HashMap<String, Handler>>>,
+    requests: Arc<Mutex<Vec<HttpRequest>>>,
+}
+
+impl MockTransport {
+    /// Create new transport without routes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answer requests to `path` (e.g. `/wallet/getaccount`) with given json
+    pub fn with_response(self, path: &str, body: serde_json::Value) -> Self {
+        let resp = HttpResponse::json(&body);
+        self.with_handler(path, move |_| Ok(resp.clone()))
+    }
+
+    /// Answer requests to `path` with custom handler
+    pub fn with_handler<F>(self, path: &str, handler: F) -> Self
+    where
+        F: Fn(&HttpRequest) -> Result<HttpResponse, crate::Error> + Send + Sync + 'static,
+    {
+        self.routes
+            .lock()
+            .expect("Not poisoned")
+            .insert(normalize_path(path), Arc::new(handler));
+        self
+    }
+
+    /// Requests received so far
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().expect("Not poisoned").clone()
+    }
+}
+
+fn normalize_path(path: &str) -> String {
+    path.trim_matches('/').to_owned()
+}
+
+#[async_trait]
+impl Transport for MockTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, crate::Error> {
+        self.requests
+            .lock()
+            .expect("Not poisoned")
+            .push(request.clone());
+        let handler = self
+            .routes
+            .lock()
+            .expect("Not poisoned")
+            .get(&normalize_path(&request.path()))
+            .cloned();
+        match handler {
+            Some(handler) => handler(&request),
+            None => Ok(HttpResponse::new(404, "Not Found")),
+        }
+    }
+}
@@ -0,0 +1,325 @@
+use std::{
+    fmt::Display,
+    sync::{Arc, OnceLock},
+};
+
+use alloy_primitives::U256;
+use heliosphere_core::{transaction::Transaction, Address};
+
+use super::{ContractTxBuilder, RpcClient};
+use crate::abi::{self, AbiValue};
+
+/// Token amount in smallest units with number of decimals of the token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TokenAmount {
+    raw: U256,
+    decimals: u8,
+}
+
+impl TokenAmount {
+    /// Create from amount in smallest token units
+    pub fn new(raw: U256, decimals: u8) -> Self {
+        Self { raw, decimals }
+    }
+
+    /// Parse decimal string (e.g. `1.5`), fails if it has more fractional digits than `decimals`
+    pub fn parse(amount: &str, decimals: u8) -> Result<Self, crate::Error> {
+        let invalid = || crate::Error::InvalidAmount(amount.to_owned());
+        let (int, frac) = amount.trim().split_once('.').unwrap_or((amount.trim(), ""));
+        if (int.is_empty() && frac.is_empty())
+            || frac.len() > decimals as usize
+            || !int.chars().chain(frac.chars()).all(|x| x.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+        let digits = format!("{}{:0<width$}", int, frac, width = decimals as usize);
+        let raw = match digits.trim_start_matches('0') {
+            "" => U256::ZERO,
+            digits => U256::from_str_radix(digits, 10).map_err(|_| invalid())?,
+        };
+        Ok(Self { raw, decimals })
+    }
+
+    /// Amount in smallest token units
+    pub fn raw(&self) -> U256 {
+        self.raw
+    }
+
+    /// Number of token decimals
+    pub fn decimals(&self) -> u8 {
+        self.decimals
+    }
+}
+
+impl Display for TokenAmount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let unit = U256::from(10).pow(U256::from(self.decimals));
+        let (int, frac) = self.raw.div_rem(unit);
+        let frac = format!("{:0>width$}", frac, width = self.decimals as usize);
+        match frac.trim_end_matches('0') {
+            "" => write!(f, "{}", int),
+            frac => write!(f, "{}.{}", int, frac),
+        }
+    }
+}
+
+/// Amount passed to TRC20 transaction builders
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Trc20Amount {
+    /// Amount in smallest token units
+    Raw(U256),
+    /// Decimal amount in whole tokens (e.g. `1.5`), scaled by token decimals
+    Decimal(String),
+}
+
+impl Trc20Amount {
+    async fn resolve(&self, token: &Trc20) -> Result<U256, crate::Error> {
+        match self {
+            Self::Raw(raw) => Ok(*raw),
+            Self::Decimal(amount) => Ok(TokenAmount::parse(amount, token.decimals().await?)?.raw),
+        }
+    }
+}
+
+impl From<U256> for Trc20Amount {
+    fn from(raw: U256) -> Self {
+        Self::Raw(raw)
+    }
+}
+
+impl From<u64> for Trc20Amount {
+    fn from(raw: u64) -> Self {
+        Self::Raw(U256::from(raw))
+    }
+}
+
+impl From<TokenAmount> for Trc20Amount {
+    fn from(amount: TokenAmount) -> Self {
+        Self::Raw(amount.raw)
+    }
+}
+
+impl From<&str> for Trc20Amount {
+    fn from(amount: &str) -> Self {
+        Self::Decimal(amount.to_owned())
+    }
+}
+
+/// Handle of TRC20 token contract
+#[derive(Clone)]
+pub struct Trc20 {
+    client: RpcClient,
+    address: Address,
+    decimals: Arc<OnceLock<u8>>,
+}
+
+impl Trc20 {
+    /// Create new handle of token at given address
+    pub fn new(client: RpcClient, address: Address) -> Self {
+        Self {
+            client,
+            address,
+            decimals: Default::default(),
+        }
+    }
+
+    /// Token contract address
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn call(
+        &self,
+        selector: &str,
+        args: &[AbiValue],
+        output: &str,
+    ) -> Result<AbiValue, crate::Error> {
+        Ok(self
+            .client
+            .query_values(&self.address, selector, args, &[output])
+            .await?
+            .remove(0))
+    }
+
+    async fn call_string(&self, selector: &str) -> Result<String, crate::Error> {
+        match self.call(selector, &[], "string").await {
+            Ok(AbiValue::String(x)) => Ok(x),
+            // some early tokens return bytes32
+            Err(crate::Error::AbiError(_)) => match self.call(selector, &[], "bytes32").await? {
+                AbiValue::FixedBytes(x) => Ok(String::from_utf8_lossy(&x)
+                    .trim_end_matches('\0')
+                    .to_owned()),
+                _ => unreachable!("Decoded as bytes32"),
+            },
+            Ok(_) => unreachable!("Decoded as string"),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn call_uint(&self, selector: &str, args: &[AbiValue]) -> Result<U256, crate::Error> {
+        Ok(self
+            .call(selector, args, "uint256")
+            .await?
+            .as_uint()
+            .expect("Decoded as uint256"))
+    }
+
+    /// Token name
+    pub async fn name(&self) -> Result<String, crate::Error> {
+        self.call_string("name()").await
+    }
+
+    /// Token symbol
+    pub async fn symbol(&self) -> Result<String, crate::Error> {
+        self.call_string("symbol()").await
+    }
+
+    /// Number of decimals (queried once, then cached in handle and its clones)
+    pub async fn decimals(&self) -> Result<u8, crate::Error> {
+        if let Some(decimals) = self.decimals.get() {
+            return Ok(*decimals);
+        }
+        let decimals = self.call_uint("decimals()", &[]).await?;
+        let decimals = u8::try_from(decimals)
+            .map_err(|_| crate::Error::UnknownResponse(format!("decimals {}", decimals)))?;
+        Ok(*self.decimals.get_or_init(|| decimals))
+    }
+
+    async fn amount(&self, raw: U256) -> Result<TokenAmount, crate::Error> {
+        Ok(TokenAmount::new(raw, self.decimals().await?))
+    }
+
+    /// Total token supply
+    pub async fn total_supply(&self) -> Result<TokenAmount, crate::Error> {
+        let raw = self.call_uint("totalSupply()", &[]).await?;
+        self.amount(raw).await
+    }
+
+    /// Token balance of account
+    pub async fn balance_of(&self, owner: &Address) -> Result<TokenAmount, crate::Error> {
+        let raw = self
+            .call_uint("balanceOf(address)", &[(*owner).into()])
+            .await?;
+        self.amount(raw).await
+    }
+
+    /// Amount `spender` is allowed to transfer from `owner`
+    pub async fn allowance(
+        &self,
+        owner: &Address,
+        spender: &Address,
+    ) -> Result<TokenAmount, crate::Error> {
+        let raw = self
+            .call_uint(
+                "allowance(address,address)",
+                &[(*owner).into(), (*spender).into()],
+            )
+            .await?;
+        self.amount(raw).await
+    }
+
+    /// Transfer tokens from `from` to `to`
+    pub fn transfer(
+        &self,
+        from: &Address,
+        to: &Address,
+        amount: impl Into<Trc20Amount>,
+    ) -> Trc20TxBuilder {
+        self.builder(
+            from,
+            "transfer(address,uint256)",
+            vec![(*to).into()],
+            amount,
+        )
+    }
+
+    /// Allow `spender` to transfer up to `amount` tokens of `owner`
+    pub fn approve(
+        &self,
+        owner: &Address,
+        spender: &Address,
+        amount: impl Into<Trc20Amount>,
+    ) -> Trc20TxBuilder {
+        self.builder(
+            owner,
+            "approve(address,uint256)",
+            vec![(*spender).into()],
+            amount,
+        )
+    }
+
+    /// Transfer approved tokens of `from` to `to` on behalf of `spender`
+    pub fn transfer_from(
+        &self,
+        spender: &Address,
+        from: &Address,
+        to: &Address,
+        amount: impl Into<Trc20Amount>,
+    ) -> Trc20TxBuilder {
+        self.builder(
+            spender,
+            "transferFrom(address,address,uint256)",
+            vec![(*from).into(), (*to).into()],
+            amount,
+        )
+    }
+
+    fn builder(
+        &self,
+        caller: &Address,
+        selector: &'static str,
+        args: Vec<AbiValue>,
+        amount: impl Into<Trc20Amount>,
+    ) -> Trc20TxBuilder {
+        Trc20TxBuilder {
+            token: self.clone(),
+            caller: *caller,
+            selector,
+            args,
+            amount: amount.into(),
+            fee_limit: None,
+        }
+    }
+}
+
+/// Builder of TRC20 transaction (fee limit is estimated unless set)
+pub struct Trc20TxBuilder {
+    token: Trc20,
+    caller: Address,
+    selector: &'static str,
+    args: Vec<AbiValue>,
+    amount: Trc20Amount,
+    fee_limit: Option<u64>,
+}
+
+impl Trc20TxBuilder {
+    /// Set fee limit in SUN instead of estimating it
+    pub fn with_fee_limit(mut self, fee_limit: u64) -> Self {
+        self.fee_limit = Some(fee_limit);
+        self
+    }
+
+    /// Build unsigned transaction
+    pub async fn build(self) -> Result<Transaction, crate::Error> {
+        let mut args = self.args;
+        args.push(AbiValue::Uint(self.amount.resolve(&self.token).await?));
+        let builder = ContractTxBuilder::new(
+            self.token.client,
+            self.caller,
+            self.token.address,
+            self.selector,
+            abi::encode(&args),
+        );
+        match self.fee_limit {
+            Some(fee_limit) => builder.with_fee_limit(fee_limit).build().await,
+            None => builder.build().await,
+        }
+    }
+}
+
+impl RpcClient {
+    /// Handle of TRC20 token at given address
+    pub fn trc20(&self, token: Address) -> Trc20 {
+        Trc20::new(self.clone(), token)
+    }
+}
@@ -0,0 +1,542 @@
+use std::collections::BTreeMap;
+
+use alloy_primitives::U256;
+use futures::{Stream, TryStreamExt};
+use heliosphere_core::{transaction::Transaction, transaction::TransactionId, Address};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer};
+
+use super::{Account, RpcClient, TransactionRet};
+
+/// Query options of TronGrid v1 API (options not supported by endpoint are ignored)
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TronGridFilter {
+    only_confirmed: bool,
+    only_unconfirmed: bool,
+    only_from: bool,
+    only_to: bool,
+    min_timestamp: Option<u64>,
+    max_timestamp: Option<u64>,
+    contract_address: Option<Address>,
+    event_name: Option<String>,
+    limit: Option<u32>,
+    ascending: bool,
+}
+
+impl TronGridFilter {
+    /// Create new filter (confirmed and unconfirmed, newest first)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return solidified data only
+    pub fn only_confirmed(mut self) -> Self {
+        self.only_confirmed = true;
+        self
+    }
+
+    /// Return not yet solidified data only
+    pub fn only_unconfirmed(mut self) -> Self {
+        self.only_unconfirmed = true;
+        self
+    }
+
+    /// Return transactions sent by account only
+    pub fn only_from(mut self) -> Self {
+        self.only_from = true;
+        self
+    }
+
+    /// Return transactions received by account only
+    pub fn only_to(mut self) -> Self {
+        self.only_to = true;
+        self
+    }
+
+    /// Skip items older than given block timestamp (ms)
+    pub fn min_timestamp(mut self, timestamp: u64) -> Self {
+        self.min_timestamp = Some(timestamp);
+        self
+    }
+
+    /// Skip items newer than given block timestamp (ms)
+    pub fn max_timestamp(mut self, timestamp: u64) -> Self {
+        self.max_timestamp = Some(timestamp);
+        self
+    }
+
+    /// Return TRC20 transfers of given token only
+    pub fn contract_address(mut self, contract: Address) -> Self {
+        self.contract_address = Some(contract);
+        self
+    }
+
+    /// Return contract events with given name only (e.g. `Transfer`)
+    pub fn event_name(mut self, name: impl Into<String>) -> Self {
+        self.event_name = Some(name.into());
+        self
+    }
+
+    /// Set page size (TronGrid default 20, max 200)
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Return oldest items first
+    pub fn ascending(mut self) -> Self {
+        self.ascending = true;
+        self
+    }
+
+    /// Url query of filter, event endpoints name timestamp bounds `*_block_timestamp`
+    fn query(&self, events: bool, fingerprint: Option<&str>) -> String {
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+        if self.only_confirmed {
+            query.append_pair("only_confirmed", "true");
+        }
+        if self.only_unconfirmed {
+            query.append_pair("only_unconfirmed", "true");
+        }
+        if self.only_from {
+            query.append_pair("only_from", "true");
+        }
+        if self.only_to {
+            query.append_pair("only_to", "true");
+        }
+        let (min, max) = match events {
+            true => ("min_block_timestamp", "max_block_timestamp"),
+            false => ("min_timestamp", "max_timestamp"),
+        };
+        if let Some(timestamp) = self.min_timestamp {
+            query.append_pair(min, &timestamp.to_string());
+        }
+        if let Some(timestamp) = self.max_timestamp {
+            query.append_pair(max, &timestamp.to_string());
+        }
+        if let Some(contract) = self.contract_address {
+            query.append_pair("contract_address", &contract.as_base58());
+        }
+        if let Some(name) = &self.event_name {
+            query.append_pair("event_name", name);
+        }
+        if let Some(limit) = self.limit {
+            query.append_pair("limit", &limit.to_string());
+        }
+        if self.ascending {
+            query.append_pair("order_by", "block_timestamp,asc");
+        }
+        if let Some(fingerprint) = fingerprint {
+            query.append_pair("fingerprint", fingerprint);
+        }
+        query.finish()
+    }
+}
+
+/// Single page of TronGrid list response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TronGridPage<T> {
+    /// Page items
+    pub data: Vec<T>,
+    /// Cursor of next page (None on last page)
+    pub fingerprint: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Meta {
+    #[serde(default)]
+    fingerprint: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Response<T> {
+    #[serde(default = "Vec::new")]
+    data: Vec<T>,
+    #[serde(default)]
+    success: bool,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    meta: Option<Meta>,
+}
+
+/// Account with TRC20 balances (`/v1/accounts/{address}`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct TronGridAccount {
+    /// Account info
+    #[serde(flatten)]
+    pub account: Account,
+    /// TRC20 token balances
+    #[serde(deserialize_with = "de_trc20_balances", default)]
+    pub trc20: Vec<Trc20Balance>,
+}
+
+/// TRC20 token balance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Trc20Balance {
+    /// Token contract
+    pub contract: Address,
+    /// Balance in token units
+    pub balance: U256,
+}
+
+/// Deserialize list of single-entry `{contract: balance}` maps
+fn de_trc20_balances<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Trc20Balance>, D::Error> {
+    let balances = Vec::<BTreeMap<Address, String>>::deserialize(deserializer)?;
+    balances
+        .into_iter()
+        .flatten()
+        .map(|(contract, balance)| {
+            Ok(Trc20Balance {
+                contract,
+                balance: balance.parse().map_err(serde::de::Error::custom)?,
+            })
+        })
+        .collect()
+}
+
+/// Deserialize decimal string as U256
+fn de_u256<'de, D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    s.parse().map_err(serde::de::Error::custom)
+}
+
+/// Deserialize address which may be empty
+fn de_opt_address<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Address>, D::Error> {
+    match Option::<String>::deserialize(deserializer)? {
+        Some(s) if !s.is_empty() => s.parse().map(Some).map_err(serde::de::Error::custom),
+        _ => Ok(None),
+    }
+}
+
+/// Account transaction (`/v1/accounts/{address}/transactions`)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum AccountTransaction {
+    /// Transaction signed by account or sent to it
+    Transaction(Box<TronGridTransaction>),
+    /// Internal transaction involving account
+    Internal(TronGridInternalTransaction),
+}
+
+/// Transaction with block and fee info
+#[derive(Debug, Clone, Deserialize)]
+pub struct TronGridTransaction {
+    /// Transaction
+    #[serde(flatten)]
+    pub transaction: Transaction,
+    /// Execution results
+    #[serde(default)]
+    pub ret: Vec<TransactionRet>,
+    /// Block number
+    #[serde(rename = "blockNumber")]
+    pub block_number: u64,
+    /// Block timestamp (ms)
+    pub block_timestamp: u64,
+    /// Energy consumed from staked energy
+    #[serde(default)]
+    pub energy_usage: u64,
+    /// Total energy consumed
+    #[serde(default)]
+    pub energy_usage_total: u64,
+    /// TRX burned for energy in SUN
+    #[serde(default)]
+    pub energy_fee: u64,
+    /// Bandwidth consumed from free or staked bandwidth
+    #[serde(default)]
+    pub net_usage: u64,
+    /// TRX burned for bandwidth in SUN
+    #[serde(default)]
+    pub net_fee: u64,
+}
+
+/// Internal transaction made during contract execution
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct TronGridInternalTransaction {
+    /// Internal transaction id
+    pub internal_tx_id: String,
+    /// Parent transaction id
+    pub tx_id: TransactionId,
+    /// Block timestamp (ms)
+    pub block_timestamp: u64,
+    /// Caller
+    pub from_address: Address,
+    /// Callee
+    pub to_address: Address,
+    /// Call details
+    pub data: InternalTransactionData,
+}
+
+/// Internal transaction details
+#[derive(Debug, Default, Clone, Deserialize, PartialEq, Eq)]
+pub struct InternalTransactionData {
+    /// Hex encoded type of internal transaction (`call`, `create` or `suicide`)
+    #[serde(default)]
+    pub note: String,
+    /// Whether internal transaction was rejected
+    #[serde(default)]
+    pub rejected: bool,
+    /// Transferred values by token (`_` for TRX)
+    #[serde(default)]
+    pub call_value: BTreeMap<String, u64>,
+}
+
+/// TRC20 token metadata
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Hash)]
+pub struct TokenInfo {
+    /// Token contract
+    pub address: Address,
+    /// Token name
+    #[serde(default)]
+    pub name: String,
+    /// Token symbol
+    #[serde(default)]
+    pub symbol: String,
+    /// Number of decimals
+    #[serde(default)]
+    pub decimals: u8,
+}
+
+/// TRC20 token transfer (`/v1/accounts/{address}/transactions/trc20`)
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Hash)]
+pub struct Trc20Transfer {
+    /// Transaction id
+    pub transaction_id: TransactionId,
+    /// Token
+    pub token_info: TokenInfo,
+    /// Block timestamp (ms)
+    pub block_timestamp: u64,
+    /// Sender
+    pub from: Address,
+    /// Recipient
+    pub to: Address,
+    /// Event type (`Transfer` or `Approval`)
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Amount in token units
+    #[serde(deserialize_with = "de_u256")]
+    pub value: U256,
+}
+
+/// Decoded contract event (`/v1/contracts/{address}/events`)
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct ContractEvent {
+    /// Transaction id
+    pub transaction_id: TransactionId,
+    /// Block number
+    pub block_number: u64,
+    /// Block timestamp (ms)
+    pub block_timestamp: u64,
+    /// Emitting contract
+    pub contract_address: Address,
+    /// Contract which made the call (if different from emitter)
+    #[serde(deserialize_with = "de_opt_address", default)]
+    pub caller_contract_address: Option<Address>,
+    /// Event index in transaction
+    #[serde(default)]
+    pub event_index: u32,
+    /// Event name (e.g. `Transfer`)
+    pub event_name: String,
+    /// Event signature with parameter names
+    #[serde(default)]
+    pub event: String,
+    /// Parameter values by name and position
+    #[serde(default)]
+    pub result: BTreeMap<String, serde_json::Value>,
+    /// Parameter types by name and position
+    #[serde(default)]
+    pub result_type: BTreeMap<String, String>,
+    /// Whether event is not solidified yet
+    #[serde(rename = "_unconfirmed", default)]
+    pub unconfirmed: bool,
+}
+
+/// Client of TronGrid v1 REST API (account history, TRC20 transfers and contract events)
+#[derive(Clone)]
+pub struct TronGridClient {
+    client: RpcClient,
+}
+
+impl TronGridClient {
+    /// Create new instance sending requests to `/v1/*` of given client
+    pub fn new(client: RpcClient) -> Self {
+        Self { client }
+    }
+
+    async fn get_page<T>(&self, path: &str, query: String) -> Result<TronGridPage<T>, crate::Error>
+    where
+        T: DeserializeOwned,
+    {
+        let method = match query.is_empty() {
+            true => path.to_owned(),
+            false => format!("{}?{}", path, query),
+        };
+        let resp: Response<T> = self.client.api_get(&method).await?;
+        if !resp.success {
+            return Err(crate::Error::ApiError(
+                resp.error.unwrap_or_else(|| "request failed".to_owned()),
+            ));
+        }
+        Ok(TronGridPage {
+            data: resp.data,
+            fingerprint: resp.meta.and_then(|x| x.fingerprint),
+        })
+    }
+
+    /// Stream of all items following `meta.fingerprint` cursor
+    fn paginate<T>(
+        &self,
+        path: String,
+        filter: TronGridFilter,
+        events: bool,
+    ) -> impl Stream<Item = Result<T, crate::Error>> + Send + 'static
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let client = self.clone();
+        futures::stream::try_unfold(Some(None), move |cursor: Option<Option<String>>| {
+            let client = client.clone();
+            let path = path.clone();
+            let filter = filter.clone();
+            async move {
+                let Some(fingerprint) = cursor else {
+                    return Ok::<_, crate::Error>(None);
+                };
+                let page = client
+                    .get_page::<T>(&path, filter.query(events, fingerprint.as_deref()))
+                    .await?;
+                let next = match page.data.is_empty() {
+                    true => None,
+                    false => page.fingerprint.map(Some),
+                };
+                Ok(Some((
+                    futures::stream::iter(page.data.into_iter().map(Ok)),
+                    next,
+                )))
+            }
+        })
+        .try_flatten()
+    }
+
+    /// Get account with TRC20 balances. Returns `Error::AccountNotFound` if account is not activated
+    pub async fn get_account(
+        &self,
+        address: &Address,
+        filter: &TronGridFilter,
+    ) -> Result<TronGridAccount, crate::Error> {
+        let page = self
+            .get_page(
+                &format!("v1/accounts/{}", address.as_base58()),
+                filter.query(false, None),
+            )
+            .await?;
+        page.data
+            .into_iter()
+            .next()
+            .ok_or(crate::Error::AccountNotFound)
+    }
+
+    /// Get single page of account transactions, pass `fingerprint` of previous page to continue
+    pub async fn get_transactions_page(
+        &self,
+        address: &Address,
+        filter: &TronGridFilter,
+        fingerprint: Option<&str>,
+    ) -> Result<TronGridPage<AccountTransaction>, crate::Error> {
+        self.get_page(
+            &format!("v1/accounts/{}/transactions", address.as_base58()),
+            filter.query(false, fingerprint),
+        )
+        .await
+    }
+
+    /// Stream of all account transactions matching filter
+    pub fn transactions(
+        &self,
+        address: &Address,
+        filter: TronGridFilter,
+    ) -> impl Stream<Item = Result<AccountTransaction, crate::Error>> + Send + 'static {
+        self.paginate(
+            format!("v1/accounts/{}/transactions", address.as_base58()),
+            filter,
+            false,
+        )
+    }
+
+    /// Get single page of account TRC20 transfers, pass `fingerprint` of previous page to continue
+    pub async fn get_trc20_transfers_page(
+        &self,
+        address: &Address,
+        filter: &TronGridFilter,
+        fingerprint: Option<&str>,
+    ) -> Result<TronGridPage<Trc20Transfer>, crate::Error> {
+        self.get_page(
+            &format!("v1/accounts/{}/transactions/trc20", address.as_base58()),
+            filter.query(false, fingerprint),
+        )
+        .await
+    }
+
+    /// Stream of all account TRC20 transfers matching filter
+    pub fn trc20_transfers(
+        &self,
+        address: &Address,
+        filter: TronGridFilter,
+    ) -> impl Stream<Item = Result<Trc20Transfer, crate::Error>> + Send + 'static {
+        self.paginate(
+            format!("v1/accounts/{}/transactions/trc20", address.as_base58()),
+            filter,
+            false,
+        )
+    }
+
+    /// Get single page of contract events, pass `fingerprint` of previous page to continue
+    pub async fn get_contract_events_page(
+        &self,
+        contract: &Address,
+        filter: &TronGridFilter,
+        fingerprint: Option<&str>,
+    ) -> Result<TronGridPage<ContractEvent>, crate::Error> {
+        self.get_page(
+            &format!("v1/contracts/{}/events", contract.as_base58()),
+            filter.query(true, fingerprint),
+        )
+        .await
+    }
+
+    /// Stream of all contract events matching filter
+    pub fn contract_events(
+        &self,
+        contract: &Address,
+        filter: TronGridFilter,
+    ) -> impl Stream<Item = Result<ContractEvent, crate::Error>> + Send + 'static {
+        self.paginate(
+            format!("v1/contracts/{}/events", contract.as_base58()),
+            filter,
+            true,
+        )
+    }
+
+    /// Get events emitted by transaction
+    pub async fn get_transaction_events(
+        &self,
+        txid: TransactionId,
+        filter: &TronGridFilter,
+    ) -> Result<Vec<ContractEvent>, crate::Error> {
+        let page = self
+            .get_page(
+                &format!("v1/transactions/{}/events", txid),
+                filter.query(true, None),
+            )
+            .await?;
+        Ok(page.data)
+    }
+}
+
+impl RpcClient {
+    /// Client of TronGrid v1 API (`/v1/*`) sharing this client's transport, url and API keys
+    pub fn trongrid(&self) -> TronGridClient {
+        TronGridClient::new(self.clone())
+    }
+}
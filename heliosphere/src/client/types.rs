@@ -1,13 +1,113 @@
-use heliosphere_core::transaction::{Transaction, TransactionId};
-use serde::{Deserialize, Serialize};
+use heliosphere_core::{
+    block::Block,
+    transaction::{Transaction, TransactionId},
+    util::as_hex_buffer,
+    Address,
+};
+use serde::{Deserialize, Deserializer, Serialize};
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct BroadcastTxResponse {
+    #[serde(default)]
+    pub code: Option<BroadcastErrorCode>,
+    #[serde(default)]
+    pub message: String,
+    #[serde(default)]
+    pub txid: Option<TransactionId>,
+}
+
+/// Broadcast response code
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BroadcastErrorCode {
+    /// Invalid signature
+    #[serde(rename = "SIGERROR")]
+    SigError,
+    /// Contract validation failed (e.g. insufficient balance, account does not exist)
+    ContractValidateError,
+    /// Contract execution failed
+    ContractExeError,
+    /// Not enough bandwidth (or TRX to burn for it)
+    #[serde(rename = "BANDWITH_ERROR")]
+    BandwidthError,
+    /// Transaction is already known to the node
+    DupTransactionError,
+    /// Reference block is invalid or too old
+    TaposError,
+    /// Transaction is too big
+    TooBigTransactionError,
+    /// Transaction expired (or expiration is too far in the future)
+    TransactionExpirationError,
+    /// Node is busy
+    ServerBusy,
+    /// Node has no peers
+    NoConnection,
+    /// Node has not enough peers
+    NotEnoughEffectiveConnection,
+    /// Node's solidified block is lagging behind
+    BlockUnsolidified,
+    /// Other error
+    OtherError,
+    /// Code not known to this library
+    #[serde(other)]
+    Unknown,
+}
+
+impl BroadcastErrorCode {
+    /// Whether broadcasting the same transaction again may succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::ServerBusy
+                | Self::NoConnection
+                | Self::NotEnoughEffectiveConnection
+                | Self::BlockUnsolidified
+        )
+    }
+}
+
+/// Broadcast error returned by node
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct BroadcastError {
+    /// Response code
+    pub code: BroadcastErrorCode,
+    /// Decoded message
+    pub message: String,
+}
+
+impl BroadcastError {
+    /// Whether broadcasting the same transaction again may succeed
+    pub fn is_retryable(&self) -> bool {
+        self.code.is_retryable()
+    }
+}
+
+impl core::fmt::Display for BroadcastError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:?}: {}", self.code, self.message)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct QueryResult {
     #[serde(default)]
     pub code: Option<String>,
     #[serde(default)]
     pub message: String,
-    pub txid: TransactionId,
+}
+
+/// API result message (hex encoded text unless node is configured otherwise)
+pub fn result_message(message: &str) -> String {
+    hex::decode(message)
+        .ok()
+        .and_then(|x| String::from_utf8(x).ok())
+        .unwrap_or_else(|| message.to_owned())
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct QueryTransaction {
+    #[serde(default)]
+    pub ret: Vec<TransactionRet>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -17,9 +117,16 @@ pub struct QueryContractResponse {
     #[serde(default)]
     pub message: String,
     #[serde(default)]
+    pub result: QueryResult,
+    #[serde(default)]
     pub constant_result: Vec<String>,
     #[serde(default)]
     pub energy_used: u64,
+    /// Dynamic energy penalty included in `energy_used` (0 if not reported by node)
+    #[serde(default)]
+    pub energy_penalty: u64,
+    #[serde(default)]
+    pub transaction: Option<QueryTransaction>,
 }
 
 impl QueryContractResponse {
@@ -30,6 +137,29 @@ impl QueryContractResponse {
             .ok_or(crate::Error::InvalidIndex)?;
         hex::decode(res).map_err(|e| crate::Error::UnknownResponse(e.to_string()))
     }
+
+    /// Contract execution result of simulated call
+    pub fn contract_result(&self) -> ContractResult {
+        self.transaction
+            .as_ref()
+            .and_then(|tx| tx.ret.first())
+            .map(|ret| ret.contract_ret)
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct EstimateEnergyResponse {
+    #[serde(default)]
+    pub result: QueryResult,
+    #[serde(default)]
+    pub energy_required: u64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BlockListResponse {
+    #[serde(default)]
+    pub block: Vec<Block>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -37,14 +167,17 @@ pub struct TriggerContractResponse {
     pub transaction: Transaction,
 }
 
-/// Resource type: Energy or Bandwidth
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
-#[serde(rename_all = "UPPERCASE")]
+/// Resource type: Energy, Bandwidth or Tron Power
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ResourceType {
-    /// Bandwidth resource
+    /// Bandwidth resource (omitted by the node, as it is the default)
+    #[default]
     Bandwidth,
     /// Energy resource
     Energy,
+    /// Tron Power (votes)
+    TronPower,
 }
 
 /// Account resources
@@ -86,11 +219,149 @@ pub struct AccountResources {
     pub total_energy_weight: u64,
 }
 
+/// Contract execution result code
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ContractResult {
+    /// Not a contract call (omitted by the node, as it is the default)
+    #[default]
+    Default,
+    /// Success
+    Success,
+    /// REVERT opcode executed
+    Revert,
+    /// Invalid jump destination
+    BadJumpDestination,
+    /// Out of memory
+    OutOfMemory,
+    /// Precompiled contract failed
+    PrecompiledContract,
+    /// Stack underflow
+    StackTooSmall,
+    /// Stack too large
+    StackTooLarge,
+    /// Illegal (or invalid) opcode
+    IllegalOperation,
+    /// Stack overflow
+    StackOverflow,
+    /// Not enough energy (fee limit reached)
+    OutOfEnergy,
+    /// Execution time limit exceeded
+    OutOfTime,
+    /// JVM stack overflow
+    JvmStackOverFlow,
+    /// TRX or TRC10 transfer failed
+    TransferFailed,
+    /// Invalid contract code
+    InvalidCode,
+    /// Unknown error (or code not known to this library)
+    #[serde(other)]
+    Unknown,
+}
+
+impl ContractResult {
+    /// Whether execution succeeded (or transaction is not a contract call)
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Default | Self::Success)
+    }
+}
+
+/// Decoded revert payload
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum RevertReason {
+    /// `Error(string)`, as produced by `revert("...")` and `require(..., "...")`
+    Error(String),
+    /// `Panic(uint256)`, as produced by failed `assert`, overflows, division by zero etc.
+    Panic(u64),
+    /// Custom error (selector, ABI encoded arguments)
+    Custom([u8; 4], Vec<u8>),
+}
+
+impl RevertReason {
+    /// `Error(string)` selector
+    pub const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+    /// `Panic(uint256)` selector
+    pub const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+    /// Decode revert data (returns None if empty)
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        if data.len() < 4 {
+            return None;
+        }
+        let (selector, args) = data.split_at(4);
+        let selector: [u8; 4] = selector.try_into().expect("Always 4 bytes");
+        let decoded = match selector {
+            Self::ERROR_SELECTOR => decode_abi_string(args).map(Self::Error),
+            Self::PANIC_SELECTOR => decode_abi_u64(args).map(Self::Panic),
+            _ => None,
+        };
+        Some(decoded.unwrap_or_else(|| Self::Custom(selector, args.to_vec())))
+    }
+
+    /// Human readable description of panic code
+    pub fn panic_description(code: u64) -> &'static str {
+        match code {
+            0x00 => "generic compiler panic",
+            0x01 => "assertion failed",
+            0x11 => "arithmetic overflow or underflow",
+            0x12 => "division or modulo by zero",
+            0x21 => "invalid enum value",
+            0x22 => "invalid storage byte array",
+            0x31 => "pop on empty array",
+            0x32 => "array index out of bounds",
+            0x41 => "out of memory",
+            0x51 => "call to zero-initialized function",
+            _ => "unknown panic",
+        }
+    }
+}
+
+impl core::fmt::Display for RevertReason {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Error(msg) => write!(f, "{}", msg),
+            Self::Panic(code) => write!(
+                f,
+                "panic 0x{:02x} ({})",
+                code,
+                Self::panic_description(*code)
+            ),
+            Self::Custom(selector, data) => {
+                write!(
+                    f,
+                    "custom error 0x{} {}",
+                    hex::encode(selector),
+                    hex::encode(data)
+                )
+            }
+        }
+    }
+}
+
+/// Decode ABI encoded `uint256` that fits into u64
+fn decode_abi_u64(data: &[u8]) -> Option<u64> {
+    let word = data.get(..32)?;
+    if word[..24].iter().any(|x| *x != 0) {
+        return None;
+    }
+    Some(u64::from_be_bytes(word[24..].try_into().ok()?))
+}
+
+/// Decode ABI encoded (single) `string`
+fn decode_abi_string(data: &[u8]) -> Option<String> {
+    let offset = decode_abi_u64(data)? as usize;
+    let len = decode_abi_u64(data.get(offset..)?)? as usize;
+    let start = offset.checked_add(32)?;
+    let bytes = data.get(start..start.checked_add(len)?)?;
+    String::from_utf8(bytes.to_vec()).ok()
+}
+
 /// Transaction receipt
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionRet {
-    #[serde(rename = "contractRet")]
-    pub contract_ret: String,
+    /// Contract execution result
+    #[serde(rename = "contractRet", default)]
+    pub contract_ret: ContractResult,
 }
 
 /// Transaction info
@@ -101,6 +372,200 @@ pub struct TransactionInfo {
     pub ret: Vec<TransactionRet>,
 }
 
+/// Overall transaction result (as reported by gettransactioninfobyid)
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TransactionResult {
+    /// Success (omitted by the node, as it is the default)
+    #[default]
+    Success,
+    /// Failed
+    Failed,
+}
+
+/// Resources consumed by transaction
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct ResourceReceipt {
+    /// Energy consumed from caller's staked energy
+    #[serde(default)]
+    pub energy_usage: u64,
+    /// TRX burned for energy in SUN
+    #[serde(default)]
+    pub energy_fee: u64,
+    /// Energy paid by contract deployer
+    #[serde(default)]
+    pub origin_energy_usage: u64,
+    /// Total energy consumed
+    #[serde(default)]
+    pub energy_usage_total: u64,
+    /// Bandwidth consumed from caller's free or staked bandwidth
+    #[serde(default)]
+    pub net_usage: u64,
+    /// TRX burned for bandwidth in SUN
+    #[serde(default)]
+    pub net_fee: u64,
+    /// Contract execution result (only for smart contract calls)
+    #[serde(default)]
+    pub result: ContractResult,
+}
+
+/// Contract event log
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Log {
+    /// Emitting contract
+    #[serde(deserialize_with = "de_evm_address")]
+    pub address: Address,
+    /// Indexed topics, topic 0 is the event signature hash
+    #[serde(deserialize_with = "de_hex_topics", default)]
+    pub topics: Vec<[u8; 32]>,
+    /// ABI encoded non-indexed event arguments
+    #[serde(with = "as_hex_buffer", default)]
+    pub data: Vec<u8>,
+}
+
+/// TRX or TRC10 value passed with internal transaction
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct CallValueInfo {
+    /// Amount in SUN (or TRC10 units)
+    #[serde(rename = "callValue", default)]
+    pub call_value: u64,
+    /// TRC10 token id (empty for TRX)
+    #[serde(rename = "tokenId", default)]
+    pub token_id: String,
+}
+
+/// Internal transaction (call, create or suicide) made during contract execution
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct InternalTransaction {
+    /// Internal transaction hash
+    pub hash: TransactionId,
+    /// Caller contract
+    pub caller_address: Address,
+    /// Callee address
+    #[serde(rename = "transferTo_address")]
+    pub transfer_to_address: Address,
+    /// Transferred values
+    #[serde(rename = "callValueInfo", default)]
+    pub call_value_info: Vec<CallValueInfo>,
+    /// Type of internal transaction (`call`, `create` or `suicide`)
+    #[serde(deserialize_with = "de_hex_string", default)]
+    pub note: Option<String>,
+    /// Whether internal transaction was rejected
+    #[serde(default)]
+    pub rejected: bool,
+}
+
+/// Transaction execution receipt (as returned by gettransactioninfobyid)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct TransactionReceipt {
+    /// Transaction id
+    pub id: TransactionId,
+    /// Total TRX burned in SUN
+    #[serde(default)]
+    pub fee: u64,
+    /// Block number
+    #[serde(rename = "blockNumber", default)]
+    pub block_number: u64,
+    /// Block timestamp (ms)
+    #[serde(rename = "blockTimeStamp", default)]
+    pub block_timestamp: u64,
+    /// Resources consumed
+    #[serde(default)]
+    pub receipt: ResourceReceipt,
+    /// Overall result
+    #[serde(default)]
+    pub result: TransactionResult,
+    /// Failure message
+    #[serde(rename = "resMessage", deserialize_with = "de_hex_string", default)]
+    pub res_message: Option<String>,
+    /// Contract call return values (hex encoded)
+    #[serde(rename = "contractResult", default)]
+    pub contract_result: Vec<String>,
+    /// Address of deployed contract (for contract creation) or called contract
+    #[serde(default)]
+    pub contract_address: Option<Address>,
+    /// Emitted event logs
+    #[serde(rename = "log", default)]
+    pub logs: Vec<Log>,
+    /// Internal transactions
+    #[serde(default)]
+    pub internal_transactions: Vec<InternalTransaction>,
+}
+
+impl TransactionReceipt {
+    /// Get decoded contract result
+    pub fn contract_result(&self, index: usize) -> Result<Vec<u8>, crate::Error> {
+        let res = self
+            .contract_result
+            .get(index)
+            .ok_or(crate::Error::InvalidIndex)?;
+        hex::decode(res).map_err(|e| crate::Error::UnknownResponse(e.to_string()))
+    }
+
+    /// Whether transaction (and contract execution, if any) succeeded
+    pub fn is_success(&self) -> bool {
+        self.result == TransactionResult::Success && self.receipt.result.is_success()
+    }
+
+    /// Decoded revert reason (if contract execution reverted with data)
+    pub fn revert_reason(&self) -> Option<RevertReason> {
+        self.contract_result(0)
+            .ok()
+            .and_then(|data| RevertReason::decode(&data))
+    }
+
+    /// Short human readable failure description
+    pub fn failure_message(&self) -> String {
+        match (self.revert_reason(), self.res_message.as_deref()) {
+            (Some(reason), _) => format!("{:?}: {}", self.receipt.result, reason),
+            (None, Some(msg)) => format!("{:?}: {}", self.receipt.result, msg),
+            (None, None) => format!("{:?}", self.receipt.result),
+        }
+    }
+}
+
+/// Deserialize 20-byte hex address (as used in logs) into Tron address
+fn de_evm_address<'de, D>(deserializer: D) -> Result<Address, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    let mut buf = [0x41; 21];
+    hex::decode_to_slice(s.trim_start_matches("0x"), &mut buf[1..])
+        .map_err(serde::de::Error::custom)?;
+    Address::new(buf).map_err(serde::de::Error::custom)
+}
+
+/// Deserialize list of hex encoded 32-byte topics
+fn de_hex_topics<'de, D>(deserializer: D) -> Result<Vec<[u8; 32]>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Vec::<String>::deserialize(deserializer)?
+        .into_iter()
+        .map(|s| {
+            let mut buf = [0; 32];
+            hex::decode_to_slice(s.trim_start_matches("0x"), &mut buf)
+                .map_err(serde::de::Error::custom)?;
+            Ok(buf)
+        })
+        .collect()
+}
+
+/// Deserialize hex encoded utf8 string (falls back to raw string if not hex)
+fn de_hex_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    Ok(Some(
+        hex::decode(&s)
+            .ok()
+            .and_then(|x| String::from_utf8(x).ok())
+            .unwrap_or(s),
+    ))
+}
+
 /// Chain parameter (key, value)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub(crate) struct ChainParameter {
@@ -116,9 +581,206 @@ pub(crate) struct ChainParametersResponse {
     pub chain_parameter: Vec<ChainParameter>,
 }
 
+/// TRX staked for a resource (Stake 2.0)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct FrozenV2 {
+    /// Resource the TRX is staked for
+    #[serde(rename = "type", default)]
+    pub resource: ResourceType,
+    /// Staked amount in SUN
+    #[serde(default)]
+    pub amount: u64,
+}
+
+/// TRX being unstaked (Stake 2.0), withdrawable after `unfreeze_expire_time`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct UnfrozenV2 {
+    /// Resource the TRX was staked for
+    #[serde(rename = "type", default)]
+    pub resource: ResourceType,
+    /// Amount in SUN
+    #[serde(default)]
+    pub unfreeze_amount: u64,
+    /// Timestamp (ms) after which the amount can be withdrawn
+    #[serde(default)]
+    pub unfreeze_expire_time: u64,
+}
+
+/// Vote for a super representative
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Vote {
+    /// Witness address
+    pub vote_address: Address,
+    /// Number of votes
+    #[serde(default)]
+    pub vote_count: u64,
+}
+
+/// Permission type
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum PermissionType {
+    /// Owner permission (omitted by the node, as it is the default)
+    #[default]
+    Owner,
+    /// Witness permission
+    Witness,
+    /// Active permission
+    Active,
+}
+
+/// Permission key
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct PermissionKey {
+    /// Key address
+    pub address: Address,
+    /// Key weight
+    #[serde(default)]
+    pub weight: u64,
+}
+
+/// Account permission (multisig)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Permission {
+    /// Permission type
+    #[serde(rename = "type", default)]
+    pub permission_type: PermissionType,
+    /// Permission id (0 - owner, 1 - witness, 2+ - active)
+    #[serde(default)]
+    pub id: u32,
+    /// Permission name
+    #[serde(default)]
+    pub permission_name: String,
+    /// Threshold of key weights required to sign
+    #[serde(default)]
+    pub threshold: u64,
+    /// Parent permission id
+    #[serde(default)]
+    pub parent_id: u32,
+    /// Allowed contract types bitmap (hex), active permissions only
+    #[serde(default)]
+    pub operations: Option<String>,
+    /// Keys
+    #[serde(default)]
+    pub keys: Vec<PermissionKey>,
+}
+
+/// TRC10 asset balance
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct AssetBalance {
+    /// Token id
+    pub key: String,
+    /// Balance in token units
+    #[serde(default)]
+    pub value: u64,
+}
+
+/// Energy related part of account info
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct AccountResource {
+    /// TRX delegated to other accounts for energy
+    #[serde(rename = "delegated_frozenV2_balance_for_energy", default)]
+    pub delegated_frozen_v2_balance_for_energy: u64,
+    /// TRX delegated by other accounts for energy
+    #[serde(rename = "acquired_delegated_frozenV2_balance_for_energy", default)]
+    pub acquired_delegated_frozen_v2_balance_for_energy: u64,
+    /// Last energy consumption timestamp (ms)
+    #[serde(default)]
+    pub latest_consume_time_for_energy: u64,
+    /// Energy recovery window size
+    #[serde(default)]
+    pub energy_window_size: u64,
+}
+
 /// Account info (as returned by /wallet/getaccount)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
-pub(crate) struct AccountBalanceResponse {
+pub struct Account {
+    /// Account address
+    pub address: Address,
+    /// Liquid TRX balance in SUN (excluding staked TRX)
+    #[serde(default)]
+    pub balance: u64,
+    /// Account creation timestamp (ms)
+    #[serde(default)]
+    pub create_time: u64,
+    /// TRX staked for resources
+    #[serde(rename = "frozenV2", default)]
+    pub frozen_v2: Vec<FrozenV2>,
+    /// TRX being unstaked
+    #[serde(rename = "unfrozenV2", default)]
+    pub unfrozen_v2: Vec<UnfrozenV2>,
+    /// TRX delegated to other accounts for bandwidth
+    #[serde(rename = "delegated_frozenV2_balance_for_bandwidth", default)]
+    pub delegated_frozen_v2_balance_for_bandwidth: u64,
+    /// TRX delegated by other accounts for bandwidth
+    #[serde(rename = "acquired_delegated_frozenV2_balance_for_bandwidth", default)]
+    pub acquired_delegated_frozen_v2_balance_for_bandwidth: u64,
+    /// Energy related info
+    #[serde(default)]
+    pub account_resource: AccountResource,
+    /// Votes
+    #[serde(default)]
+    pub votes: Vec<Vote>,
+    /// Owner permission
+    #[serde(default)]
+    pub owner_permission: Option<Permission>,
+    /// Active permissions
     #[serde(default)]
-    pub balance: Option<u64>,
+    pub active_permission: Vec<Permission>,
+    /// TRC10 balances
+    #[serde(rename = "assetV2", default)]
+    pub asset_v2: Vec<AssetBalance>,
+    /// Unclaimed witness reward in SUN
+    #[serde(default)]
+    pub allowance: u64,
+    /// Last operation timestamp (ms)
+    #[serde(rename = "latest_opration_time", default)]
+    pub latest_operation_time: u64,
+    /// Last bandwidth consumption timestamp (ms)
+    #[serde(default)]
+    pub latest_consume_time: u64,
+    /// Last free bandwidth consumption timestamp (ms)
+    #[serde(default)]
+    pub latest_consume_free_time: u64,
+    /// Last reward withdrawal timestamp (ms)
+    #[serde(default)]
+    pub latest_withdraw_time: u64,
+}
+
+impl Account {
+    /// TRX staked for given resource (excluding delegated to others)
+    pub fn frozen_balance(&self, resource: ResourceType) -> u64 {
+        self.frozen_v2
+            .iter()
+            .filter(|x| x.resource == resource)
+            .map(|x| x.amount)
+            .sum()
+    }
+
+    /// TRX delegated to other accounts (both resources)
+    pub fn delegated_balance(&self) -> u64 {
+        self.delegated_frozen_v2_balance_for_bandwidth
+            + self.account_resource.delegated_frozen_v2_balance_for_energy
+    }
+
+    /// TRX being unstaked (not yet withdrawn)
+    pub fn unfreezing_balance(&self) -> u64 {
+        self.unfrozen_v2.iter().map(|x| x.unfreeze_amount).sum()
+    }
+
+    /// Total TRX owned: liquid + staked + delegated + being unstaked
+    pub fn total_balance(&self) -> u64 {
+        self.balance
+            + self.frozen_v2.iter().map(|x| x.amount).sum::<u64>()
+            + self.delegated_balance()
+            + self.unfreezing_balance()
+    }
+
+    /// TRC10 balance by token id
+    pub fn asset_balance(&self, token_id: &str) -> u64 {
+        self.asset_v2
+            .iter()
+            .find(|x| x.key == token_id)
+            .map(|x| x.value)
+            .unwrap_or(0)
+    }
 }
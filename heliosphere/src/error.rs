@@ -1,3 +1,10 @@
+use heliosphere_core::transaction::TransactionId;
+
+use crate::{
+    BroadcastError, ContractResult, EndpointAnswer, PreflightReport, RevertReason,
+    TransactionReceipt,
+};
+
 /// Error type
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -5,23 +12,73 @@ pub enum Error {
     #[error("invalid url")]
     InvalidUrl,
     /// RpcError wrapping reqwest::Error
+    #[cfg(feature = "reqwest")]
     #[error("rpc error {0}")]
     RpcError(#[from] reqwest::Error),
+    /// gRPC call failed with non-OK status (code, message)
+    #[cfg(feature = "grpc")]
+    #[error("grpc status {0:?} {1}")]
+    GrpcStatus(tonic::Code, String),
+    /// Connection level failure reported by custom transport
+    #[error("transport error {0}")]
+    Transport(String),
+    /// Node responded with unsuccessful HTTP status (status, body)
+    #[error("http status {0} {1}")]
+    HttpStatus(u16, String),
+    /// JSON-RPC call returned error (code, message)
+    #[error("json-rpc error {0} {1}")]
+    JsonRpcError(i64, String),
     /// API call failed
     #[error("api error {0}")]
     ApiError(String),
     /// Returned when tx construction fails (code, message)
     #[error("tx construction failed {0}")]
     TxConstructionFailed(String, String),
-    /// Transaction failed on chain
-    #[error("tx failed {0}")]
-    TxFailed(String),
-    /// Returned when contract query fails
+    /// Returned when node rejects transaction broadcast
+    #[error("broadcast failed {0}")]
+    BroadcastFailed(BroadcastError),
+    /// Pre-flight check before broadcast found problems (report attached)
+    #[error("preflight failed {}", .0.issues.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(", "))]
+    PreflightFailed(Box<PreflightReport>),
+    /// Transaction failed on chain (failed receipt attached)
+    #[error("tx failed {}", .0.failure_message())]
+    TxFailed(Box<TransactionReceipt>),
+    /// Transaction was not included before its expiration
+    #[error("tx expired {0}")]
+    TxExpired(TransactionId),
+    /// Block is missing in requested range
+    #[error("missing block {0}")]
+    MissingBlock(u64),
+    /// Block stream found branch switch deeper than remembered blocks (number of replaced blocks)
+    #[error("reorg deeper than {0} blocks")]
+    ReorgTooDeep(usize),
+    /// No healthy endpoint available in pool
+    #[error("no healthy endpoint")]
+    NoHealthyEndpoint,
+    /// Quorum threshold is zero or exceeds number of endpoints (threshold, endpoints)
+    #[error("invalid quorum {0} of {1}")]
+    InvalidQuorum(usize, usize),
+    /// Not enough endpoints agreed on result (answer of each endpoint)
+    #[error("quorum not reached {0:?}")]
+    QuorumDivergence(Vec<EndpointAnswer>),
+    /// Operation deadline exceeded
+    #[error("timeout")]
+    Timeout,
+    /// Returned when contract query is rejected by node (code, message)
     #[error("contract query failed {0} {1}")]
     ContractQueryFailed(String, String),
+    /// Returned when simulated contract call fails (result, decoded revert reason)
+    #[error("contract execution failed {0:?} {}", .1.as_ref().map(|x| x.to_string()).unwrap_or_default())]
+    ContractExecutionFailed(ContractResult, Option<RevertReason>),
     /// Returned when RPC returns invalid or unknown response
     #[error("rpc returned unknown response type")]
     UnknownResponse(String),
+    /// Invalid contract ABI or ABI encoded data
+    #[error("abi error {0}")]
+    AbiError(String),
+    /// Token amount cannot be parsed or has too many decimal places
+    #[error("invalid amount {0}")]
+    InvalidAmount(String),
     /// Invalid index passed
     #[error("invalid index")]
     InvalidIndex,
@@ -35,3 +92,31 @@ pub enum Error {
     #[error("Failed to sign tx: {0}")]
     SignerError(String),
 }
+
+impl Error {
+    /// Whether the failed operation may succeed if retried as is
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            #[cfg(feature = "reqwest")]
+            Self::RpcError(e) => e.is_timeout() || e.is_connect(),
+            Self::Transport(_) => true,
+            #[cfg(feature = "grpc")]
+            Self::GrpcStatus(code, _) => matches!(
+                code,
+                tonic::Code::Unavailable
+                    | tonic::Code::DeadlineExceeded
+                    | tonic::Code::ResourceExhausted
+            ),
+            Self::HttpStatus(status, _) => matches!(status, 429 | 502 | 503 | 504),
+            Self::BroadcastFailed(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "grpc")]
+impl From<tonic::Status> for Error {
+    fn from(status: tonic::Status) -> Self {
+        Self::GrpcStatus(status.code(), status.message().to_owned())
+    }
+}
@@ -0,0 +1,224 @@
+//! Contract event log decoding and filtering
+use std::{collections::BTreeMap, ops::Range};
+
+use futures::{StreamExt, TryStreamExt};
+use heliosphere_core::{transaction::TransactionId, Address};
+
+use crate::{
+    abi::{AbiEvent, AbiValue, ContractAbi},
+    Consistency, Log, RpcClient, TransactionReceipt,
+};
+
+/// Decoded event parameter
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EventParam {
+    /// Parameter name
+    pub name: String,
+    /// Whether parameter was stored in topics
+    pub indexed: bool,
+    /// Decoded value (indexed dynamic values are `FixedBytes` keccak256 hash)
+    pub value: AbiValue,
+}
+
+/// Decoded event log
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DecodedEvent {
+    /// Emitting contract
+    pub contract: Address,
+    /// Event name
+    pub name: String,
+    /// Event signature (e.g. `Transfer(address,address,uint256)`)
+    pub signature: String,
+    /// Parameters in declaration order
+    pub params: Vec<EventParam>,
+}
+
+impl DecodedEvent {
+    /// Get parameter value by name
+    pub fn param(&self, name: &str) -> Option<&AbiValue> {
+        self.params
+            .iter()
+            .find(|x| x.name == name)
+            .map(|x| &x.value)
+    }
+}
+
+/// Decoded event with its transaction
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ReceiptEvent {
+    /// Transaction id
+    pub transaction_id: TransactionId,
+    /// Block number
+    pub block_number: u64,
+    /// Block timestamp (ms)
+    pub block_timestamp: u64,
+    /// Index of log in transaction
+    pub log_index: usize,
+    /// Decoded event
+    pub event: DecodedEvent,
+}
+
+/// Log filter by event signature and emitting contract (empty filter matches any log)
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct EventFilter {
+    topics: Vec<[u8; 32]>,
+    contracts: Vec<Address>,
+}
+
+impl EventFilter {
+    /// Create new filter matching any log
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept events with given signature, e.g. `Transfer(address,address,uint256)`
+    pub fn signature(self, signature: &str) -> Self {
+        self.topic(alloy_primitives::keccak256(signature.as_bytes()).0)
+    }
+
+    /// Accept given ABI event
+    pub fn event(self, event: &AbiEvent) -> Self {
+        self.topic(event.topic())
+    }
+
+    /// Accept events with given signature hash (topic 0)
+    pub fn topic(mut self, topic: [u8; 32]) -> Self {
+        self.topics.push(topic);
+        self
+    }
+
+    /// Accept events emitted by given contract
+    pub fn contract(mut self, contract: Address) -> Self {
+        self.contracts.push(contract);
+        self
+    }
+
+    /// Whether log matches filter
+    pub fn matches(&self, log: &Log) -> bool {
+        (self.contracts.is_empty() || self.contracts.contains(&log.address))
+            && (self.topics.is_empty()
+                || log
+                    .topics
+                    .first()
+                    .map(|x| self.topics.contains(x))
+                    .unwrap_or(false))
+    }
+}
+
+/// Decoder of event logs by contract ABI
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct EventDecoder {
+    events: BTreeMap<[u8; 32], AbiEvent>,
+}
+
+impl EventDecoder {
+    /// Create decoder of (non-anonymous) events of given ABI
+    pub fn new(abi: &ContractAbi) -> Self {
+        Self {
+            events: abi
+                .events
+                .iter()
+                .filter(|x| !x.anonymous)
+                .map(|x| (x.topic(), x.clone()))
+                .collect(),
+        }
+    }
+
+    /// Create decoder from JSON ABI (as accepted by `deploy_contract`)
+    pub fn from_abi_json(abi: &str) -> Result<Self, crate::Error> {
+        Ok(Self::new(&ContractAbi::from_json(abi)?))
+    }
+
+    /// Find event by name
+    pub fn event(&self, name: &str) -> Option<&AbiEvent> {
+        self.events.values().find(|x| x.name == name)
+    }
+
+    /// Decode log, returns None if log is not an event of the ABI
+    /// (signature hash is unknown or number of topics differs, e.g. TRC721 `Transfer` for TRC20 ABI)
+    pub fn decode_log(&self, log: &Log) -> Result<Option<DecodedEvent>, crate::Error> {
+        let Some(event) = log
+            .topics
+            .first()
+            .and_then(|x| self.events.get(x))
+            .filter(|x| x.topic_count() == log.topics.len())
+        else {
+            return Ok(None);
+        };
+        let values = event.decode(&log.topics, &log.data)?;
+        Ok(Some(DecodedEvent {
+            contract: log.address,
+            name: event.name.clone(),
+            signature: event.signature(),
+            params: event
+                .inputs
+                .iter()
+                .zip(values)
+                .map(|(param, value)| EventParam {
+                    name: param.name.clone(),
+                    indexed: param.indexed,
+                    value,
+                })
+                .collect(),
+        }))
+    }
+
+    /// Decode receipt logs matching filter (logs of unknown events are skipped)
+    pub fn decode_receipt(
+        &self,
+        receipt: &TransactionReceipt,
+        filter: &EventFilter,
+    ) -> Result<Vec<ReceiptEvent>, crate::Error> {
+        let mut events = Vec::new();
+        for (log_index, log) in receipt.logs.iter().enumerate() {
+            if !filter.matches(log) {
+                continue;
+            }
+            if let Some(event) = self.decode_log(log)? {
+                events.push(ReceiptEvent {
+                    transaction_id: receipt.id,
+                    block_number: receipt.block_number,
+                    block_timestamp: receipt.block_timestamp,
+                    log_index,
+                    event,
+                });
+            }
+        }
+        Ok(events)
+    }
+}
+
+impl RpcClient {
+    /** Get decoded events emitted in block range, matching filter.
+     ** Receipts of up to `max_concurrency` blocks are fetched in parallel.
+     */
+    pub async fn get_events(
+        &self,
+        range: Range<u64>,
+        decoder: &EventDecoder,
+        filter: &EventFilter,
+    ) -> Result<Vec<ReceiptEvent>, crate::Error> {
+        self.get_events_with(range, decoder, filter, Consistency::Latest)
+            .await
+    }
+
+    /// Get decoded events emitted in block range with given consistency level
+    pub async fn get_events_with(
+        &self,
+        range: Range<u64>,
+        decoder: &EventDecoder,
+        filter: &EventFilter,
+        consistency: Consistency,
+    ) -> Result<Vec<ReceiptEvent>, crate::Error> {
+        let blocks: Vec<Vec<TransactionReceipt>> = futures::stream::iter(range)
+            .map(|number| self.get_block_receipts_with(number, consistency))
+            .buffered(self.max_concurrency().max(1))
+            .try_collect()
+            .await?;
+        let mut events = Vec::new();
+        for receipt in blocks.iter().flatten() {
+            events.extend(decoder.decode_receipt(receipt, filter)?);
+        }
+        Ok(events)
+    }
+}
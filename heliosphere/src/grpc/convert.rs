@@ -0,0 +1,420 @@
+use heliosphere_core::{
+    block::{Block, BlockHeader, BlockId, BlockRawData},
+    transaction::{Contract, RawTxData, Transaction, TransactionId},
+    Address,
+};
+use prost::Message;
+use sha2::{Digest, Sha256};
+
+use super::proto;
+use crate::client::types::{
+    Account, AccountResource, AssetBalance, BroadcastErrorCode, CallValueInfo, ContractResult,
+    FrozenV2, InternalTransaction, Log, Permission, PermissionKey, PermissionType, ResourceReceipt,
+    ResourceType, TransactionReceipt, TransactionResult, TransactionRet, UnfrozenV2, Vote,
+};
+use crate::client::{ContractInfo, ContractState, SmartContract};
+
+fn unknown(what: &str) -> crate::Error {
+    crate::Error::UnknownResponse(format!("invalid {}", what))
+}
+
+pub(crate) fn address(bytes: &[u8]) -> Result<Address, crate::Error> {
+    Address::new(bytes.try_into().map_err(|_| unknown("address"))?).map_err(|_| unknown("address"))
+}
+
+/// 20-byte address (as used in logs) into Tron address
+fn evm_address(bytes: &[u8]) -> Result<Address, crate::Error> {
+    match bytes.len() {
+        20 => {
+            let mut buf = [0x41; 21];
+            buf[1..].copy_from_slice(bytes);
+            Address::new(buf).map_err(|_| unknown("address"))
+        }
+        _ => address(bytes),
+    }
+}
+
+fn hash(bytes: &[u8], what: &str) -> Result<[u8; 32], crate::Error> {
+    bytes.try_into().map_err(|_| unknown(what))
+}
+
+fn string(bytes: &[u8]) -> Option<String> {
+    (!bytes.is_empty()).then(|| String::from_utf8_lossy(bytes).into_owned())
+}
+
+pub(crate) fn contract_result(code: i32) -> ContractResult {
+    match code {
+        0 => ContractResult::Default,
+        1 => ContractResult::Success,
+        2 => ContractResult::Revert,
+        3 => ContractResult::BadJumpDestination,
+        4 => ContractResult::OutOfMemory,
+        5 => ContractResult::PrecompiledContract,
+        6 => ContractResult::StackTooSmall,
+        7 => ContractResult::StackTooLarge,
+        8 => ContractResult::IllegalOperation,
+        9 => ContractResult::StackOverflow,
+        10 => ContractResult::OutOfEnergy,
+        11 => ContractResult::OutOfTime,
+        12 => ContractResult::JvmStackOverFlow,
+        14 => ContractResult::TransferFailed,
+        15 => ContractResult::InvalidCode,
+        _ => ContractResult::Unknown,
+    }
+}
+
+pub(crate) fn broadcast_error_code(code: i32) -> BroadcastErrorCode {
+    match code {
+        1 => BroadcastErrorCode::SigError,
+        2 => BroadcastErrorCode::ContractValidateError,
+        3 => BroadcastErrorCode::ContractExeError,
+        4 => BroadcastErrorCode::BandwidthError,
+        5 => BroadcastErrorCode::DupTransactionError,
+        6 => BroadcastErrorCode::TaposError,
+        7 => BroadcastErrorCode::TooBigTransactionError,
+        8 => BroadcastErrorCode::TransactionExpirationError,
+        9 => BroadcastErrorCode::ServerBusy,
+        10 => BroadcastErrorCode::NoConnection,
+        11 => BroadcastErrorCode::NotEnoughEffectiveConnection,
+        12 => BroadcastErrorCode::BlockUnsolidified,
+        20 => BroadcastErrorCode::OtherError,
+        _ => BroadcastErrorCode::Unknown,
+    }
+}
+
+/// Name of `Return.response_code`
+pub(crate) fn return_code_name(code: i32) -> String {
+    match code {
+        0 => "SUCCESS".to_owned(),
+        code => serde_json::to_value(broadcast_error_code(code))
+            .ok()
+            .and_then(|x| x.as_str().map(ToOwned::to_owned))
+            .unwrap_or_else(|| code.to_string()),
+    }
+}
+
+fn resource_type(code: i32) -> ResourceType {
+    match code {
+        1 => ResourceType::Energy,
+        2 => ResourceType::TronPower,
+        _ => ResourceType::Bandwidth,
+    }
+}
+
+/// Contract parameter as json in the shape returned by HTTP API (for known contract types)
+fn contract_parameter(any: &proto::Any) -> serde_json::Value {
+    let value = match any.type_url.rsplit('.').next() {
+        Some("TransferContract") => proto::TransferContract::decode(&any.value[..])
+            .ok()
+            .map(|x| {
+                serde_json::json!({
+                    "owner_address": hex::encode(x.owner_address),
+                    "to_address": hex::encode(x.to_address),
+                    "amount": x.amount,
+                })
+            }),
+        Some("AccountCreateContract") => proto::AccountCreateContract::decode(&any.value[..])
+            .ok()
+            .map(|x| {
+                serde_json::json!({
+                    "owner_address": hex::encode(x.owner_address),
+                    "account_address": hex::encode(x.account_address),
+                })
+            }),
+        Some("TriggerSmartContract") => proto::TriggerSmartContract::decode(&any.value[..])
+            .ok()
+            .map(|x| {
+                serde_json::json!({
+                    "owner_address": hex::encode(x.owner_address),
+                    "contract_address": hex::encode(x.contract_address),
+                    "call_value": x.call_value,
+                    "data": hex::encode(x.data),
+                })
+            }),
+        _ => None,
+    };
+    serde_json::json!({
+        "value": value.unwrap_or_else(|| hex::encode(&any.value).into()),
+        "type_url": any.type_url,
+    })
+}
+
+/// Transaction from protobuf, raw data bytes are preserved as is
+pub(crate) fn transaction(tx: proto::Transaction) -> Result<Transaction, crate::Error> {
+    let raw =
+        proto::TransactionRaw::decode(&tx.raw_data[..]).map_err(|_| unknown("transaction"))?;
+    Ok(Transaction {
+        raw_data: RawTxData {
+            data: (!raw.data.is_empty()).then(|| hex::encode(&raw.data)),
+            contract: raw
+                .contract
+                .iter()
+                .map(|contract| {
+                    let parameter = contract.parameter.clone().unwrap_or_default();
+                    Contract {
+                        r#type: parameter
+                            .type_url
+                            .rsplit('.')
+                            .next()
+                            .unwrap_or_default()
+                            .to_owned(),
+                        parameter: contract_parameter(&parameter),
+                    }
+                })
+                .collect(),
+            expiration: raw.expiration as u64,
+            timestamp: raw.timestamp as u64,
+            fee_limit: raw.fee_limit as u64,
+            ref_block_bytes: raw.ref_block_bytes,
+            ref_block_hash: raw.ref_block_hash,
+        },
+        tx_id: TransactionId(Sha256::digest(&tx.raw_data).into()),
+        raw_data_bytes: tx.raw_data,
+        signature: tx.signature.iter().map(hex::encode).collect(),
+    })
+}
+
+/// Signed transaction to protobuf
+pub(crate) fn signed_transaction(tx: &Transaction) -> Result<proto::Transaction, crate::Error> {
+    Ok(proto::Transaction {
+        raw_data: tx.raw_data_bytes.clone(),
+        signature: tx
+            .signature
+            .iter()
+            .map(|x| hex::decode(x).map_err(|e| crate::Error::SignerError(e.to_string())))
+            .collect::<Result<_, _>>()?,
+        ret: Vec::new(),
+    })
+}
+
+/// Set fee limit of unsigned transaction (raw data is re-encoded, tx id recalculated)
+pub(crate) fn set_fee_limit(
+    tx: &mut proto::Transaction,
+    fee_limit: u64,
+) -> Result<(), crate::Error> {
+    let mut raw =
+        proto::TransactionRaw::decode(&tx.raw_data[..]).map_err(|_| unknown("transaction"))?;
+    raw.fee_limit = fee_limit as i64;
+    tx.raw_data = raw.encode_to_vec();
+    Ok(())
+}
+
+pub(crate) fn block(block: proto::BlockExtention) -> Result<Block, crate::Error> {
+    let header = block.block_header.unwrap_or_default();
+    let raw = header.raw_data.unwrap_or_default();
+    Ok(Block {
+        block_id: BlockId(hash(&block.blockid, "block id")?),
+        block_header: BlockHeader {
+            raw_data: BlockRawData {
+                number: raw.number as u64,
+                tx_trie_root: raw.tx_trie_root,
+                witness_address: address(&raw.witness_address)?,
+                parent_hash: raw.parent_hash,
+                version: raw.version as u32,
+                timestamp: raw.timestamp as u64,
+            },
+            witness_signature: header.witness_signature,
+        },
+        transactions: block
+            .transactions
+            .into_iter()
+            .filter_map(|x| x.transaction)
+            .map(transaction)
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+pub(crate) fn transaction_rets(tx: &proto::Transaction) -> Vec<TransactionRet> {
+    tx.ret
+        .iter()
+        .map(|ret| TransactionRet {
+            contract_ret: contract_result(ret.contract_ret),
+        })
+        .collect()
+}
+
+fn permission(permission: proto::Permission) -> Result<Permission, crate::Error> {
+    Ok(Permission {
+        permission_type: match permission.r#type {
+            1 => PermissionType::Witness,
+            2 => PermissionType::Active,
+            _ => PermissionType::Owner,
+        },
+        id: permission.id as u32,
+        permission_name: permission.permission_name,
+        threshold: permission.threshold as u64,
+        parent_id: permission.parent_id as u32,
+        operations: (!permission.operations.is_empty()).then(|| hex::encode(permission.operations)),
+        keys: permission
+            .keys
+            .into_iter()
+            .map(|key| {
+                Ok(PermissionKey {
+                    address: address(&key.address)?,
+                    weight: key.weight as u64,
+                })
+            })
+            .collect::<Result<_, crate::Error>>()?,
+    })
+}
+
+/// Contract info without ABI (omitted from proto subset)
+pub(crate) fn contract_info(
+    info: proto::SmartContractDataWrapper,
+) -> Result<ContractInfo, crate::Error> {
+    let contract = info.smart_contract.ok_or(crate::Error::ContractNotFound)?;
+    let state = info.contract_state.unwrap_or_default();
+    Ok(ContractInfo {
+        smart_contract: SmartContract {
+            contract_address: address(&contract.contract_address)?,
+            origin_address: address(&contract.origin_address)?,
+            name: contract.name,
+            abi: Default::default(),
+            bytecode: contract.bytecode,
+            consume_user_resource_percent: contract.consume_user_resource_percent as u64,
+            origin_energy_limit: contract.origin_energy_limit as u64,
+            code_hash: contract.code_hash,
+        },
+        runtime_code: info.runtimecode,
+        contract_state: ContractState {
+            energy_usage: state.energy_usage as u64,
+            energy_factor: state.energy_factor as u64,
+            update_cycle: state.update_cycle as u64,
+        },
+    })
+}
+
+pub(crate) fn account(account: proto::Account) -> Result<Account, crate::Error> {
+    let resource = account.account_resource.unwrap_or_default();
+    let mut asset_v2: Vec<_> = account
+        .asset_v2
+        .into_iter()
+        .map(|(key, value)| AssetBalance {
+            key,
+            value: value as u64,
+        })
+        .collect();
+    asset_v2.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(Account {
+        address: address(&account.address)?,
+        balance: account.balance as u64,
+        create_time: account.create_time as u64,
+        frozen_v2: account
+            .frozen_v2
+            .into_iter()
+            .map(|x| FrozenV2 {
+                resource: resource_type(x.r#type),
+                amount: x.amount as u64,
+            })
+            .collect(),
+        unfrozen_v2: account
+            .unfrozen_v2
+            .into_iter()
+            .map(|x| UnfrozenV2 {
+                resource: resource_type(x.r#type),
+                unfreeze_amount: x.unfreeze_amount as u64,
+                unfreeze_expire_time: x.unfreeze_expire_time as u64,
+            })
+            .collect(),
+        delegated_frozen_v2_balance_for_bandwidth: account.delegated_frozen_v2_balance_for_bandwidth
+            as u64,
+        acquired_delegated_frozen_v2_balance_for_bandwidth: account
+            .acquired_delegated_frozen_v2_balance_for_bandwidth
+            as u64,
+        account_resource: AccountResource {
+            delegated_frozen_v2_balance_for_energy: resource.delegated_frozen_v2_balance_for_energy
+                as u64,
+            acquired_delegated_frozen_v2_balance_for_energy: resource
+                .acquired_delegated_frozen_v2_balance_for_energy
+                as u64,
+            latest_consume_time_for_energy: resource.latest_consume_time_for_energy as u64,
+            energy_window_size: resource.energy_window_size as u64,
+        },
+        votes: account
+            .votes
+            .into_iter()
+            .map(|x| {
+                Ok(Vote {
+                    vote_address: address(&x.vote_address)?,
+                    vote_count: x.vote_count as u64,
+                })
+            })
+            .collect::<Result<_, crate::Error>>()?,
+        owner_permission: account.owner_permission.map(permission).transpose()?,
+        active_permission: account
+            .active_permission
+            .into_iter()
+            .map(permission)
+            .collect::<Result<_, _>>()?,
+        asset_v2,
+        allowance: account.allowance as u64,
+        latest_operation_time: account.latest_opration_time as u64,
+        latest_consume_time: account.latest_consume_time as u64,
+        latest_consume_free_time: account.latest_consume_free_time as u64,
+        latest_withdraw_time: account.latest_withdraw_time as u64,
+    })
+}
+
+pub(crate) fn receipt(info: proto::TransactionInfo) -> Result<TransactionReceipt, crate::Error> {
+    let receipt = info.receipt.unwrap_or_default();
+    Ok(TransactionReceipt {
+        id: TransactionId(hash(&info.id, "transaction id")?),
+        fee: info.fee as u64,
+        block_number: info.block_number as u64,
+        block_timestamp: info.block_time_stamp as u64,
+        receipt: ResourceReceipt {
+            energy_usage: receipt.energy_usage as u64,
+            energy_fee: receipt.energy_fee as u64,
+            origin_energy_usage: receipt.origin_energy_usage as u64,
+            energy_usage_total: receipt.energy_usage_total as u64,
+            net_usage: receipt.net_usage as u64,
+            net_fee: receipt.net_fee as u64,
+            result: contract_result(receipt.result),
+        },
+        result: match info.result {
+            0 => TransactionResult::Success,
+            _ => TransactionResult::Failed,
+        },
+        res_message: string(&info.res_message),
+        contract_result: info.contract_result.iter().map(hex::encode).collect(),
+        contract_address: (!info.contract_address.is_empty())
+            .then(|| address(&info.contract_address))
+            .transpose()?,
+        logs: info
+            .log
+            .into_iter()
+            .map(|log| {
+                Ok(Log {
+                    address: evm_address(&log.address)?,
+                    topics: log
+                        .topics
+                        .iter()
+                        .map(|x| hash(x, "log topic"))
+                        .collect::<Result<_, _>>()?,
+                    data: log.data,
+                })
+            })
+            .collect::<Result<_, crate::Error>>()?,
+        internal_transactions: info
+            .internal_transactions
+            .into_iter()
+            .map(|tx| {
+                Ok(InternalTransaction {
+                    hash: TransactionId(hash(&tx.hash, "internal transaction hash")?),
+                    caller_address: address(&tx.caller_address)?,
+                    transfer_to_address: address(&tx.transfer_to_address)?,
+                    call_value_info: tx
+                        .call_value_info
+                        .into_iter()
+                        .map(|x| CallValueInfo {
+                            call_value: x.call_value as u64,
+                            token_id: x.token_id,
+                        })
+                        .collect(),
+                    note: string(&tx.note),
+                    rejected: tx.rejected,
+                })
+            })
+            .collect::<Result<_, crate::Error>>()?,
+    })
+}
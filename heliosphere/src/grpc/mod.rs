@@ -0,0 +1,639 @@
+//! gRPC client for java-tron nodes (`Wallet` and `WalletSolidity` services)
+use std::{collections::BTreeMap, time::Duration};
+
+use heliosphere_core::{
+    block::{Block, BlockBy},
+    transaction::{Transaction, TransactionId},
+    Address,
+};
+use prost::Message;
+use tonic::{
+    codegen::http::uri::PathAndQuery,
+    transport::{Channel, Endpoint},
+};
+use tonic_prost::ProstCodec;
+
+use crate::client::energy::{
+    apply_energy_factor, origin_pays, split_energy, ESTIMATE_ENERGY_DISABLED,
+};
+use crate::client::types::{QueryContractResponse, QueryResult, QueryTransaction, TransactionInfo};
+use crate::{
+    Account, BroadcastError, BroadcastErrorCode, Consistency, ContractInfo, EnergyEstimate,
+    EnergySource, MethodCall, RevertReason, TransactionReceipt,
+};
+
+mod convert;
+pub mod proto;
+
+/// Builder struct for GrpcClient
+pub struct GrpcClientBuilder {
+    fullnode: Endpoint,
+    solidity: Option<Endpoint>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    fee_limit_margin: u64,
+}
+
+impl GrpcClientBuilder {
+    /// Create new instance (e.g. `http://127.0.0.1:50051`)
+    pub fn new(endpoint: impl AsRef<str>) -> Result<Self, crate::Error> {
+        Ok(Self {
+            fullnode: parse_endpoint(endpoint.as_ref())?,
+            solidity: None,
+            timeout: None,
+            connect_timeout: None,
+            fee_limit_margin: 20,
+        })
+    }
+
+    /// Set separate solidity node endpoint serving `WalletSolidity` service (default is fullnode endpoint)
+    pub fn with_solidity_endpoint(
+        mut self,
+        endpoint: impl AsRef<str>,
+    ) -> Result<Self, crate::Error> {
+        self.solidity = Some(parse_endpoint(endpoint.as_ref())?);
+        Ok(self)
+    }
+
+    /// Set per-request timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set connection timeout
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Set safety margin of estimated fee limit in percent (default 20)
+    pub fn with_fee_limit_margin(mut self, percent: u64) -> Self {
+        self.fee_limit_margin = percent;
+        self
+    }
+
+    fn configure(&self, mut endpoint: Endpoint) -> Endpoint {
+        if let Some(timeout) = self.timeout {
+            endpoint = endpoint.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            endpoint = endpoint.connect_timeout(timeout);
+        }
+        endpoint
+    }
+
+    /// Build new GrpcClient instance (connects lazily, must be called within tokio runtime)
+    pub fn build(self) -> GrpcClient {
+        let fullnode = self.configure(self.fullnode.clone()).connect_lazy();
+        let solidity = match &self.solidity {
+            Some(endpoint) => self.configure(endpoint.clone()).connect_lazy(),
+            None => fullnode.clone(),
+        };
+        GrpcClient {
+            fullnode,
+            solidity,
+            fee_limit_margin: self.fee_limit_margin,
+        }
+    }
+}
+
+fn parse_endpoint(endpoint: &str) -> Result<Endpoint, crate::Error> {
+    Endpoint::from_shared(endpoint.to_owned()).map_err(|_| crate::Error::InvalidUrl)
+}
+
+/// gRPC client for java-tron nodes, returns the same types as RpcClient
+#[derive(Debug, Clone)]
+pub struct GrpcClient {
+    fullnode: Channel,
+    solidity: Channel,
+    fee_limit_margin: u64,
+}
+
+impl GrpcClient {
+    /// Create new GrpcClient with default params (connects lazily, must be called within tokio runtime)
+    pub fn new(endpoint: impl AsRef<str>) -> Result<Self, crate::Error> {
+        Ok(GrpcClientBuilder::new(endpoint)?.build())
+    }
+
+    /// Make unary call of `Wallet` (or `WalletSolidity` if `Consistency::Solidified`) service method
+    pub async fn call<Req, Resp>(
+        &self,
+        consistency: Consistency,
+        method: &str,
+        request: Req,
+    ) -> Result<Resp, crate::Error>
+    where
+        Req: Message + Send + Sync + 'static,
+        Resp: Message + Default + Send + Sync + 'static,
+    {
+        let (channel, service) = match consistency {
+            Consistency::Latest => (&self.fullnode, "Wallet"),
+            Consistency::Solidified => (&self.solidity, "WalletSolidity"),
+        };
+        let path = PathAndQuery::try_from(format!("/protocol.{}/{}", service, method))
+            .map_err(|e| crate::Error::ApiError(e.to_string()))?;
+        let mut grpc = tonic::client::Grpc::new(channel.clone());
+        grpc.ready()
+            .await
+            .map_err(|e| crate::Error::Transport(e.to_string()))?;
+        let resp = grpc
+            .unary(tonic::Request::new(request), path, ProstCodec::default())
+            .await?;
+        Ok(resp.into_inner())
+    }
+
+    /// Get latest block
+    pub async fn get_latest_block(&self) -> Result<Block, crate::Error> {
+        self.get_latest_block_with(Consistency::Latest).await
+    }
+
+    /// Get latest block with given consistency level
+    pub async fn get_latest_block_with(
+        &self,
+        consistency: Consistency,
+    ) -> Result<Block, crate::Error> {
+        let block: proto::BlockExtention = self
+            .call(consistency, "GetNowBlock2", proto::EmptyMessage {})
+            .await?;
+        convert::block(block)
+    }
+
+    /// Get block by id or number
+    pub async fn get_block(&self, by: BlockBy) -> Result<Block, crate::Error> {
+        self.get_block_with(by, Consistency::Latest).await
+    }
+
+    /// Get block by id or number with given consistency level
+    pub async fn get_block_with(
+        &self,
+        by: BlockBy,
+        consistency: Consistency,
+    ) -> Result<Block, crate::Error> {
+        let block: proto::BlockExtention = match by {
+            BlockBy::Number(number) => {
+                let block: proto::BlockExtention = self
+                    .call(
+                        consistency,
+                        "GetBlockByNum2",
+                        proto::NumberMessage { num: number as i64 },
+                    )
+                    .await?;
+                if block.blockid.is_empty() {
+                    return Err(crate::Error::MissingBlock(number));
+                }
+                block
+            }
+            BlockBy::Id(_) => {
+                self.call(
+                    consistency,
+                    "GetBlock",
+                    proto::BlockReq {
+                        id_or_num: by.id_or_num(),
+                        detail: true,
+                    },
+                )
+                .await?
+            }
+        };
+        convert::block(block)
+    }
+
+    /// Query account info. Returns `Error::AccountNotFound` if account is not activated
+    pub async fn get_account(&self, account: &Address) -> Result<Account, crate::Error> {
+        self.get_account_with(account, Consistency::Latest).await
+    }
+
+    /// Query account info with given consistency level
+    pub async fn get_account_with(
+        &self,
+        account: &Address,
+        consistency: Consistency,
+    ) -> Result<Account, crate::Error> {
+        let resp: proto::Account = self
+            .call(
+                consistency,
+                "GetAccount",
+                proto::Account {
+                    address: account.as_bytes().to_vec(),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        if resp.address.is_empty() {
+            return Err(crate::Error::AccountNotFound);
+        }
+        convert::account(resp)
+    }
+
+    /// Query account liquid TRX balance in SUN
+    pub async fn get_account_balance(&self, account: &Address) -> Result<u64, crate::Error> {
+        Ok(self.get_account(account).await?.balance)
+    }
+
+    /// Get transaction with its contract results (None if not found)
+    pub async fn get_tx_info_by_id(
+        &self,
+        txid: TransactionId,
+    ) -> Result<Option<TransactionInfo>, crate::Error> {
+        let tx: proto::Transaction = self
+            .call(
+                Consistency::Solidified,
+                "GetTransactionById",
+                proto::BytesMessage {
+                    value: txid.0.to_vec(),
+                },
+            )
+            .await?;
+        if tx.raw_data.is_empty() {
+            return Ok(None);
+        }
+        let ret = convert::transaction_rets(&tx);
+        Ok(Some(TransactionInfo {
+            transaction: convert::transaction(tx)?,
+            ret,
+        }))
+    }
+
+    /// Get transaction execution receipt of confirmed transaction
+    pub async fn get_tx_receipt(
+        &self,
+        txid: TransactionId,
+    ) -> Result<Option<TransactionReceipt>, crate::Error> {
+        self.get_tx_receipt_with(txid, Consistency::Solidified)
+            .await
+    }
+
+    /// Get transaction execution receipt with given consistency level
+    pub async fn get_tx_receipt_with(
+        &self,
+        txid: TransactionId,
+        consistency: Consistency,
+    ) -> Result<Option<TransactionReceipt>, crate::Error> {
+        let info: proto::TransactionInfo = self
+            .call(
+                consistency,
+                "GetTransactionInfoById",
+                proto::BytesMessage {
+                    value: txid.0.to_vec(),
+                },
+            )
+            .await?;
+        if info.id.is_empty() {
+            return Ok(None);
+        }
+        convert::receipt(info).map(Some)
+    }
+
+    /// Broadcast signed transaction (already known transaction is treated as success)
+    pub async fn broadcast_transaction(
+        &self,
+        tx: &Transaction,
+    ) -> Result<TransactionId, crate::Error> {
+        let resp: proto::Return = self
+            .call(
+                Consistency::Latest,
+                "BroadcastTransaction",
+                convert::signed_transaction(tx)?,
+            )
+            .await?;
+        match (resp.result, convert::broadcast_error_code(resp.code)) {
+            (true, _) | (_, BroadcastErrorCode::DupTransactionError) => Ok(tx.tx_id),
+            (false, code) => Err(crate::Error::BroadcastFailed(BroadcastError {
+                code,
+                message: String::from_utf8_lossy(&resp.message).into_owned(),
+            })),
+        }
+    }
+
+    /// Unwrap created transaction, optionally setting its fee limit
+    fn created_transaction(
+        resp: proto::TransactionExtention,
+        fee_limit: Option<u64>,
+    ) -> Result<Transaction, crate::Error> {
+        let result = resp.result.unwrap_or_default();
+        let mut tx = match resp.transaction {
+            Some(tx) if result.result && !tx.raw_data.is_empty() => tx,
+            _ => {
+                return Err(crate::Error::ApiError(format!(
+                    "{} {}",
+                    convert::return_code_name(result.code),
+                    String::from_utf8_lossy(&result.message)
+                )))
+            }
+        };
+        if let Some(fee_limit) = fee_limit {
+            convert::set_fee_limit(&mut tx, fee_limit)?;
+        }
+        convert::transaction(tx)
+    }
+
+    /** Create a TRX transfer transaction
+     ** from - Sender address
+     ** to - Receiver address
+     ** amount - Raw amount of TRX to transfer in SUN (1 TRX = 1,000,000 SUN)
+     */
+    pub async fn trx_transfer(
+        &self,
+        from: &Address,
+        to: &Address,
+        amount: u64,
+    ) -> Result<Transaction, crate::Error> {
+        let resp = self
+            .call(
+                Consistency::Latest,
+                "CreateTransaction2",
+                proto::TransferContract {
+                    owner_address: from.as_bytes().to_vec(),
+                    to_address: to.as_bytes().to_vec(),
+                    amount: amount as i64,
+                },
+            )
+            .await?;
+        Self::created_transaction(resp, None)
+    }
+
+    /** Create an account
+     ** payer - Activated account from which account creation fee should be deduced
+     ** account - Account address to create
+     */
+    pub async fn create_account(
+        &self,
+        payer: &Address,
+        account: &Address,
+    ) -> Result<Transaction, crate::Error> {
+        let resp = self
+            .call(
+                Consistency::Latest,
+                "CreateAccount2",
+                proto::AccountCreateContract {
+                    owner_address: payer.as_bytes().to_vec(),
+                    account_address: account.as_bytes().to_vec(),
+                    r#type: 0,
+                },
+            )
+            .await?;
+        Self::created_transaction(resp, None)
+    }
+
+    fn trigger_request(method_call: &MethodCall<'_>, value: u64) -> proto::TriggerSmartContract {
+        let mut data = alloy_primitives::keccak256(method_call.selector.as_bytes())[..4].to_vec();
+        data.extend_from_slice(&method_call.parameter);
+        proto::TriggerSmartContract {
+            owner_address: method_call.caller.as_bytes().to_vec(),
+            contract_address: method_call.contract.as_bytes().to_vec(),
+            call_value: value as i64,
+            data,
+            ..Default::default()
+        }
+    }
+
+    /** Call a smart contract method
+     ** method_call: Call parameters
+     ** value - Amount of TRX in SUN to send along with method call
+     ** fee_limit - Maximum TRX consumption, measured in SUN (1 TRX = 1,000,000 SUN)
+     */
+    pub async fn trigger_contract(
+        &self,
+        method_call: &MethodCall<'_>,
+        value: u64,
+        fee_limit: Option<u64>,
+    ) -> Result<Transaction, crate::Error> {
+        let fee_limit = match fee_limit {
+            Some(fee_limit) => fee_limit,
+            None => {
+                self.estimate_contract_energy(method_call, value)
+                    .await?
+                    .fee_limit
+            }
+        };
+        let resp = self
+            .call(
+                Consistency::Latest,
+                "TriggerContract",
+                Self::trigger_request(method_call, value),
+            )
+            .await?;
+        Self::created_transaction(resp, Some(fee_limit))
+    }
+
+    /** Query a smart contract view method
+     ** method_call: Call parameters
+     */
+    pub async fn query_contract(
+        &self,
+        method_call: &MethodCall<'_>,
+    ) -> Result<QueryContractResponse, crate::Error> {
+        self.query_contract_with(method_call, Consistency::Latest)
+            .await
+    }
+
+    /** Query a smart contract view method with given consistency level
+     ** method_call: Call parameters
+     */
+    pub async fn query_contract_with(
+        &self,
+        method_call: &MethodCall<'_>,
+        consistency: Consistency,
+    ) -> Result<QueryContractResponse, crate::Error> {
+        let resp = self
+            .constant_call(consistency, Self::trigger_request(method_call, 0))
+            .await?;
+        let contract_result = resp.contract_result();
+        if !contract_result.is_success() {
+            return Err(crate::Error::ContractExecutionFailed(
+                contract_result,
+                resp.constant_result(0)
+                    .ok()
+                    .and_then(|data| RevertReason::decode(&data)),
+            ));
+        }
+        Ok(resp)
+    }
+
+    /// TriggerConstantContract call (without checking contract result)
+    async fn constant_call(
+        &self,
+        consistency: Consistency,
+        request: proto::TriggerSmartContract,
+    ) -> Result<QueryContractResponse, crate::Error> {
+        let resp: proto::TransactionExtention = self
+            .call(consistency, "TriggerConstantContract", request)
+            .await?;
+        let result = resp.result.unwrap_or_default();
+        if !result.result {
+            let message = String::from_utf8_lossy(&result.message).into_owned();
+            if message.contains("Smart contract is not exist") {
+                return Err(crate::Error::ContractNotFound);
+            }
+            return Err(crate::Error::ContractQueryFailed(
+                convert::return_code_name(result.code),
+                message,
+            ));
+        }
+        let resp = QueryContractResponse {
+            code: None,
+            message: String::new(),
+            result: QueryResult::default(),
+            constant_result: resp.constant_result.iter().map(hex::encode).collect(),
+            energy_used: resp.energy_used as u64,
+            energy_penalty: resp.energy_penalty as u64,
+            transaction: resp.transaction.as_ref().map(|tx| QueryTransaction {
+                ret: convert::transaction_rets(tx),
+            }),
+        };
+        Ok(resp)
+    }
+
+    /** Estimate energy required by given smart contract call
+     ** method_call: Call parameters
+     */
+    pub async fn estimate_energy(&self, method_call: &MethodCall<'_>) -> Result<u64, crate::Error> {
+        match self
+            .required_energy(Self::trigger_request(method_call, 0))
+            .await?
+        {
+            (energy, 0, EnergySource::ConstantCall) => {
+                let info = self.contract_info(method_call.contract).await?;
+                Ok(apply_energy_factor(
+                    energy,
+                    info.contract_state.energy_factor,
+                ))
+            }
+            (energy, _, _) => Ok(energy),
+        }
+    }
+
+    /** Estimate energy of given smart contract call split between caller and contract origin
+     ** method_call: Call parameters
+     ** call_value: Amount of TRX in SUN sent along with method call
+     */
+    pub async fn estimate_contract_energy(
+        &self,
+        method_call: &MethodCall<'_>,
+        call_value: u64,
+    ) -> Result<EnergyEstimate, crate::Error> {
+        let info = self.contract_info(method_call.contract).await?;
+        let required = self
+            .required_energy(Self::trigger_request(method_call, call_value))
+            .await?;
+        let origin_limit = self.origin_energy_limit(&info).await?;
+        let params = self.get_chain_parameters().await?;
+        split_energy(
+            required,
+            &info,
+            origin_limit,
+            &params,
+            self.fee_limit_margin,
+        )
+    }
+
+    /** Estimate fee limit of given smart contract call (caller energy cost with safety margin)
+     ** method_call: Call parameters
+     */
+    pub async fn estimate_fee_limit(
+        &self,
+        method_call: &MethodCall<'_>,
+    ) -> Result<u64, crate::Error> {
+        Ok(self
+            .estimate_contract_energy(method_call, 0)
+            .await?
+            .fee_limit)
+    }
+
+    /// Required energy, included penalty and source; falls back to constant call
+    /// if node does not support EstimateEnergy (disabled by default)
+    async fn required_energy(
+        &self,
+        request: proto::TriggerSmartContract,
+    ) -> Result<(u64, u64, EnergySource), crate::Error> {
+        let resp: proto::EstimateEnergyMessage = match self
+            .call(Consistency::Latest, "EstimateEnergy", request.clone())
+            .await
+        {
+            Ok(resp) => resp,
+            // node predating EstimateEnergy
+            Err(crate::Error::GrpcStatus(tonic::Code::Unimplemented, _)) => {
+                return self.constant_call_energy(request).await
+            }
+            Err(e) => return Err(e),
+        };
+        let result = resp.result.unwrap_or_default();
+        if result.result {
+            return Ok((resp.energy_required as u64, 0, EnergySource::EstimateEnergy));
+        }
+        let message = String::from_utf8_lossy(&result.message).into_owned();
+        if message.contains(ESTIMATE_ENERGY_DISABLED) {
+            self.constant_call_energy(request).await
+        } else {
+            Err(crate::Error::ContractQueryFailed(
+                convert::return_code_name(result.code),
+                message,
+            ))
+        }
+    }
+
+    /// Energy used by constant call and included penalty
+    async fn constant_call_energy(
+        &self,
+        request: proto::TriggerSmartContract,
+    ) -> Result<(u64, u64, EnergySource), crate::Error> {
+        let resp = self.constant_call(Consistency::Latest, request).await?;
+        Ok((
+            resp.energy_used,
+            resp.energy_penalty,
+            EnergySource::ConstantCall,
+        ))
+    }
+
+    /// Contract info used by energy estimation (ABI is not included)
+    async fn contract_info(&self, contract: &Address) -> Result<ContractInfo, crate::Error> {
+        let resp: proto::SmartContractDataWrapper = self
+            .call(
+                Consistency::Latest,
+                "GetContractInfo",
+                proto::BytesMessage {
+                    value: contract.as_bytes().to_vec(),
+                },
+            )
+            .await?;
+        convert::contract_info(resp)
+    }
+
+    /// Energy contract origin is able to pay per call
+    async fn origin_energy_limit(&self, info: &ContractInfo) -> Result<u64, crate::Error> {
+        let contract = &info.smart_contract;
+        if !origin_pays(contract) {
+            return Ok(0);
+        }
+        let resources: proto::AccountResourceMessage = self
+            .call(
+                Consistency::Latest,
+                "GetAccountResource",
+                proto::Account {
+                    address: contract.origin_address.as_bytes().to_vec(),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        Ok((resources.energy_limit as u64)
+            .saturating_sub(resources.energy_used as u64)
+            .min(contract.origin_energy_limit))
+    }
+
+    /// Get chain parameters
+    pub async fn get_chain_parameters(&self) -> Result<BTreeMap<String, i64>, crate::Error> {
+        let resp: proto::ChainParameters = self
+            .call(
+                Consistency::Latest,
+                "GetChainParameters",
+                proto::EmptyMessage {},
+            )
+            .await?;
+        Ok(resp
+            .chain_parameter
+            .into_iter()
+            .map(|p| (p.key, p.value))
+            .collect())
+    }
+}
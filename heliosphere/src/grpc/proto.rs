@@ -0,0 +1,455 @@
+//! Subset of java-tron protocol messages (`core/Tron.proto`, `core/contract/*.proto`, `api/api.proto`)
+//! used by GrpcClient. Field tags match java-tron, unused fields are omitted (skipped on decode).
+//! Transaction raw data is kept as bytes so that signed payloads are re-encoded byte for byte,
+//! `TransactionRaw` has all fields so that setting fee limit preserves the rest of transaction.
+#![allow(missing_docs)]
+
+use std::collections::HashMap;
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EmptyMessage {}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NumberMessage {
+    #[prost(int64, tag = "1")]
+    pub num: i64,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BytesMessage {
+    #[prost(bytes = "vec", tag = "1")]
+    pub value: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlockReq {
+    #[prost(string, tag = "1")]
+    pub id_or_num: String,
+    #[prost(bool, tag = "2")]
+    pub detail: bool,
+}
+
+/// API call result (`Return.response_code` values in `code`)
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Return {
+    #[prost(bool, tag = "1")]
+    pub result: bool,
+    #[prost(int32, tag = "2")]
+    pub code: i32,
+    #[prost(bytes = "vec", tag = "3")]
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Any {
+    #[prost(string, tag = "1")]
+    pub type_url: String,
+    #[prost(bytes = "vec", tag = "2")]
+    pub value: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Contract {
+    #[prost(int32, tag = "1")]
+    pub r#type: i32,
+    #[prost(message, optional, tag = "2")]
+    pub parameter: Option<Any>,
+    #[prost(int32, tag = "5")]
+    pub permission_id: i32,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AccountId {
+    #[prost(bytes = "vec", tag = "1")]
+    pub name: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub address: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Authority {
+    #[prost(message, optional, tag = "1")]
+    pub account: Option<AccountId>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub permission_name: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransactionRaw {
+    #[prost(bytes = "vec", tag = "1")]
+    pub ref_block_bytes: Vec<u8>,
+    #[prost(int64, tag = "3")]
+    pub ref_block_num: i64,
+    #[prost(bytes = "vec", tag = "4")]
+    pub ref_block_hash: Vec<u8>,
+    #[prost(int64, tag = "8")]
+    pub expiration: i64,
+    #[prost(message, repeated, tag = "9")]
+    pub auths: Vec<Authority>,
+    #[prost(bytes = "vec", tag = "10")]
+    pub data: Vec<u8>,
+    #[prost(message, repeated, tag = "11")]
+    pub contract: Vec<Contract>,
+    #[prost(bytes = "vec", tag = "12")]
+    pub scripts: Vec<u8>,
+    #[prost(int64, tag = "14")]
+    pub timestamp: i64,
+    #[prost(int64, tag = "18")]
+    pub fee_limit: i64,
+}
+
+/// `Transaction.Result` (`contractResult` values in `contract_ret`)
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransactionResult {
+    #[prost(int64, tag = "1")]
+    pub fee: i64,
+    #[prost(int32, tag = "2")]
+    pub ret: i32,
+    #[prost(int32, tag = "3")]
+    pub contract_ret: i32,
+}
+
+/// Transaction with encoded `raw` message as `raw_data`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Transaction {
+    #[prost(bytes = "vec", tag = "1")]
+    pub raw_data: Vec<u8>,
+    #[prost(bytes = "vec", repeated, tag = "2")]
+    pub signature: Vec<Vec<u8>>,
+    #[prost(message, repeated, tag = "5")]
+    pub ret: Vec<TransactionResult>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransactionExtention {
+    #[prost(message, optional, tag = "1")]
+    pub transaction: Option<Transaction>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub txid: Vec<u8>,
+    #[prost(bytes = "vec", repeated, tag = "3")]
+    pub constant_result: Vec<Vec<u8>>,
+    #[prost(message, optional, tag = "4")]
+    pub result: Option<Return>,
+    #[prost(int64, tag = "5")]
+    pub energy_used: i64,
+    #[prost(int64, tag = "8")]
+    pub energy_penalty: i64,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlockHeaderRaw {
+    #[prost(int64, tag = "1")]
+    pub timestamp: i64,
+    #[prost(bytes = "vec", tag = "2")]
+    pub tx_trie_root: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub parent_hash: Vec<u8>,
+    #[prost(int64, tag = "7")]
+    pub number: i64,
+    #[prost(bytes = "vec", tag = "9")]
+    pub witness_address: Vec<u8>,
+    #[prost(int32, tag = "10")]
+    pub version: i32,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlockHeader {
+    #[prost(message, optional, tag = "1")]
+    pub raw_data: Option<BlockHeaderRaw>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub witness_signature: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlockExtention {
+    #[prost(message, repeated, tag = "1")]
+    pub transactions: Vec<TransactionExtention>,
+    #[prost(message, optional, tag = "2")]
+    pub block_header: Option<BlockHeader>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub blockid: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Vote {
+    #[prost(bytes = "vec", tag = "1")]
+    pub vote_address: Vec<u8>,
+    #[prost(int64, tag = "2")]
+    pub vote_count: i64,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Key {
+    #[prost(bytes = "vec", tag = "1")]
+    pub address: Vec<u8>,
+    #[prost(int64, tag = "2")]
+    pub weight: i64,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Permission {
+    #[prost(int32, tag = "1")]
+    pub r#type: i32,
+    #[prost(int32, tag = "2")]
+    pub id: i32,
+    #[prost(string, tag = "3")]
+    pub permission_name: String,
+    #[prost(int64, tag = "4")]
+    pub threshold: i64,
+    #[prost(int32, tag = "5")]
+    pub parent_id: i32,
+    #[prost(bytes = "vec", tag = "6")]
+    pub operations: Vec<u8>,
+    #[prost(message, repeated, tag = "7")]
+    pub keys: Vec<Key>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AccountResource {
+    #[prost(int64, tag = "3")]
+    pub latest_consume_time_for_energy: i64,
+    #[prost(int64, tag = "9")]
+    pub energy_window_size: i64,
+    #[prost(int64, tag = "10")]
+    pub delegated_frozen_v2_balance_for_energy: i64,
+    #[prost(int64, tag = "11")]
+    pub acquired_delegated_frozen_v2_balance_for_energy: i64,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FreezeV2 {
+    #[prost(int32, tag = "1")]
+    pub r#type: i32,
+    #[prost(int64, tag = "2")]
+    pub amount: i64,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UnFreezeV2 {
+    #[prost(int32, tag = "1")]
+    pub r#type: i32,
+    #[prost(int64, tag = "3")]
+    pub unfreeze_amount: i64,
+    #[prost(int64, tag = "4")]
+    pub unfreeze_expire_time: i64,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Account {
+    #[prost(bytes = "vec", tag = "3")]
+    pub address: Vec<u8>,
+    #[prost(int64, tag = "4")]
+    pub balance: i64,
+    #[prost(message, repeated, tag = "5")]
+    pub votes: Vec<Vote>,
+    #[prost(int64, tag = "9")]
+    pub create_time: i64,
+    #[prost(int64, tag = "10")]
+    pub latest_opration_time: i64,
+    #[prost(int64, tag = "11")]
+    pub allowance: i64,
+    #[prost(int64, tag = "12")]
+    pub latest_withdraw_time: i64,
+    #[prost(int64, tag = "21")]
+    pub latest_consume_time: i64,
+    #[prost(int64, tag = "22")]
+    pub latest_consume_free_time: i64,
+    #[prost(message, optional, tag = "26")]
+    pub account_resource: Option<AccountResource>,
+    #[prost(message, optional, tag = "31")]
+    pub owner_permission: Option<Permission>,
+    #[prost(message, repeated, tag = "33")]
+    pub active_permission: Vec<Permission>,
+    #[prost(message, repeated, tag = "34")]
+    pub frozen_v2: Vec<FreezeV2>,
+    #[prost(message, repeated, tag = "35")]
+    pub unfrozen_v2: Vec<UnFreezeV2>,
+    #[prost(int64, tag = "36")]
+    pub delegated_frozen_v2_balance_for_bandwidth: i64,
+    #[prost(int64, tag = "37")]
+    pub acquired_delegated_frozen_v2_balance_for_bandwidth: i64,
+    #[prost(map = "string, int64", tag = "56")]
+    pub asset_v2: HashMap<String, i64>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Log {
+    #[prost(bytes = "vec", tag = "1")]
+    pub address: Vec<u8>,
+    #[prost(bytes = "vec", repeated, tag = "2")]
+    pub topics: Vec<Vec<u8>>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResourceReceipt {
+    #[prost(int64, tag = "1")]
+    pub energy_usage: i64,
+    #[prost(int64, tag = "2")]
+    pub energy_fee: i64,
+    #[prost(int64, tag = "3")]
+    pub origin_energy_usage: i64,
+    #[prost(int64, tag = "4")]
+    pub energy_usage_total: i64,
+    #[prost(int64, tag = "5")]
+    pub net_usage: i64,
+    #[prost(int64, tag = "6")]
+    pub net_fee: i64,
+    #[prost(int32, tag = "7")]
+    pub result: i32,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CallValueInfo {
+    #[prost(int64, tag = "1")]
+    pub call_value: i64,
+    #[prost(string, tag = "2")]
+    pub token_id: String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InternalTransaction {
+    #[prost(bytes = "vec", tag = "1")]
+    pub hash: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub caller_address: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub transfer_to_address: Vec<u8>,
+    #[prost(message, repeated, tag = "4")]
+    pub call_value_info: Vec<CallValueInfo>,
+    #[prost(bytes = "vec", tag = "5")]
+    pub note: Vec<u8>,
+    #[prost(bool, tag = "6")]
+    pub rejected: bool,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransactionInfo {
+    #[prost(bytes = "vec", tag = "1")]
+    pub id: Vec<u8>,
+    #[prost(int64, tag = "2")]
+    pub fee: i64,
+    #[prost(int64, tag = "3")]
+    pub block_number: i64,
+    #[prost(int64, tag = "4")]
+    pub block_time_stamp: i64,
+    #[prost(bytes = "vec", repeated, tag = "5")]
+    pub contract_result: Vec<Vec<u8>>,
+    #[prost(bytes = "vec", tag = "6")]
+    pub contract_address: Vec<u8>,
+    #[prost(message, optional, tag = "7")]
+    pub receipt: Option<ResourceReceipt>,
+    #[prost(message, repeated, tag = "8")]
+    pub log: Vec<Log>,
+    #[prost(int32, tag = "9")]
+    pub result: i32,
+    #[prost(bytes = "vec", tag = "10")]
+    pub res_message: Vec<u8>,
+    #[prost(message, repeated, tag = "17")]
+    pub internal_transactions: Vec<InternalTransaction>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransferContract {
+    #[prost(bytes = "vec", tag = "1")]
+    pub owner_address: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub to_address: Vec<u8>,
+    #[prost(int64, tag = "3")]
+    pub amount: i64,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AccountCreateContract {
+    #[prost(bytes = "vec", tag = "1")]
+    pub owner_address: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub account_address: Vec<u8>,
+    #[prost(int32, tag = "3")]
+    pub r#type: i32,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TriggerSmartContract {
+    #[prost(bytes = "vec", tag = "1")]
+    pub owner_address: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub contract_address: Vec<u8>,
+    #[prost(int64, tag = "3")]
+    pub call_value: i64,
+    #[prost(bytes = "vec", tag = "4")]
+    pub data: Vec<u8>,
+    #[prost(int64, tag = "5")]
+    pub call_token_value: i64,
+    #[prost(int64, tag = "6")]
+    pub token_id: i64,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChainParameter {
+    #[prost(string, tag = "1")]
+    pub key: String,
+    #[prost(int64, tag = "2")]
+    pub value: i64,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChainParameters {
+    #[prost(message, repeated, tag = "1")]
+    pub chain_parameter: Vec<ChainParameter>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EstimateEnergyMessage {
+    #[prost(message, optional, tag = "1")]
+    pub result: Option<Return>,
+    #[prost(int64, tag = "2")]
+    pub energy_required: i64,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SmartContract {
+    #[prost(bytes = "vec", tag = "1")]
+    pub origin_address: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub contract_address: Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub bytecode: Vec<u8>,
+    #[prost(int64, tag = "6")]
+    pub consume_user_resource_percent: i64,
+    #[prost(string, tag = "7")]
+    pub name: String,
+    #[prost(int64, tag = "8")]
+    pub origin_energy_limit: i64,
+    #[prost(bytes = "vec", tag = "9")]
+    pub code_hash: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ContractState {
+    #[prost(int64, tag = "1")]
+    pub energy_usage: i64,
+    #[prost(int64, tag = "2")]
+    pub energy_factor: i64,
+    #[prost(int64, tag = "3")]
+    pub update_cycle: i64,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SmartContractDataWrapper {
+    #[prost(message, optional, tag = "1")]
+    pub smart_contract: Option<SmartContract>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub runtimecode: Vec<u8>,
+    #[prost(message, optional, tag = "3")]
+    pub contract_state: Option<ContractState>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AccountResourceMessage {
+    #[prost(int64, tag = "13")]
+    pub energy_used: i64,
+    #[prost(int64, tag = "14")]
+    pub energy_limit: i64,
+}
@@ -3,9 +3,16 @@
 #![forbid(unsafe_code)]
 #![deny(missing_docs)]
 
+pub mod abi;
 mod client;
 pub use client::*;
 mod error;
 pub use error::Error;
+pub mod event;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub use alloy_primitives::{I256, U256};
 pub use heliosphere_core as core;
+#[cfg(feature = "macros")]
+pub use heliosphere_macros::contract;
 pub use heliosphere_signer as signer;
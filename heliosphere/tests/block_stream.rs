@@ -0,0 +1,200 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use futures::StreamExt;
+use heliosphere::{
+    BlockEvent, BlockStreamOptions, Error, HttpResponse, MockTransport, RetryPolicy,
+    RpcClientBuilder,
+};
+use serde_json::json;
+
+const FORK: u64 = 10;
+
+/// Block id of given height, blocks after fork point differ once `forked`
+fn block_id(number: u64, forked: bool) -> String {
+    let branch = (forked && number > FORK) as u8;
+    format!("{:02x}{:062x}", branch, number)
+}
+
+fn block(number: u64, forked: bool) -> serde_json::Value {
+    json!({
+        "blockID": block_id(number, forked),
+        "block_header": {
+            "raw_data": {
+                "number": number,
+                "txTrieRoot": "00".repeat(32),
+                "witness_address": "410b53ce4aa6f0c2f3c849f11f682702ec99622e2e",
+                "parentHash": block_id(number - 1, forked),
+                "version": 30,
+                "timestamp": 1680000000000u64 + number * 3000,
+            },
+            "witness_signature": "00",
+        }
+    })
+}
+
+/// Chain with head 12 which switches to branch forked after block 10 with head 13,
+/// single blocks can not be fetched while `failing`
+fn transport(forked: Arc<AtomicBool>, failing: Arc<AtomicBool>) -> MockTransport {
+    let by_num = forked.clone();
+    MockTransport::new()
+        .with_handler("/wallet/getblock", move |req| {
+            let forked = forked.load(Ordering::SeqCst);
+            let body = req.body.as_ref().unwrap();
+            let number = match body["id_or_num"].as_str() {
+                Some(_) if failing.load(Ordering::SeqCst) => {
+                    return Ok(HttpResponse::new(500, "unavailable"));
+                }
+                Some(number) => number.parse().unwrap(),
+                None => 12 + forked as u64,
+            };
+            Ok(HttpResponse::json(&block(number, forked)))
+        })
+        .with_handler("/wallet/getblockbylimitnext", move |req| {
+            let forked = by_num.load(Ordering::SeqCst);
+            let body = req.body.as_ref().unwrap();
+            let (start, end) = (
+                body["startNum"].as_u64().unwrap(),
+                body["endNum"].as_u64().unwrap(),
+            );
+            let blocks: Vec<_> = (start..end).map(|x| block(x, forked)).collect();
+            Ok(HttpResponse::json(&json!({ "block": blocks })))
+        })
+}
+
+fn numbers(blocks: &[heliosphere_core::block::Block]) -> Vec<u64> {
+    blocks.iter().map(|x| x.block_number()).collect()
+}
+
+#[tokio::test]
+async fn test_block_stream_reorg() {
+    let forked = Arc::new(AtomicBool::new(false));
+    let client = RpcClientBuilder::new("http://fullnode")
+        .unwrap()
+        .with_transport(transport(forked.clone(), Arc::default()))
+        .build();
+    let mut stream = Box::pin(client.block_stream(BlockStreamOptions::default().with_start(FORK)));
+    for number in FORK..=12 {
+        match stream.next().await.unwrap().unwrap() {
+            BlockEvent::Block(block) => assert_eq!(block.block_number(), number),
+            event => panic!("unexpected event {:?}", event),
+        }
+    }
+
+    forked.store(true, Ordering::SeqCst);
+    match stream.next().await.unwrap().unwrap() {
+        BlockEvent::Reorg { dropped, new } => {
+            assert_eq!(numbers(&dropped), [11, 12]);
+            assert_eq!(numbers(&new), [11, 12, 13]);
+            assert_eq!(dropped[0].block_id.to_string(), block_id(11, false));
+            assert_eq!(new[0].block_id.to_string(), block_id(11, true));
+        }
+        event => panic!("unexpected event {:?}", event),
+    }
+}
+
+#[tokio::test]
+async fn test_block_stream_reorg_too_deep() {
+    let forked = Arc::new(AtomicBool::new(false));
+    let client = RpcClientBuilder::new("http://fullnode")
+        .unwrap()
+        .with_transport(transport(forked.clone(), Arc::default()))
+        .build();
+    let options = BlockStreamOptions::default()
+        .with_start(FORK)
+        .with_max_reorg_depth(2);
+    let mut stream = Box::pin(client.block_stream(options));
+    for _ in FORK..=12 {
+        assert!(matches!(
+            stream.next().await.unwrap(),
+            Ok(BlockEvent::Block(_))
+        ));
+    }
+
+    // only blocks 11 and 12 are remembered, fork point 10 is unknown
+    forked.store(true, Ordering::SeqCst);
+    assert!(matches!(
+        stream.next().await.unwrap(),
+        Err(Error::ReorgTooDeep(2))
+    ));
+    match stream.next().await.unwrap().unwrap() {
+        BlockEvent::Block(block) => {
+            assert_eq!(block.block_id.to_string(), block_id(13, true))
+        }
+        event => panic!("unexpected event {:?}", event),
+    }
+}
+
+#[tokio::test]
+async fn test_block_stream_reorg_fetch_error() {
+    let forked = Arc::new(AtomicBool::new(false));
+    let failing = Arc::new(AtomicBool::new(false));
+    let client = RpcClientBuilder::new("http://fullnode")
+        .unwrap()
+        .with_transport(transport(forked.clone(), failing.clone()))
+        .build();
+    let mut stream = Box::pin(client.block_stream(BlockStreamOptions::default().with_start(FORK)));
+    for _ in FORK..=12 {
+        assert!(matches!(
+            stream.next().await.unwrap(),
+            Ok(BlockEvent::Block(_))
+        ));
+    }
+
+    // replacement of block 12 can not be fetched, remembered blocks stay intact
+    forked.store(true, Ordering::SeqCst);
+    failing.store(true, Ordering::SeqCst);
+    assert!(matches!(
+        stream.next().await.unwrap(),
+        Err(Error::HttpStatus(500, _))
+    ));
+    failing.store(false, Ordering::SeqCst);
+    match stream.next().await.unwrap().unwrap() {
+        BlockEvent::Reorg { dropped, new } => {
+            assert_eq!(numbers(&dropped), [11, 12]);
+            assert_eq!(numbers(&new), [11, 12, 13]);
+        }
+        event => panic!("unexpected event {:?}", event),
+    }
+}
+
+#[tokio::test]
+async fn test_block_range_fallback() {
+    // node serving single blocks only
+    let single = || {
+        MockTransport::new().with_handler("/wallet/getblock", |req| {
+            let number = req.body.as_ref().unwrap()["id_or_num"].as_str().unwrap();
+            Ok(HttpResponse::json(&block(number.parse().unwrap(), false)))
+        })
+    };
+    let unsupported = single();
+    let limited = single().with_handler("/wallet/getblockbylimitnext", |_| {
+        Ok(HttpResponse::new(429, "rate limited"))
+    });
+
+    // getblockbylimitnext is not supported, blocks are fetched one by one
+    let client = RpcClientBuilder::new("http://fullnode")
+        .unwrap()
+        .with_transport(unsupported.clone())
+        .build();
+    let blocks = client.get_block_range(5..8).await.unwrap();
+    assert_eq!(numbers(&blocks), [5, 6, 7]);
+    assert_eq!(unsupported.requests().len(), 4);
+
+    // other errors are not hidden by single block requests
+    let client = RpcClientBuilder::new("http://fullnode")
+        .unwrap()
+        .with_transport(limited.clone())
+        .with_retry_policy(RetryPolicy::none())
+        .build();
+    assert!(matches!(
+        client.get_block_range(5..8).await,
+        Err(Error::HttpStatus(429, _))
+    ));
+    assert!(limited
+        .requests()
+        .iter()
+        .all(|x| !x.url.ends_with("/wallet/getblock")));
+}
@@ -0,0 +1,28 @@
+use heliosphere::{BroadcastError, BroadcastErrorCode, Error};
+
+#[test]
+fn test_broadcast_error_codes() {
+    let codes: Vec<BroadcastErrorCode> = serde_json::from_str(
+        r#"["SIGERROR", "BANDWITH_ERROR", "TAPOS_ERROR", "SERVER_BUSY", "NOT_ENOUGH_EFFECTIVE_CONNECTION", "NEW_CODE"]"#,
+    )
+    .unwrap();
+    assert_eq!(
+        codes,
+        [
+            BroadcastErrorCode::SigError,
+            BroadcastErrorCode::BandwidthError,
+            BroadcastErrorCode::TaposError,
+            BroadcastErrorCode::ServerBusy,
+            BroadcastErrorCode::NotEnoughEffectiveConnection,
+            BroadcastErrorCode::Unknown,
+        ]
+    );
+    let retryable: Vec<bool> = codes.iter().map(|x| x.is_retryable()).collect();
+    assert_eq!(retryable, [false, false, false, true, true, false]);
+
+    let err = Error::BroadcastFailed(BroadcastError {
+        code: BroadcastErrorCode::ServerBusy,
+        message: "Server busy".to_owned(),
+    });
+    assert!(err.is_retryable());
+}
@@ -0,0 +1,65 @@
+//! Fixtures shared by mock transport tests
+#![allow(dead_code)]
+
+use heliosphere::{MockTransport, RpcClient, RpcClientBuilder};
+use heliosphere_core::Address;
+use serde_json::json;
+
+/// Contract (token) address
+pub const CONTRACT: &str = "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t";
+/// Sender, contract owner
+pub const FROM: &str = "TB9n2jzcWoqta1xX2Mv8P3y9tyUNsGTFsQ";
+/// Recipient with existing account
+pub const TO: &str = "TG3XXyExBkPp9nzdajDZsozEu4BkaSJozs";
+/// Address without account
+pub const INACTIVE: &str = "TQn9Y2khEsLJW1ChVWFMSMeRDow5KcbLSE";
+/// Timestamp of mock transactions and head block
+pub const TIMESTAMP: u64 = 1_700_000_000_000;
+
+/// ABI encoded uint
+pub fn word(value: u64) -> String {
+    format!("{:064x}", value)
+}
+
+/// ABI encoded address
+pub fn address(address: &Address) -> String {
+    format!("{:0>64}", hex::encode(&address.as_bytes()[1..]))
+}
+
+/// ABI encoded string as single return value
+pub fn string(s: &str) -> String {
+    format!(
+        "{}{}{:0<64}",
+        word(32),
+        word(s.len() as u64),
+        hex::encode(s)
+    )
+}
+
+/// Hex address as used by HTTP API
+pub fn hex_address(address: &str) -> String {
+    address.parse::<Address>().unwrap().as_hex()
+}
+
+/// Unsigned transaction without contracts, expiring a minute after `TIMESTAMP`
+pub fn transaction() -> serde_json::Value {
+    json!({
+        "txID": "00".repeat(32),
+        "raw_data_hex": "0a02",
+        "raw_data": {
+            "contract": [],
+            "ref_block_bytes": "1234",
+            "ref_block_hash": "5656565656565656",
+            "expiration": TIMESTAMP + 60_000,
+            "timestamp": TIMESTAMP
+        }
+    })
+}
+
+/// Client using given mock transport
+pub fn client(transport: &MockTransport) -> RpcClient {
+    RpcClientBuilder::new("http://fullnode")
+        .unwrap()
+        .with_transport(transport.clone())
+        .build()
+}
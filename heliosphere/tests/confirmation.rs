@@ -0,0 +1,161 @@
+mod common;
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use common::client;
+use futures::StreamExt;
+use heliosphere::{
+    ConfirmationEvent, ConfirmationLevel, ConfirmationOptions, Error, HttpResponse, MockTransport,
+};
+use heliosphere_core::transaction::Transaction;
+use serde_json::json;
+
+const INCLUDED_IN: u64 = 101;
+
+fn timestamp(number: u64) -> u64 {
+    1_680_000_000_000 + number * 3000
+}
+
+fn head_block(number: u64) -> serde_json::Value {
+    json!({
+        "blockID": format!("{:064x}", number),
+        "block_header": {
+            "raw_data": {
+                "number": number,
+                "txTrieRoot": "00".repeat(32),
+                "witness_address": "410b53ce4aa6f0c2f3c849f11f682702ec99622e2e",
+                "parentHash": format!("{:064x}", number - 1),
+                "version": 30,
+                "timestamp": timestamp(number),
+            },
+            "witness_signature": "00",
+        }
+    })
+}
+
+fn receipt(result: &str) -> serde_json::Value {
+    json!({
+        "id": "00".repeat(32),
+        "blockNumber": INCLUDED_IN,
+        "blockTimeStamp": timestamp(INCLUDED_IN),
+        "receipt": { "result": result }
+    })
+}
+
+/// Chain advancing by one block on every head poll. Transaction is seen by fullnode
+/// in heads listed by `included`, solidified from `solidified_at` head.
+fn chain(
+    included: impl Fn(u64) -> bool + Send + Sync + 'static,
+    solidified_at: u64,
+    result: &'static str,
+) -> MockTransport {
+    let head = Arc::new(AtomicU64::new(99));
+    let (latest, solid) = (head.clone(), head.clone());
+    MockTransport::new()
+        .with_handler("/wallet/getblock", move |_| {
+            let number = head.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(HttpResponse::json(&head_block(number)))
+        })
+        .with_handler("/wallet/gettransactioninfobyid", move |_| {
+            Ok(HttpResponse::json(
+                &match included(latest.load(Ordering::SeqCst)) {
+                    true => receipt(result),
+                    false => json!({}),
+                },
+            ))
+        })
+        .with_handler("/walletsolidity/gettransactioninfobyid", move |_| {
+            Ok(HttpResponse::json(
+                &match solid.load(Ordering::SeqCst) >= solidified_at {
+                    true => receipt(result),
+                    false => json!({}),
+                },
+            ))
+        })
+}
+
+fn transaction(expiration: u64) -> Transaction {
+    let mut tx = common::transaction();
+    tx["raw_data"]["expiration"] = json!(expiration);
+    serde_json::from_value(tx).unwrap()
+}
+
+fn polling(level: ConfirmationLevel) -> ConfirmationOptions {
+    ConfirmationOptions::new(level).with_poll_interval(Duration::from_millis(1))
+}
+
+/// All events of confirmation stream
+async fn track(
+    transport: &MockTransport,
+    tx: &Transaction,
+    options: ConfirmationOptions,
+) -> Vec<Result<ConfirmationEvent, Error>> {
+    client(transport)
+        .confirmation_stream(tx, options)
+        .collect()
+        .await
+}
+
+#[tokio::test]
+async fn test_confirmation_levels() {
+    let tx = transaction(timestamp(200));
+
+    // first poll at head 100 does not see transaction yet
+    let transport = chain(|head| head >= INCLUDED_IN, u64::MAX, "SUCCESS");
+    let events = track(&transport, &tx, polling(ConfirmationLevel::Depth(3))).await;
+    assert!(
+        matches!(events[0], Ok(ConfirmationEvent::SeenInBlock(ref x)) if x.block_number == INCLUDED_IN)
+    );
+    assert!(matches!(events[1], Ok(ConfirmationEvent::Confirmations(2))));
+    assert!(matches!(events[2], Ok(ConfirmationEvent::Finalized(_))));
+    assert_eq!(events.len(), 3);
+
+    let transport = chain(|head| head >= INCLUDED_IN, 103, "SUCCESS");
+    let events = track(&transport, &tx, polling(ConfirmationLevel::Solidified)).await;
+    assert!(matches!(events[0], Ok(ConfirmationEvent::SeenInBlock(_))));
+    assert!(matches!(events[1], Ok(ConfirmationEvent::Confirmations(2))));
+    assert!(matches!(events[2], Ok(ConfirmationEvent::Finalized(_))));
+    assert_eq!(events.len(), 3);
+    assert!(transport
+        .requests()
+        .iter()
+        .any(|x| x.url.ends_with("/walletsolidity/gettransactioninfobyid")));
+
+    let transport = chain(|head| head >= INCLUDED_IN, u64::MAX, "SUCCESS");
+    let events = track(&transport, &tx, polling(ConfirmationLevel::Included)).await;
+    assert!(matches!(events[0], Ok(ConfirmationEvent::SeenInBlock(_))));
+    assert!(matches!(events[1], Ok(ConfirmationEvent::Finalized(_))));
+    assert_eq!(events.len(), 2);
+}
+
+#[tokio::test]
+async fn test_confirmation_failures() {
+    // reverted transaction fails once it is seen
+    let tx = transaction(timestamp(200));
+    let transport = chain(|head| head >= INCLUDED_IN, u64::MAX, "REVERT");
+    let events = track(&transport, &tx, polling(ConfirmationLevel::Included)).await;
+    match &events[..] {
+        [Err(Error::TxFailed(receipt))] => assert_eq!(receipt.block_number, INCLUDED_IN),
+        events => panic!("unexpected events {:?}", events),
+    }
+
+    // transaction dropped by reorg at head 102 expires before being included again
+    let tx = transaction(timestamp(103) - 1);
+    let transport = chain(|head| head == INCLUDED_IN, u64::MAX, "SUCCESS");
+    let events = track(&transport, &tx, polling(ConfirmationLevel::Depth(5))).await;
+    assert!(matches!(events[0], Ok(ConfirmationEvent::SeenInBlock(_))));
+    assert!(matches!(events[1], Err(Error::TxExpired(_))));
+    assert_eq!(events.len(), 2);
+
+    let tx = transaction(timestamp(1_000_000));
+    let transport = chain(|_| false, u64::MAX, "SUCCESS");
+    let options = polling(ConfirmationLevel::Included).with_timeout(Duration::from_millis(20));
+    let events = track(&transport, &tx, options).await;
+    assert!(matches!(events[..], [Err(Error::Timeout)]));
+}
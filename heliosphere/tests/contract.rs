@@ -0,0 +1,182 @@
+mod common;
+
+use common::{address, client, hex_address, transaction, word, CONTRACT, FROM};
+use heliosphere::{abi::AbiValue, Error, HttpResponse, MethodCall, MockTransport, U256};
+use heliosphere_core::Address;
+use serde_json::json;
+
+fn smart_contract() -> serde_json::Value {
+    json!({
+        "origin_address": hex_address(FROM),
+        "contract_address": hex_address(CONTRACT),
+        "abi": { "entrys": [
+            { "outputs": [{ "type": "uint256" }], "constant": true, "name": "balanceOf",
+              "inputs": [{ "name": "who", "type": "address" }], "stateMutability": "View", "type": "Function" },
+            { "name": "balanceOf", "stateMutability": "View", "type": "Function",
+              "inputs": [{ "name": "who", "type": "address" }, { "name": "id", "type": "uint256" }],
+              "outputs": [{ "type": "uint256" }] },
+            { "name": "setConfig", "stateMutability": "Nonpayable", "type": "Function",
+              "inputs": [{ "name": "config", "type": "tuple", "components": [
+                  { "name": "fee", "type": "uint16" }, { "name": "salt", "type": "bytes4" }] },
+                  { "name": "admins", "type": "address[]" }] },
+            { "type": "Event", "name": "Transfer", "inputs": [] }
+        ]},
+        "bytecode": "6080",
+        "consume_user_resource_percent": 30,
+        "name": "Token",
+        "origin_energy_limit": 10_000_000,
+        "code_hash": "ab".repeat(32)
+    })
+}
+
+fn transport() -> MockTransport {
+    MockTransport::new()
+        .with_handler("/wallet/getcontract", |req| {
+            let found = req.body.as_ref().unwrap()["value"] == hex_address(CONTRACT);
+            Ok(HttpResponse::json(&match found {
+                true => smart_contract(),
+                false => json!({}),
+            }))
+        })
+        .with_response(
+            "/wallet/getcontractinfo",
+            json!({
+                "runtimecode": "6080aa",
+                "smart_contract": smart_contract(),
+                "contract_state": { "energy_usage": 500, "energy_factor": 3400, "update_cycle": 7 }
+            }),
+        )
+        .with_response(
+            "/wallet/triggerconstantcontract",
+            json!({ "result": { "result": true }, "constant_result": [word(77)] }),
+        )
+        .with_response(
+            "/wallet/triggersmartcontract",
+            json!({ "result": { "result": true }, "transaction": transaction() }),
+        )
+}
+
+#[tokio::test]
+async fn test_get_contract() {
+    let client = client(&transport());
+    let contract = client
+        .get_contract(&CONTRACT.parse().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(contract.origin_address, FROM.parse().unwrap());
+    assert_eq!(contract.name, "Token");
+    assert_eq!(contract.bytecode, [0x60, 0x80]);
+    assert_eq!(contract.consume_user_resource_percent, 30);
+    assert_eq!(contract.origin_energy_limit, 10_000_000);
+    assert_eq!(contract.code_hash, [0xab; 32]);
+    assert_eq!(contract.abi.functions.len(), 3);
+    assert_eq!(contract.abi.events.len(), 1);
+
+    let info = client
+        .get_contract_info(&CONTRACT.parse().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(info.runtime_code, [0x60, 0x80, 0xaa]);
+    assert_eq!(info.contract_state.energy_factor, 3400);
+    assert_eq!(info.smart_contract, contract);
+
+    assert!(matches!(
+        client.get_contract(&FROM.parse().unwrap()).await,
+        Err(Error::ContractNotFound)
+    ));
+}
+
+#[tokio::test]
+async fn test_dynamic_contract() {
+    let transport = transport();
+    let client = client(&transport);
+    let contract = client
+        .dynamic_contract(&CONTRACT.parse().unwrap())
+        .await
+        .unwrap();
+    let owner: Address = FROM.parse().unwrap();
+
+    // overload resolved by number of arguments
+    let balance = contract
+        .query("balanceOf", &[json!(FROM), json!("0x10")])
+        .await
+        .unwrap();
+    assert_eq!(balance, [AbiValue::Uint(U256::from(77))]);
+    assert_eq!(balance[0].to_json(), json!("77"));
+    let query = transport.requests().pop().unwrap().body.unwrap();
+    assert_eq!(query["function_selector"], "balanceOf(address,uint256)");
+    assert_eq!(
+        query["parameter"],
+        format!("{:0>64}{}", hex::encode(&owner.as_bytes()[1..]), word(16))
+    );
+
+    contract
+        .call(
+            &owner,
+            "setConfig((uint16,bytes4),address[])",
+            &[
+                json!({ "fee": 30, "salt": "0xdeadbeef" }),
+                json!([
+                    owner.as_hex(),
+                    format!("0x{}", hex::encode(&owner.as_bytes()[1..]))
+                ]),
+            ],
+        )
+        .unwrap()
+        .with_fee_limit(100)
+        .build()
+        .await
+        .unwrap();
+    let trigger = transport.requests().pop().unwrap().body.unwrap();
+    assert_eq!(
+        trigger["function_selector"],
+        "setConfig((uint16,bytes4),address[])"
+    );
+    // tuple (2 words), offset, length, 2 addresses
+    assert_eq!(trigger["parameter"].as_str().unwrap().len(), 6 * 64);
+
+    let invalid = [
+        ("balanceOf", vec![json!("not an address")]),
+        ("balanceOf", vec![json!(FROM), json!(-1)]),
+        ("balanceOf", vec![]),
+        ("setConfig", vec![json!([70000, "0xdeadbeef"]), json!([])]),
+        (
+            "setConfig",
+            vec![json!({ "fee": 1, "salt": "0xdead" }), json!([])],
+        ),
+    ];
+    for (method, args) in invalid {
+        assert!(matches!(
+            contract.call(&owner, method, &args),
+            Err(Error::AbiError(_))
+        ));
+    }
+}
+
+#[test]
+fn test_method_call_new() {
+    let contract: Address = CONTRACT.parse().unwrap();
+    let owner: Address = FROM.parse().unwrap();
+    let call = MethodCall::new(
+        &owner,
+        &contract,
+        "transfer(address,uint256)",
+        &[owner.into(), U256::from(7).into()],
+    )
+    .unwrap();
+    assert_eq!(
+        hex::encode(&call.parameter),
+        format!("{}{}", address(&owner), word(7))
+    );
+
+    for (selector, args) in [
+        ("transfer(address,uint256)", vec![owner.into()]),
+        ("transfer(address,uint256)", vec![owner.into(), true.into()]),
+        ("transfer", vec![]),
+    ] {
+        assert!(matches!(
+            MethodCall::new(&owner, &contract, selector, &args),
+            Err(Error::AbiError(_))
+        ));
+    }
+}
@@ -0,0 +1,164 @@
+#![cfg(feature = "macros")]
+mod common;
+
+use common::{address, client, string, transaction, word, CONTRACT, FROM, TO};
+use heliosphere::{abi, HttpResponse, Log, MockTransport, I256, U256};
+use heliosphere_core::Address;
+use serde_json::json;
+
+heliosphere::contract!(pub Token, "tests/abi/token.json");
+
+fn transport() -> MockTransport {
+    MockTransport::new()
+        .with_handler("/wallet/triggerconstantcontract", |req| {
+            let body = req.body.as_ref().unwrap();
+            let result = match body["function_selector"].as_str().unwrap() {
+                "name()" => string("Foo"),
+                "decimals()" => word(6),
+                "getReserves()" => format!("{}{}{}", word(10), word(20), word(30)),
+                _ => word(1000),
+            };
+            Ok(HttpResponse::json(&json!({
+                "result": { "result": true },
+                "constant_result": [result],
+            })))
+        })
+        .with_handler("/wallet/triggersmartcontract", |_| {
+            Ok(HttpResponse::json(
+                &json!({ "result": { "result": true }, "transaction": transaction() }),
+            ))
+        })
+}
+
+#[tokio::test]
+async fn test_view_functions() {
+    let transport = transport();
+    let token = Token::new(client(&transport), CONTRACT.parse().unwrap());
+    let owner: Address = FROM.parse().unwrap();
+
+    assert_eq!(token.name().await.unwrap(), "Foo");
+    let decimals: u8 = token.decimals().await.unwrap();
+    assert_eq!(decimals, 6);
+    assert_eq!(token.balance_of(owner).await.unwrap(), U256::from(1000));
+    let (reserve0, reserve1, timestamp) = token.get_reserves().await.unwrap();
+    assert_eq!((reserve0, reserve1), (U256::from(10), U256::from(20)));
+    assert_eq!(timestamp, 30u32);
+
+    let query = transport.requests()[2].body.clone().unwrap();
+    assert_eq!(query["function_selector"], "balanceOf(address)");
+    assert_eq!(query["parameter"], address(&owner));
+}
+
+#[tokio::test]
+async fn test_mutating_functions() {
+    let transport = transport();
+    let token = Token::new(client(&transport), CONTRACT.parse().unwrap());
+    let from: Address = FROM.parse().unwrap();
+    let to: Address = TO.parse().unwrap();
+
+    token
+        .transfer(&from, to, U256::from(5))
+        .with_fee_limit(100)
+        .build()
+        .await
+        .unwrap();
+    let trigger = transport.requests().pop().unwrap().body.unwrap();
+    assert_eq!(trigger["function_selector"], "transfer(address,uint256)");
+    assert_eq!(trigger["owner_address"], from.as_hex());
+    assert_eq!(trigger["parameter"], format!("{}{}", address(&to), word(5)));
+
+    // overload gets numeric suffix
+    token
+        .safe_transfer_from_1(&from, from, to, U256::from(1), vec![0xab])
+        .with_fee_limit(100)
+        .build()
+        .await
+        .unwrap();
+    let trigger = transport.requests().pop().unwrap().body.unwrap();
+    assert_eq!(
+        trigger["function_selector"],
+        "safeTransferFrom(address,address,uint256,bytes)"
+    );
+
+    token
+        .submit(&from, (7, vec![vec![1, 2]]), [*b"abcd", *b"efgh"])
+        .with_call_value(1_000_000)
+        .with_fee_limit(100)
+        .build()
+        .await
+        .unwrap();
+    let trigger = transport.requests().pop().unwrap().body.unwrap();
+    assert_eq!(
+        trigger["function_selector"],
+        "submit((uint64,bytes[]),bytes4[2])"
+    );
+    assert_eq!(trigger["call_value"], 1_000_000);
+    let parameter = hex::decode(trigger["parameter"].as_str().unwrap()).unwrap();
+    let values = abi::decode(&["(uint64,bytes[])", "bytes4[2]"], &parameter).unwrap();
+    assert_eq!(
+        values[0].as_slice().unwrap()[0].as_uint(),
+        Some(U256::from(7))
+    );
+    assert_eq!(
+        values[1].as_slice().unwrap()[1].as_bytes(),
+        Some(&b"efgh"[..])
+    );
+}
+
+#[test]
+fn test_events() {
+    let token: Address = CONTRACT.parse().unwrap();
+    let from: Address = FROM.parse().unwrap();
+    let to: Address = TO.parse().unwrap();
+    let transfer: Log = serde_json::from_value(json!({
+        "address": hex::encode(&token.as_bytes()[1..]),
+        "topics": [hex::encode(TokenTransfer::TOPIC), address(&from), address(&to)],
+        "data": word(42),
+    }))
+    .unwrap();
+    let event = TokenTransfer::decode(&transfer).unwrap().unwrap();
+    assert_eq!(
+        event,
+        TokenTransfer {
+            from,
+            to,
+            value: U256::from(42)
+        }
+    );
+    assert!(TokenMemo::decode(&transfer).unwrap().is_none());
+
+    let data = abi::encode(&[
+        abi::AbiValue::Tuple(vec![
+            U256::from(3).into(),
+            abi::AbiValue::Array(vec![abi::AbiValue::Bytes(vec![9])]),
+        ]),
+        abi::AbiValue::Int(I256::try_from(-5).unwrap()),
+    ]);
+    let memo: Log = serde_json::from_value(json!({
+        "address": hex::encode(&token.as_bytes()[1..]),
+        "topics": [hex::encode(TokenMemo::TOPIC), "11".repeat(32)],
+        "data": hex::encode(data),
+    }))
+    .unwrap();
+    match TokenEvent::decode(&memo).unwrap().unwrap() {
+        TokenEvent::Memo(memo) => {
+            assert_eq!(memo.tag, [0x11; 32]);
+            assert_eq!(memo.payload, (3, vec![vec![9]]));
+            assert_eq!(memo.delta, -5);
+        }
+        event => panic!("unexpected event {:?}", event),
+    }
+
+    // TRC721 Transfer has same signature with indexed token id
+    let mut nft = transfer.clone();
+    nft.topics.push([7; 32]);
+    nft.data.clear();
+    assert!(TokenEvent::decode(&nft).unwrap().is_none());
+
+    let mut malformed = transfer.clone();
+    malformed.data.truncate(16);
+    assert!(matches!(
+        TokenEvent::decode(&malformed),
+        Err(heliosphere::Error::AbiError(_))
+    ));
+}
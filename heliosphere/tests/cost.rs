@@ -0,0 +1,152 @@
+mod common;
+
+use common::{client, hex_address, CONTRACT, FROM, INACTIVE, TO};
+use heliosphere::{CostPreview, HttpResponse, MockTransport};
+use heliosphere_core::transaction::Transaction;
+use serde_json::json;
+
+fn transaction(kind: &str, value: serde_json::Value, raw_size: usize, memo: bool) -> Transaction {
+    let mut tx = common::transaction();
+    tx["raw_data_hex"] = json!("0a".repeat(raw_size));
+    tx["raw_data"]["contract"] = json!([{ "type": kind, "parameter": { "value": value } }]);
+    if memo {
+        tx["raw_data"]["data"] = json!(hex::encode("order 42"));
+    }
+    serde_json::from_value(tx).unwrap()
+}
+
+fn transfer(to: &str, memo: bool) -> Transaction {
+    transaction(
+        "TransferContract",
+        json!({
+            "owner_address": hex_address(FROM),
+            "to_address": hex_address(to),
+            "amount": 1_000_000
+        }),
+        100,
+        memo,
+    )
+}
+
+fn transport(free_net_used: u64, net_limit: u64) -> MockTransport {
+    MockTransport::new()
+        .with_response(
+            "/wallet/getchainparameters",
+            json!({ "chainParameter": [
+                { "key": "getTransactionFee", "value": 1000 },
+                { "key": "getCreateAccountFee", "value": 100_000 },
+                { "key": "getCreateNewAccountFeeInSystemContract", "value": 1_000_000 },
+                { "key": "getMemoFee", "value": 1_000_000 },
+                { "key": "getEnergyFee", "value": 100 }
+            ] }),
+        )
+        .with_response(
+            "/wallet/getaccountresource",
+            json!({
+                "freeNetLimit": 600,
+                "freeNetUsed": free_net_used,
+                "NetLimit": net_limit,
+                "EnergyLimit": 10_000,
+                "EnergyUsed": 4000
+            }),
+        )
+        .with_handler("/wallet/getaccount", |req| {
+            let address = req.body.as_ref().unwrap()["address"].clone();
+            Ok(HttpResponse::json(
+                &match address == hex_address(INACTIVE) {
+                    true => json!({}),
+                    false => json!({ "address": address, "balance": 5_000_000 }),
+                },
+            ))
+        })
+}
+
+#[tokio::test]
+async fn test_preview_transfer() {
+    // raw data, one signature and contract result
+    let bandwidth = 2 + 100 + 67 + 64;
+
+    // free bandwidth covers transfer
+    let cost = client(&transport(0, 0))
+        .preview_cost(&transfer(TO, false))
+        .await
+        .unwrap();
+    assert_eq!(
+        cost,
+        CostPreview {
+            bandwidth,
+            ..Default::default()
+        }
+    );
+    assert_eq!(cost.total(), 0);
+
+    // bandwidth is burned with memo fee
+    let cost = client(&transport(500, 0))
+        .preview_cost(&transfer(TO, true))
+        .await
+        .unwrap();
+    assert_eq!(cost.bandwidth_fee, bandwidth * 1000);
+    assert_eq!(cost.memo_fee, 1_000_000);
+    assert_eq!(cost.total(), bandwidth * 1000 + 1_000_000);
+
+    // free bandwidth can not be used for activation
+    let cost = client(&transport(0, 0))
+        .preview_cost(&transfer(INACTIVE, false))
+        .await
+        .unwrap();
+    assert_eq!(cost.bandwidth_fee, 100_000);
+    assert_eq!(cost.activation_fee, 1_000_000);
+    let cost = client(&transport(0, 1000))
+        .preview_cost(&transfer(INACTIVE, false))
+        .await
+        .unwrap();
+    assert_eq!(cost.total(), 1_000_000);
+}
+
+#[tokio::test]
+async fn test_preview_contract_call() {
+    let transport = transport(0, 0)
+        .with_response(
+            "/wallet/getcontractinfo",
+            json!({
+                "smart_contract": {
+                    "origin_address": hex_address(TO),
+                    "contract_address": hex_address(CONTRACT),
+                    "consume_user_resource_percent": 100,
+                },
+                "contract_state": { "energy_factor": 0 }
+            }),
+        )
+        .with_response(
+            "/wallet/estimateenergy",
+            json!({ "result": { "result": true }, "energy_required": 30_000 }),
+        );
+    let mut tx = transaction(
+        "TriggerSmartContract",
+        json!({
+            "owner_address": hex_address(FROM),
+            "contract_address": hex_address(CONTRACT),
+            "data": "a9059cbb",
+            "call_value": 5
+        }),
+        200,
+        false,
+    );
+    tx.signature = vec!["00".repeat(65); 2];
+    let cost = client(&transport).preview_cost(&tx).await.unwrap();
+    assert_eq!(cost.bandwidth, 3 + 200 + 2 * 67 + 64);
+    assert_eq!(cost.energy, 30_000);
+    // 6000 energy is staked
+    assert_eq!(cost.energy_fee, 24_000 * 100);
+    assert_eq!(cost.total(), 24_000 * 100);
+
+    let estimate = transport
+        .requests()
+        .into_iter()
+        .find(|x| x.url.ends_with("estimateenergy"))
+        .unwrap()
+        .body
+        .unwrap();
+    assert_eq!(estimate["data"], "a9059cbb");
+    assert_eq!(estimate["call_value"], 5);
+}
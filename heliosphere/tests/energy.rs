@@ -0,0 +1,229 @@
+mod common;
+
+use heliosphere::{
+    EnergySource, Error, HttpResponse, MethodCall, MockTransport, RetryPolicy, RpcClientBuilder,
+};
+use heliosphere_core::Address;
+use serde_json::json;
+
+const CONTRACT: &str = "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t";
+const CALLER: &str = "TB9n2jzcWoqta1xX2Mv8P3y9tyUNsGTFsQ";
+const ORIGIN: &str = "TG3XXyExBkPp9nzdajDZsozEu4BkaSJozs";
+
+fn transport(consume_user_resource_percent: u64) -> MockTransport {
+    MockTransport::new()
+        .with_response(
+            "/wallet/getcontractinfo",
+            json!({
+                "smart_contract": {
+                    "origin_address": ORIGIN.parse::<Address>().unwrap().as_hex(),
+                    "contract_address": CONTRACT.parse::<Address>().unwrap().as_hex(),
+                    "consume_user_resource_percent": consume_user_resource_percent,
+                    "origin_energy_limit": 10_000,
+                },
+                "contract_state": { "energy_factor": 5000 }
+            }),
+        )
+        .with_response(
+            "/wallet/triggerconstantcontract",
+            json!({
+                "result": { "result": true },
+                "constant_result": ["00"],
+                "energy_used": 30_000,
+            }),
+        )
+        .with_response(
+            "/wallet/getaccountresource",
+            json!({ "EnergyLimit": 50_000, "EnergyUsed": 45_000 }),
+        )
+        .with_response(
+            "/wallet/getchainparameters",
+            json!({ "chainParameter": [
+                { "key": "getEnergyFee", "value": 100 },
+                { "key": "getMaxFeeLimit", "value": 15_000_000_000u64 }
+            ] }),
+        )
+}
+
+fn method_call<'a>(caller: &'a Address, contract: &'a Address) -> MethodCall<'a> {
+    MethodCall {
+        caller,
+        contract,
+        selector: "transfer(address,uint256)",
+        parameter: Default::default(),
+    }
+}
+
+#[tokio::test]
+async fn test_estimate_energy() {
+    let (caller, contract) = (CALLER.parse().unwrap(), CONTRACT.parse().unwrap());
+    let call = method_call(&caller, &contract);
+
+    // estimateenergy includes dynamic energy penalty
+    let estimating = transport(100).with_response(
+        "/wallet/estimateenergy",
+        json!({ "result": { "result": true }, "energy_required": 40_000 }),
+    );
+    let client = RpcClientBuilder::new("http://fullnode")
+        .unwrap()
+        .with_transport(estimating.clone())
+        .build();
+    assert_eq!(client.estimate_energy(&call).await.unwrap(), 40_000);
+    let estimate = client.estimate_contract_energy(&call, 0).await.unwrap();
+    assert_eq!(estimate.source, EnergySource::EstimateEnergy);
+    assert_eq!(estimate.caller_energy, 40_000);
+    assert_eq!(estimate.origin_energy, 0);
+    assert_eq!(estimate.fee_limit, 40_000 * 100 * 120 / 100);
+    // origin resources are not needed when caller pays everything
+    assert!(!estimating
+        .requests()
+        .iter()
+        .any(|x| x.url.ends_with("getaccountresource")));
+
+    // node without estimateenergy, energy factor of 0.5 is applied to constant call
+    let client = RpcClientBuilder::new("http://fullnode")
+        .unwrap()
+        .with_transport(transport(100))
+        .build();
+    assert_eq!(client.estimate_energy(&call).await.unwrap(), 45_000);
+    let estimate = client.estimate_contract_energy(&call, 0).await.unwrap();
+    assert_eq!(estimate.source, EnergySource::ConstantCall);
+    assert_eq!(estimate.energy_factor, 5000);
+    assert_eq!(estimate.energy_required, 45_000);
+}
+
+#[tokio::test]
+async fn test_estimate_energy_fallback() {
+    let (caller, contract) = (CALLER.parse().unwrap(), CONTRACT.parse().unwrap());
+    let call = method_call(&caller, &contract);
+    let mock = |estimate: HttpResponse| {
+        let transport =
+            transport(100).with_handler("/wallet/estimateenergy", move |_| Ok(estimate.clone()));
+        let client = RpcClientBuilder::new("http://fullnode")
+            .unwrap()
+            .with_transport(transport.clone())
+            .with_retry_policy(RetryPolicy::none())
+            .build();
+        (client, transport)
+    };
+    let constant_calls = |transport: &MockTransport| {
+        transport
+            .requests()
+            .iter()
+            .filter(|x| x.url.ends_with("triggerconstantcontract"))
+            .count()
+    };
+
+    // estimateenergy disabled in node config
+    let (client, disabled) = mock(HttpResponse::json(&json!({ "result": {
+        "code": "CONTRACT_VALIDATE_ERROR",
+        "message": hex::encode("this node does not support estimate energy")
+    }})));
+    let estimate = client.estimate_contract_energy(&call, 0).await.unwrap();
+    assert_eq!(estimate.source, EnergySource::ConstantCall);
+    assert_eq!(estimate.energy_required, 45_000);
+    assert_eq!(constant_calls(&disabled), 1);
+
+    // other failures are not hidden by constant call
+    let (client, limited) = mock(HttpResponse::new(429, "too many requests"));
+    assert!(matches!(
+        client.estimate_energy(&call).await,
+        Err(Error::HttpStatus(429, _))
+    ));
+    let (client, reverted) = mock(HttpResponse::json(&json!({ "result": {
+        "code": "CONTRACT_EXE_ERROR",
+        "message": hex::encode("REVERT opcode executed")
+    }})));
+    match client.estimate_energy(&call).await {
+        Err(Error::ContractQueryFailed(code, message)) => {
+            assert_eq!(code, "CONTRACT_EXE_ERROR");
+            assert_eq!(message, "REVERT opcode executed");
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert_eq!(constant_calls(&limited) + constant_calls(&reverted), 0);
+}
+
+#[tokio::test]
+async fn test_energy_split() {
+    let (caller, contract) = (CALLER.parse().unwrap(), CONTRACT.parse().unwrap());
+    let call = method_call(&caller, &contract);
+
+    // origin pays 70% but only has 5000 energy left
+    let client = RpcClientBuilder::new("http://fullnode")
+        .unwrap()
+        .with_transport(transport(30))
+        .with_fee_limit_margin(0)
+        .build();
+    let estimate = client.estimate_contract_energy(&call, 0).await.unwrap();
+    assert_eq!(estimate.energy_required, 45_000);
+    assert_eq!(estimate.origin_energy, 5000);
+    assert_eq!(estimate.caller_energy, 40_000);
+    assert_eq!(estimate.energy_fee, 100);
+    assert_eq!(
+        client.estimate_fee_limit(&call).await.unwrap(),
+        40_000 * 100
+    );
+
+    // fee limit is capped by maxFeeLimit
+    let client = RpcClientBuilder::new("http://fullnode")
+        .unwrap()
+        .with_transport(transport(100).with_response(
+            "/wallet/estimateenergy",
+            json!({ "result": { "result": true }, "energy_required": 200_000_000 }),
+        ))
+        .build();
+    assert_eq!(
+        client.estimate_fee_limit(&call).await.unwrap(),
+        15_000_000_000
+    );
+}
+
+#[tokio::test]
+async fn test_trigger_contract_call_value() {
+    let (caller, contract) = (CALLER.parse().unwrap(), CONTRACT.parse().unwrap());
+    let call = method_call(&caller, &contract);
+    let trigger = json!({ "transaction": common::transaction() });
+
+    // payable call is estimated with value it sends
+    let estimating = transport(100)
+        .with_response(
+            "/wallet/estimateenergy",
+            json!({ "result": { "result": true }, "energy_required": 40_000 }),
+        )
+        .with_response("/wallet/triggersmartcontract", trigger.clone());
+    let client = RpcClientBuilder::new("http://fullnode")
+        .unwrap()
+        .with_transport(estimating.clone())
+        .build();
+    client.trigger_contract(&call, 5, None).await.unwrap();
+    let requests = estimating.requests();
+    let body = |path: &str| {
+        requests
+            .iter()
+            .find(|x| x.url.ends_with(path))
+            .and_then(|x| x.body.clone())
+            .unwrap()
+    };
+    assert_eq!(body("/wallet/estimateenergy")["call_value"], 5);
+    assert_eq!(body("/wallet/triggersmartcontract")["call_value"], 5);
+    assert_eq!(
+        body("/wallet/triggersmartcontract")["fee_limit"],
+        40_000 * 100 * 120 / 100
+    );
+
+    // node without estimateenergy
+    let constant = transport(100).with_response("/wallet/triggersmartcontract", trigger);
+    let client = RpcClientBuilder::new("http://fullnode")
+        .unwrap()
+        .with_transport(constant.clone())
+        .build();
+    client.trigger_contract(&call, 5, None).await.unwrap();
+    let simulation = constant
+        .requests()
+        .into_iter()
+        .find(|x| x.url.ends_with("/wallet/triggerconstantcontract"))
+        .and_then(|x| x.body)
+        .unwrap();
+    assert_eq!(simulation["call_value"], 5);
+}
@@ -0,0 +1,205 @@
+use heliosphere::{
+    abi::{AbiParam, AbiValue, ContractAbi, StateMutability},
+    event::{EventDecoder, EventFilter},
+    MockTransport, RpcClientBuilder, I256, U256,
+};
+use heliosphere_core::Address;
+use serde_json::json;
+
+const ABI: &str = r#"[
+    {"type":"function","name":"transfer","stateMutability":"nonpayable",
+     "inputs":[{"name":"to","type":"address"},{"name":"value","type":"uint256"}],
+     "outputs":[{"name":"","type":"bool"}]},
+    {"type":"function","name":"balanceOf","stateMutability":"view",
+     "inputs":[{"name":"who","type":"address"}],"outputs":[{"name":"","type":"uint256"}]},
+    {"type":"event","name":"Transfer","anonymous":false,"inputs":[
+     {"name":"from","type":"address","indexed":true},
+     {"name":"to","type":"address","indexed":true},
+     {"name":"value","type":"uint256","indexed":false}]},
+    {"type":"event","name":"Memo","anonymous":false,"inputs":[
+     {"name":"tag","type":"string","indexed":true},
+     {"name":"payload","type":"tuple","indexed":false,"components":[
+      {"name":"id","type":"uint64"},{"name":"parts","type":"bytes[]"}]},
+     {"name":"delta","type":"int32","indexed":false}]}
+]"#;
+
+const TRANSFER_TOPIC: &str = "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+fn topic(address: &Address) -> String {
+    format!("{:0>64}", hex::encode(&address.as_bytes()[1..]))
+}
+
+fn transfer_log(contract: &Address, from: &Address, to: &Address, value: u64) -> serde_json::Value {
+    json!({
+        "address": hex::encode(&contract.as_bytes()[1..]),
+        "topics": [TRANSFER_TOPIC, topic(from), topic(to)],
+        "data": format!("{:064x}", value),
+    })
+}
+
+#[test]
+fn test_abi_parse() {
+    let abi = ContractAbi::from_json(ABI).unwrap();
+    assert_eq!(abi.functions.len(), 2);
+    assert_eq!(
+        abi.function("transfer").unwrap().selector(),
+        [0xa9, 0x05, 0x9c, 0xbb]
+    );
+    assert!(abi.function("balanceOf").unwrap().is_view());
+    assert_eq!(
+        hex::encode(abi.event("Transfer").unwrap().topic()),
+        TRANSFER_TOPIC
+    );
+    assert_eq!(
+        abi.event("Memo").unwrap().signature(),
+        "Memo(string,(uint64,bytes[]),int32)"
+    );
+
+    // Tron getcontract form
+    let tron = ContractAbi::from_json(
+        r#"{"entrys":[{"type":"Function","name":"name","stateMutability":"View",
+            "outputs":[{"type":"string"}]},{"type":"Constructor"}]}"#,
+    )
+    .unwrap();
+    assert_eq!(tron.functions.len(), 1);
+    assert_eq!(tron.functions[0].state_mutability, StateMutability::View);
+}
+
+#[test]
+fn test_value_from_json() {
+    let param = |kind: &str| -> AbiParam {
+        serde_json::from_value(json!({ "name": "x", "type": kind })).unwrap()
+    };
+    let int32 = param("int32");
+    assert_eq!(
+        int32.value_from_json(&json!("0x1f")).unwrap(),
+        AbiValue::Int(I256::try_from(31).unwrap())
+    );
+    assert_eq!(
+        int32.value_from_json(&json!("-0x1f")).unwrap(),
+        AbiValue::Int(I256::try_from(-31).unwrap())
+    );
+    assert_eq!(
+        int32.value_from_json(&json!(-31)).unwrap(),
+        AbiValue::Int(I256::try_from(-31).unwrap())
+    );
+    assert!(int32.value_from_json(&json!("0x100000000")).is_err());
+    assert_eq!(
+        param("uint8").value_from_json(&json!("0xff")).unwrap(),
+        AbiValue::Uint(U256::from(255))
+    );
+    assert!(param("uint8").value_from_json(&json!("0x100")).is_err());
+}
+
+#[test]
+fn test_decode_log() {
+    let decoder = EventDecoder::from_abi_json(ABI).unwrap();
+    let usdt: Address = "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t".parse().unwrap();
+    let from: Address = "TB9n2jzcWoqta1xX2Mv8P3y9tyUNsGTFsQ".parse().unwrap();
+    let to: Address = "TG3XXyExBkPp9nzdajDZsozEu4BkaSJozs".parse().unwrap();
+    let log = serde_json::from_value(transfer_log(&usdt, &from, &to, 1000)).unwrap();
+    let event = decoder.decode_log(&log).unwrap().unwrap();
+    assert_eq!(event.name, "Transfer");
+    assert_eq!(event.contract, usdt);
+    assert_eq!(event.param("from").unwrap().as_address(), Some(from));
+    assert_eq!(event.param("to"), Some(&AbiValue::Address(to)));
+    assert_eq!(
+        event.param("value").unwrap().as_uint(),
+        Some(U256::from(1000))
+    );
+    assert!(event.params[0].indexed && !event.params[2].indexed);
+
+    // dynamic types
+    let data = heliosphere::abi::encode(&[
+        AbiValue::Tuple(vec![
+            U256::from(7).into(),
+            AbiValue::Array(vec![AbiValue::Bytes(vec![1, 2]), AbiValue::Bytes(vec![])]),
+        ]),
+        I256::try_from(-5).unwrap().into(),
+    ]);
+    let memo = decoder.event("Memo").unwrap();
+    let values = memo.decode(&[memo.topic(), [9; 32]], &data).unwrap();
+    assert_eq!(values[0], AbiValue::FixedBytes(vec![9; 32]));
+    let payload = values[1].as_slice().unwrap();
+    assert_eq!(payload[0].as_uint(), Some(U256::from(7)));
+    assert_eq!(
+        payload[1].as_slice().unwrap()[0].as_bytes(),
+        Some(&[1, 2][..])
+    );
+    assert_eq!(values[2].as_int().unwrap().to_string(), "-5");
+
+    // unknown event, TRC721 Transfer with indexed token id and malformed data
+    let mut unknown = log.clone();
+    unknown.topics[0] = [0; 32];
+    assert!(decoder.decode_log(&unknown).unwrap().is_none());
+    let nft = serde_json::from_value(json!({
+        "address": hex::encode(&usdt.as_bytes()[1..]),
+        "topics": [TRANSFER_TOPIC, topic(&from), topic(&to), format!("{:064x}", 7)],
+        "data": "",
+    }))
+    .unwrap();
+    assert!(decoder.decode_log(&nft).unwrap().is_none());
+    let mut malformed = log.clone();
+    malformed.data.truncate(16);
+    assert!(matches!(
+        decoder.decode_log(&malformed),
+        Err(heliosphere::Error::AbiError(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_block_range_events() {
+    let usdt: Address = "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t".parse().unwrap();
+    let other: Address = "TG3XXyExBkPp9nzdajDZsozEu4BkaSJozs".parse().unwrap();
+    let from: Address = "TB9n2jzcWoqta1xX2Mv8P3y9tyUNsGTFsQ".parse().unwrap();
+    let receipts = json!([{
+        "id": format!("{:064x}", 1),
+        "blockNumber": 100,
+        "blockTimeStamp": 1_700_000_000_000u64,
+        "log": [
+            transfer_log(&usdt, &from, &other, 1),
+            { "address": hex::encode(&usdt.as_bytes()[1..]), "topics": ["00".repeat(32)], "data": "" },
+            transfer_log(&other, &from, &usdt, 2),
+            transfer_log(&usdt, &other, &from, 3),
+            {
+                "address": hex::encode(&usdt.as_bytes()[1..]),
+                "topics": [TRANSFER_TOPIC, topic(&from), topic(&other), format!("{:064x}", 4)],
+                "data": "",
+            },
+        ]
+    }]);
+    let transport =
+        MockTransport::new().with_handler("/wallet/gettransactioninfobyblocknum", move |req| {
+            let body = req.body.as_ref().unwrap();
+            Ok(heliosphere::HttpResponse::json(
+                &match body["num"].as_u64() {
+                    Some(100) => receipts.clone(),
+                    _ => json!([]),
+                },
+            ))
+        });
+    let client = RpcClientBuilder::new("http://fullnode")
+        .unwrap()
+        .with_transport(transport.clone())
+        .build();
+    let decoder = EventDecoder::from_abi_json(ABI).unwrap();
+    let filter = EventFilter::new()
+        .event(decoder.event("Transfer").unwrap())
+        .contract(usdt);
+    let events = client.get_events(99..102, &decoder, &filter).await.unwrap();
+    assert_eq!(transport.requests().len(), 3);
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].block_number, 100);
+    assert_eq!(events[0].log_index, 0);
+    assert_eq!(events[1].log_index, 3);
+    assert_eq!(
+        events[1].event.param("value").unwrap().as_uint(),
+        Some(U256::from(3))
+    );
+
+    let any = client
+        .get_events(100..101, &decoder, &EventFilter::new())
+        .await
+        .unwrap();
+    assert_eq!(any.len(), 3);
+}
@@ -0,0 +1,80 @@
+use heliosphere::{Account, Consistency, Error, ResourceType, RpcClient, RpcClientBuilder};
+use heliosphere_core::Address;
+
+#[tokio::test]
+async fn test_get_account() {
+    let api = "https://api.shasta.trongrid.io";
+    let client = RpcClient::new(api).unwrap();
+    let address: Address = "TB9n2jzcWoqta1xX2Mv8P3y9tyUNsGTFsQ".parse().unwrap();
+    let account = client.get_account(&address).await.unwrap();
+    assert_eq!(account.address, address);
+    println!("{:?}", account);
+    let inactive = Address::new([0x41; 21]).unwrap();
+    assert!(matches!(
+        client.get_account(&inactive).await,
+        Err(Error::AccountNotFound)
+    ));
+}
+
+#[tokio::test]
+async fn test_get_account_solidified() {
+    let client = RpcClientBuilder::new("https://api.shasta.trongrid.io")
+        .unwrap()
+        .with_solidity_url("https://api.shasta.trongrid.io")
+        .unwrap()
+        .build();
+    let address: Address = "TB9n2jzcWoqta1xX2Mv8P3y9tyUNsGTFsQ".parse().unwrap();
+    let account = client
+        .get_account_with(&address, Consistency::Solidified)
+        .await
+        .unwrap();
+    assert_eq!(account.address, address);
+}
+
+#[test]
+fn test_consistency_path() {
+    assert_eq!(Consistency::Latest.path("getaccount"), "/wallet/getaccount");
+    assert_eq!(
+        Consistency::Solidified.path("getaccount"),
+        "/walletsolidity/getaccount"
+    );
+    let client = RpcClientBuilder::new("http://127.0.0.1:8090")
+        .unwrap()
+        .with_solidity_url("http://127.0.0.1:8091")
+        .unwrap()
+        .build();
+    assert_eq!(client.rpc_url().port(), Some(8090));
+    assert_eq!(client.solidity_url().port(), Some(8091));
+    assert!(RpcClientBuilder::new("http://127.0.0.1:8090")
+        .unwrap()
+        .with_solidity_url("not a url")
+        .is_err());
+}
+
+#[test]
+fn test_account_deserialize() {
+    let account: Account = serde_json::from_str(
+        r#"{
+            "address": "410b53ce4aa6f0c2f3c849f11f682702ec99622e2e",
+            "balance": 1000000,
+            "create_time": 1680000000000,
+            "latest_opration_time": 1690000000000,
+            "frozenV2": [{"amount": 5000000}, {"type": "ENERGY", "amount": 7000000}, {"type": "TRON_POWER"}],
+            "unfrozenV2": [{"type": "ENERGY", "unfreeze_amount": 2000000, "unfreeze_expire_time": 1700000000000}],
+            "delegated_frozenV2_balance_for_bandwidth": 3000000,
+            "account_resource": {"delegated_frozenV2_balance_for_energy": 4000000, "energy_window_size": 28800},
+            "owner_permission": {"permission_name": "owner", "threshold": 1, "keys": [{"address": "410b53ce4aa6f0c2f3c849f11f682702ec99622e2e", "weight": 1}]},
+            "active_permission": [{"type": "Active", "id": 2, "permission_name": "active", "threshold": 1, "operations": "7fff1fc0033e0000000000000000000000000000000000000000000000000000", "keys": [{"address": "410b53ce4aa6f0c2f3c849f11f682702ec99622e2e", "weight": 1}]}],
+            "assetV2": [{"key": "1000001", "value": 42}]
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(account.frozen_balance(ResourceType::Bandwidth), 5_000_000);
+    assert_eq!(account.frozen_balance(ResourceType::Energy), 7_000_000);
+    assert_eq!(account.delegated_balance(), 7_000_000);
+    assert_eq!(account.unfreezing_balance(), 2_000_000);
+    assert_eq!(account.total_balance(), 22_000_000);
+    assert_eq!(account.asset_balance("1000001"), 42);
+    assert_eq!(account.latest_operation_time, 1690000000000);
+    assert_eq!(account.active_permission[0].id, 2);
+}
@@ -1,4 +1,5 @@
-use heliosphere::RpcClient;
+use futures::{StreamExt, TryStreamExt};
+use heliosphere::{BlockEvent, BlockStreamOptions, RpcClient};
 use heliosphere_core::block::BlockBy;
 
 #[tokio::test]
@@ -21,3 +22,38 @@ async fn test_get_block() {
         "invalid block"
     );
 }
+
+#[tokio::test]
+async fn test_block_stream() {
+    let api = "https://api.shasta.trongrid.io";
+    let client = RpcClient::new(api).unwrap();
+    let latest_block = client.get_latest_block().await.unwrap();
+    let start = latest_block.block_number() - 5;
+    let events: Vec<BlockEvent> = client
+        .block_stream(BlockStreamOptions::default().with_start(start))
+        .take(3)
+        .try_collect()
+        .await
+        .unwrap();
+    for (i, event) in events.into_iter().enumerate() {
+        match event {
+            BlockEvent::Block(block) => assert_eq!(block.block_number(), start + i as u64),
+            BlockEvent::Reorg { .. } => panic!("unexpected reorg"),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_get_block_range() {
+    let api = "https://api.shasta.trongrid.io";
+    let client = RpcClient::new(api).unwrap();
+    let latest_block = client.get_latest_block().await.unwrap();
+    let end = latest_block.block_number();
+    let blocks = client.get_block_range(end - 250..end).await.unwrap();
+    assert_eq!(blocks.len(), 250);
+    for (number, block) in (end - 250..end).zip(&blocks) {
+        assert_eq!(block.block_number(), number);
+    }
+    let latest = client.get_latest_blocks(3).await.unwrap();
+    assert_eq!(latest.len(), 3);
+}
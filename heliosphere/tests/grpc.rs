@@ -0,0 +1,496 @@
+#![cfg(feature = "grpc")]
+use std::{
+    convert::Infallible,
+    future::{ready, Ready},
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use heliosphere::{
+    grpc::{proto, GrpcClient},
+    ContractResult, EnergySource, Error, MethodCall,
+};
+use heliosphere_core::{block::BlockBy, Address};
+use heliosphere_signer::{keypair::Keypair, signer::Signer};
+use prost::Message;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::{
+    body::Body,
+    codegen::{http, BoxFuture, Service},
+    server::{Grpc, NamedService, UnaryService},
+    transport::Server,
+    Request, Response, Status,
+};
+use tonic_prost::ProstCodec;
+
+/// Unary handler answering with result of closure
+struct Handler<F>(F);
+
+impl<F, Req, Resp> UnaryService<Req> for Handler<F>
+where
+    F: FnMut(Req) -> Result<Resp, Status>,
+{
+    type Response = Resp;
+    type Future = Ready<Result<Response<Resp>, Status>>;
+
+    fn call(&mut self, request: Request<Req>) -> Self::Future {
+        ready((self.0)(request.into_inner()).map(Response::new))
+    }
+}
+
+async fn unary<Req, Resp, F>(req: http::Request<Body>, f: F) -> http::Response<Body>
+where
+    Req: Message + Default + Send + 'static,
+    Resp: Message + Send + 'static,
+    F: FnMut(Req) -> Result<Resp, Status>,
+{
+    Grpc::new(ProstCodec::<Resp, Req>::default())
+        .unary(Handler(f), req)
+        .await
+}
+
+/// Mock of java-tron `Wallet` service
+#[derive(Clone, Default)]
+struct MockWallet {
+    broadcasted: Arc<Mutex<Vec<proto::Transaction>>>,
+}
+
+impl NamedService for MockWallet {
+    const NAME: &'static str = "protocol.Wallet";
+}
+
+fn address(s: &str) -> Vec<u8> {
+    s.parse::<Address>().unwrap().as_bytes().to_vec()
+}
+
+fn block(number: i64) -> proto::BlockExtention {
+    proto::BlockExtention {
+        transactions: vec![proto::TransactionExtention {
+            transaction: Some(transfer(1)),
+            ..Default::default()
+        }],
+        block_header: Some(proto::BlockHeader {
+            raw_data: Some(proto::BlockHeaderRaw {
+                timestamp: 1680000000000,
+                tx_trie_root: vec![0; 32],
+                parent_hash: vec![1; 32],
+                number,
+                witness_address: address("TB9n2jzcWoqta1xX2Mv8P3y9tyUNsGTFsQ"),
+                version: 30,
+            }),
+            witness_signature: vec![2; 65],
+        }),
+        blockid: vec![3; 32],
+    }
+}
+
+fn transfer(amount: i64) -> proto::Transaction {
+    let contract = proto::TransferContract {
+        owner_address: address("TB9n2jzcWoqta1xX2Mv8P3y9tyUNsGTFsQ"),
+        to_address: address("TG3XXyExBkPp9nzdajDZsozEu4BkaSJozs"),
+        amount,
+    };
+    let raw = proto::TransactionRaw {
+        ref_block_bytes: vec![0x12, 0x34],
+        ref_block_hash: vec![0x56; 8],
+        expiration: 1680000060000,
+        contract: vec![proto::Contract {
+            r#type: 1,
+            parameter: Some(proto::Any {
+                type_url: "type.googleapis.com/protocol.TransferContract".to_owned(),
+                value: contract.encode_to_vec(),
+            }),
+            permission_id: 0,
+        }],
+        timestamp: 1680000000000,
+        ..Default::default()
+    };
+    proto::Transaction {
+        raw_data: raw.encode_to_vec(),
+        ..Default::default()
+    }
+}
+
+fn trigger_raw(fee_limit: i64) -> proto::TransactionRaw {
+    proto::TransactionRaw {
+        ref_block_bytes: vec![0x12, 0x34],
+        ref_block_hash: vec![0x56; 8],
+        expiration: 1680000060000,
+        auths: vec![proto::Authority {
+            account: Some(proto::AccountId {
+                name: b"owner".to_vec(),
+                address: address("TB9n2jzcWoqta1xX2Mv8P3y9tyUNsGTFsQ"),
+            }),
+            permission_name: b"active".to_vec(),
+        }],
+        contract: vec![proto::Contract {
+            r#type: 31,
+            parameter: Some(proto::Any {
+                type_url: "type.googleapis.com/protocol.TriggerSmartContract".to_owned(),
+                value: vec![],
+            }),
+            permission_id: 0,
+        }],
+        scripts: vec![7; 4],
+        timestamp: 1680000000000,
+        fee_limit,
+        ..Default::default()
+    }
+}
+
+impl Service<http::Request<Body>> for MockWallet {
+    type Response = http::Response<Body>;
+    type Error = Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: http::Request<Body>) -> Self::Future {
+        let broadcasted = self.broadcasted.clone();
+        Box::pin(async move {
+            let resp = match req.uri().path() {
+                "/protocol.Wallet/GetNowBlock2" => {
+                    unary(req, |_: proto::EmptyMessage| Ok(block(100))).await
+                }
+                "/protocol.Wallet/GetBlockByNum2" => {
+                    unary(req, |x: proto::NumberMessage| match x.num {
+                        num if num <= 100 => Ok(block(num)),
+                        _ => Ok(proto::BlockExtention::default()),
+                    })
+                    .await
+                }
+                "/protocol.Wallet/GetAccount" => {
+                    unary(req, |x: proto::Account| {
+                        Ok(
+                            match x.address == address("TB9n2jzcWoqta1xX2Mv8P3y9tyUNsGTFsQ") {
+                                true => proto::Account {
+                                    address: x.address,
+                                    balance: 1_000_000,
+                                    frozen_v2: vec![proto::FreezeV2 {
+                                        r#type: 1,
+                                        amount: 5,
+                                    }],
+                                    ..Default::default()
+                                },
+                                false => proto::Account::default(),
+                            },
+                        )
+                    })
+                    .await
+                }
+                "/protocol.Wallet/CreateTransaction2" => {
+                    unary(req, |x: proto::TransferContract| {
+                        Ok(proto::TransactionExtention {
+                            transaction: Some(transfer(x.amount)),
+                            result: Some(proto::Return {
+                                result: true,
+                                ..Default::default()
+                            }),
+                            ..Default::default()
+                        })
+                    })
+                    .await
+                }
+                "/protocol.Wallet/TriggerContract" => {
+                    unary(req, |_: proto::TriggerSmartContract| {
+                        Ok(proto::TransactionExtention {
+                            transaction: Some(proto::Transaction {
+                                raw_data: trigger_raw(0).encode_to_vec(),
+                                ..Default::default()
+                            }),
+                            result: Some(proto::Return {
+                                result: true,
+                                ..Default::default()
+                            }),
+                            ..Default::default()
+                        })
+                    })
+                    .await
+                }
+                "/protocol.Wallet/BroadcastTransaction" => {
+                    unary(req, move |x: proto::Transaction| {
+                        broadcasted.lock().unwrap().push(x);
+                        Ok(proto::Return {
+                            result: true,
+                            ..Default::default()
+                        })
+                    })
+                    .await
+                }
+                "/protocol.Wallet/TriggerConstantContract" => {
+                    unary(req, |x: proto::TriggerSmartContract| {
+                        // balanceOf(address) selector
+                        assert_eq!(&x.data[..4], &[0x70, 0xa0, 0x82, 0x31]);
+                        let mut ret = proto::TransactionResult::default();
+                        let mut constant_result = vec![0; 32];
+                        if x.data.len() > 4 {
+                            constant_result[31] = 42;
+                            ret.contract_ret = 1;
+                        } else {
+                            constant_result = vec![];
+                            ret.contract_ret = 2;
+                        }
+                        Ok(proto::TransactionExtention {
+                            transaction: Some(proto::Transaction {
+                                ret: vec![ret],
+                                ..Default::default()
+                            }),
+                            constant_result: vec![constant_result],
+                            result: Some(proto::Return {
+                                result: true,
+                                ..Default::default()
+                            }),
+                            energy_used: 1000,
+                            ..Default::default()
+                        })
+                    })
+                    .await
+                }
+                "/protocol.Wallet/EstimateEnergy" => {
+                    unary(req, |x: proto::TriggerSmartContract| {
+                        // estimates payable calls only
+                        Ok(match x.call_value {
+                            0 => proto::EstimateEnergyMessage {
+                                result: Some(proto::Return {
+                                    result: false,
+                                    code: 12,
+                                    message: b"this node does not support estimate energy".to_vec(),
+                                }),
+                                ..Default::default()
+                            },
+                            _ => proto::EstimateEnergyMessage {
+                                result: Some(proto::Return {
+                                    result: true,
+                                    ..Default::default()
+                                }),
+                                energy_required: 1500,
+                            },
+                        })
+                    })
+                    .await
+                }
+                "/protocol.Wallet/GetContractInfo" => {
+                    unary(req, |x: proto::BytesMessage| {
+                        Ok(proto::SmartContractDataWrapper {
+                            smart_contract: Some(proto::SmartContract {
+                                origin_address: address("TB9n2jzcWoqta1xX2Mv8P3y9tyUNsGTFsQ"),
+                                contract_address: x.value,
+                                consume_user_resource_percent: 50,
+                                origin_energy_limit: 300,
+                                ..Default::default()
+                            }),
+                            contract_state: Some(proto::ContractState {
+                                energy_factor: 2000,
+                                ..Default::default()
+                            }),
+                            ..Default::default()
+                        })
+                    })
+                    .await
+                }
+                "/protocol.Wallet/GetAccountResource" => {
+                    unary(req, |_: proto::Account| {
+                        Ok(proto::AccountResourceMessage {
+                            energy_used: 800,
+                            energy_limit: 1000,
+                        })
+                    })
+                    .await
+                }
+                "/protocol.Wallet/GetChainParameters" => {
+                    unary(req, |_: proto::EmptyMessage| {
+                        Ok(proto::ChainParameters {
+                            chain_parameter: vec![
+                                proto::ChainParameter {
+                                    key: "getEnergyFee".to_owned(),
+                                    value: 100,
+                                },
+                                proto::ChainParameter {
+                                    key: "getMaxFeeLimit".to_owned(),
+                                    value: 15_000_000_000,
+                                },
+                            ],
+                        })
+                    })
+                    .await
+                }
+                "/protocol.Wallet/GetTransactionInfoById" => {
+                    unary(req, |x: proto::BytesMessage| {
+                        Ok(proto::TransactionInfo {
+                            id: x.value,
+                            fee: 100,
+                            block_number: 100,
+                            receipt: Some(proto::ResourceReceipt {
+                                net_usage: 268,
+                                ..Default::default()
+                            }),
+                            log: vec![proto::Log {
+                                address: vec![4; 20],
+                                topics: vec![vec![5; 32]],
+                                data: vec![6; 32],
+                            }],
+                            ..Default::default()
+                        })
+                    })
+                    .await
+                }
+                _ => Status::unimplemented("not implemented").into_http(),
+            };
+            Ok(resp)
+        })
+    }
+}
+
+async fn serve() -> (GrpcClient, MockWallet) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let wallet = MockWallet::default();
+    tokio::spawn(
+        Server::builder()
+            .add_service(wallet.clone())
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    (GrpcClient::new(url).unwrap(), wallet)
+}
+
+#[tokio::test]
+async fn test_grpc_blocks_and_accounts() {
+    let (client, _) = serve().await;
+    let latest = client.get_latest_block().await.unwrap();
+    assert_eq!(latest.block_number(), 100);
+    assert_eq!(latest.block_id.0, [3; 32]);
+    assert_eq!(latest.transactions.len(), 1);
+    assert_eq!(
+        latest.transactions[0].raw_data.contract[0].r#type,
+        "TransferContract"
+    );
+    assert_eq!(
+        latest.transactions[0].raw_data.contract[0].parameter["value"]["amount"],
+        1
+    );
+    assert_eq!(
+        client
+            .get_block(BlockBy::Number(42))
+            .await
+            .unwrap()
+            .block_number(),
+        42
+    );
+    assert!(matches!(
+        client.get_block(BlockBy::Number(101)).await,
+        Err(Error::MissingBlock(101))
+    ));
+
+    let address: Address = "TB9n2jzcWoqta1xX2Mv8P3y9tyUNsGTFsQ".parse().unwrap();
+    let account = client.get_account(&address).await.unwrap();
+    assert_eq!(account.address, address);
+    assert_eq!(account.balance, 1_000_000);
+    assert_eq!(account.total_balance(), 1_000_005);
+    assert!(matches!(
+        client.get_account(&Address::new([0x41; 21]).unwrap()).await,
+        Err(Error::AccountNotFound)
+    ));
+    // WalletSolidity is not served by mock
+    assert!(matches!(
+        client
+            .get_tx_receipt(heliosphere_core::transaction::TransactionId([0; 32]))
+            .await,
+        Err(Error::GrpcStatus(tonic::Code::Unimplemented, _))
+    ));
+}
+
+#[tokio::test]
+async fn test_grpc_transfer() {
+    let (client, wallet) = serve().await;
+    let keypair = Keypair::from_hex_key(&"11".repeat(32)).unwrap();
+    let from: Address = "TB9n2jzcWoqta1xX2Mv8P3y9tyUNsGTFsQ".parse().unwrap();
+    let to: Address = "TG3XXyExBkPp9nzdajDZsozEu4BkaSJozs".parse().unwrap();
+    let mut tx = client.trx_transfer(&from, &to, 7).await.unwrap();
+    assert_eq!(tx.raw_data.contract[0].parameter["value"]["amount"], 7);
+    keypair.sign_transaction(&mut tx).unwrap();
+    let txid = client.broadcast_transaction(&tx).await.unwrap();
+    assert_eq!(txid, tx.tx_id);
+    let broadcasted = wallet.broadcasted.lock().unwrap().clone();
+    assert_eq!(broadcasted.len(), 1);
+    assert_eq!(broadcasted[0].raw_data, tx.raw_data_bytes);
+    assert_eq!(broadcasted[0].signature.len(), 1);
+
+    let receipt = client
+        .get_tx_receipt_with(txid, heliosphere::Consistency::Latest)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(receipt.id, txid);
+    assert_eq!(receipt.receipt.net_usage, 268);
+    assert_eq!(receipt.logs[0].address.as_bytes()[1..], [4; 20]);
+}
+
+#[tokio::test]
+async fn test_grpc_query_contract() {
+    let (client, _) = serve().await;
+    let caller: Address = "TB9n2jzcWoqta1xX2Mv8P3y9tyUNsGTFsQ".parse().unwrap();
+    let contract: Address = "TG3XXyExBkPp9nzdajDZsozEu4BkaSJozs".parse().unwrap();
+    let parameter = heliosphere::abi::encode(&[caller.into()]);
+    let call = MethodCall {
+        caller: &caller,
+        contract: &contract,
+        selector: "balanceOf(address)",
+        parameter: (&parameter).into(),
+    };
+    let resp = client.query_contract(&call).await.unwrap();
+    assert_eq!(resp.constant_result(0).unwrap()[31], 42);
+    assert_eq!(resp.energy_used, 1000);
+    let call = MethodCall {
+        parameter: Default::default(),
+        ..call
+    };
+    assert!(matches!(
+        client.query_contract(&call).await,
+        Err(Error::ContractExecutionFailed(ContractResult::Revert, None))
+    ));
+}
+
+#[tokio::test]
+async fn test_grpc_trigger_contract_fee_limit() {
+    let (client, _) = serve().await;
+    let caller: Address = "TB9n2jzcWoqta1xX2Mv8P3y9tyUNsGTFsQ".parse().unwrap();
+    let contract: Address = "TG3XXyExBkPp9nzdajDZsozEu4BkaSJozs".parse().unwrap();
+    let call = MethodCall::new(&caller, &contract, "balanceOf(address)", &[caller.into()]).unwrap();
+    let tx = client
+        .trigger_contract(&call, 0, Some(1_000_000))
+        .await
+        .unwrap();
+    // auths and scripts survive re-encoding
+    assert_eq!(tx.raw_data_bytes, trigger_raw(1_000_000).encode_to_vec());
+    assert_eq!(tx.raw_data.fee_limit, 1_000_000);
+}
+
+#[tokio::test]
+async fn test_grpc_estimate_energy() {
+    let (client, _) = serve().await;
+    let caller: Address = "TB9n2jzcWoqta1xX2Mv8P3y9tyUNsGTFsQ".parse().unwrap();
+    let contract: Address = "TG3XXyExBkPp9nzdajDZsozEu4BkaSJozs".parse().unwrap();
+    let call = MethodCall::new(&caller, &contract, "balanceOf(address)", &[caller.into()]).unwrap();
+    // constant call fallback, 20% energy penalty applied from contract state
+    assert_eq!(client.estimate_energy(&call).await.unwrap(), 1200);
+    let estimate = client.estimate_contract_energy(&call, 0).await.unwrap();
+    assert_eq!(estimate.source, EnergySource::ConstantCall);
+    assert_eq!(estimate.energy_required, 1200);
+    // half of energy paid by origin, limited by its available energy
+    assert_eq!(estimate.origin_energy, 200);
+    assert_eq!(estimate.caller_energy, 1000);
+    assert_eq!(estimate.fee_limit, 1000 * 100 * 120 / 100);
+    assert_eq!(
+        client.estimate_fee_limit(&call).await.unwrap(),
+        estimate.fee_limit
+    );
+
+    let estimate = client.estimate_contract_energy(&call, 5).await.unwrap();
+    assert_eq!(estimate.source, EnergySource::EstimateEnergy);
+    assert_eq!(estimate.energy_required, 1500);
+    assert_eq!(estimate.caller_energy, 1300);
+    let tx = client.trigger_contract(&call, 5, None).await.unwrap();
+    assert_eq!(tx.raw_data.fee_limit, 1300 * 100 * 120 / 100);
+}
@@ -0,0 +1,187 @@
+use heliosphere::{
+    ContractResult, Error, EthBlockTag, HttpResponse, LogFilter, MethodCall, MockTransport,
+    RevertReason, RpcClientBuilder,
+};
+use heliosphere_core::{transaction::TransactionId, Address};
+use serde_json::json;
+
+const TRANSFER_TOPIC: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+const TXID: &str = "0x04644c93d200adb9010cc6396eb77a327fbdfa81a5e9e27407f84a010169e7c4";
+const BLOCK_HASH: &str = "0x0000000002faf08063a46b1ba3f67ac1e91d9d6a1b4c3b4e7e0b95b3a6a4c7a1";
+
+fn log() -> serde_json::Value {
+    json!({
+        "address": "0x42a1e39aefa49290f2b3f9ed688d7cecf86cd6e0",
+        "topics": [TRANSFER_TOPIC, format!("0x{:064x}", 1)],
+        "data": format!("0x{:064x}", 1000),
+        "blockNumber": "0x2faf080",
+        "blockHash": BLOCK_HASH,
+        "transactionHash": TXID,
+        "transactionIndex": "0x0",
+        "logIndex": "0x1",
+        "removed": false,
+    })
+}
+
+fn client() -> (heliosphere::JsonRpcClient, MockTransport) {
+    let transport = MockTransport::new().with_handler("/jsonrpc", |req| {
+        let body = req.body.clone().unwrap();
+        let result = match body["method"].as_str().unwrap() {
+            "eth_blockNumber" => json!("0x2faf080"),
+            "eth_getLogs" => json!([log()]),
+            "eth_estimateGas" => json!("0x3a98"),
+            "eth_getBlockByNumber" if body["params"][0] == "0x2faf081" => json!(null),
+            "eth_getBlockByNumber" => json!({
+                "number": "0x2faf080",
+                "hash": BLOCK_HASH,
+                "parentHash": BLOCK_HASH,
+                "timestamp": "0x64d4f9a1",
+                "miner": "0x42a1e39aefa49290f2b3f9ed688d7cecf86cd6e0",
+                "gasUsed": "0x0",
+                "gasLimit": "0x0",
+                "transactions": [TXID],
+            }),
+            "eth_getTransactionReceipt" => json!({
+                "transactionHash": TXID,
+                "transactionIndex": "0x0",
+                "blockHash": BLOCK_HASH,
+                "blockNumber": "0x2faf080",
+                "from": "0x42a1e39aefa49290f2b3f9ed688d7cecf86cd6e0",
+                "to": "0xa614f803b6fd780986a42c78ec9c7f77e6ded13c",
+                "contractAddress": null,
+                "gasUsed": "0x3a98",
+                "cumulativeGasUsed": "0x3a98",
+                "effectiveGasPrice": "0x1a4",
+                "status": "0x1",
+                "logs": [log()],
+            }),
+            "eth_call" => {
+                return Ok(HttpResponse::json(&json!({
+                    "jsonrpc": "2.0",
+                    "id": body["id"],
+                    "error": {
+                        "code": -32000,
+                        "message": "REVERT opcode executed",
+                        "data": "0x08c379a0\
+                            0000000000000000000000000000000000000000000000000000000000000020\
+                            0000000000000000000000000000000000000000000000000000000000000004\
+                            6e6f706500000000000000000000000000000000000000000000000000000000",
+                    }
+                })))
+            }
+            _ => {
+                return Ok(HttpResponse::json(&json!({
+                    "jsonrpc": "2.0",
+                    "id": body["id"],
+                    "error": { "code": -32601, "message": "method not found" }
+                })))
+            }
+        };
+        Ok(HttpResponse::json(
+            &json!({ "jsonrpc": "2.0", "id": body["id"], "result": result }),
+        ))
+    });
+    let client = RpcClientBuilder::new("http://fullnode:8545")
+        .unwrap()
+        .with_transport(transport.clone())
+        .build();
+    (client.json_rpc(), transport)
+}
+
+#[tokio::test]
+async fn test_json_rpc_logs() {
+    let (client, transport) = client();
+    let usdt: Address = "TG3XXyExBkPp9nzdajDZsozEu4BkaSJozs".parse().unwrap();
+    let mut topic = [0; 32];
+    hex::decode_to_slice(&TRANSFER_TOPIC[2..], &mut topic).unwrap();
+    let filter = LogFilter::new()
+        .from_block(50_000_000)
+        .to_block(EthBlockTag::Latest)
+        .address(usdt)
+        .event(topic)
+        .topic(2, [0xff; 32]);
+    let logs = client.get_logs(&filter).await.unwrap();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].topics[0], topic);
+    assert_eq!(logs[0].block_number, 50_000_000);
+    assert_eq!(logs[0].log_index, 1);
+    assert_eq!(logs[0].address.as_bytes()[0], 0x41);
+    assert_eq!(
+        logs[0].transaction_hash,
+        TXID[2..].parse::<TransactionId>().unwrap()
+    );
+
+    let request = transport.requests()[0].body.clone().unwrap();
+    assert_eq!(request["method"], "eth_getLogs");
+    let params = &request["params"][0];
+    assert_eq!(params["fromBlock"], "0x2faf080");
+    assert_eq!(params["toBlock"], "latest");
+    assert_eq!(
+        params["address"].as_str().unwrap().to_lowercase(),
+        format!("0x{}", hex::encode(&usdt.as_bytes()[1..]))
+    );
+    assert_eq!(params["topics"][0], TRANSFER_TOPIC);
+    assert_eq!(params["topics"][1], json!(null));
+    assert_eq!(params["topics"][2], format!("0x{}", "ff".repeat(32)));
+}
+
+#[tokio::test]
+async fn test_json_rpc_blocks_and_receipts() {
+    let (client, _) = client();
+    assert_eq!(client.block_number().await.unwrap(), 50_000_000);
+    let block = client
+        .get_block_by_number(EthBlockTag::Latest)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(block.number, 50_000_000);
+    assert_eq!(block.transactions.len(), 1);
+    assert!(client
+        .get_block_by_number(EthBlockTag::Number(50_000_001))
+        .await
+        .unwrap()
+        .is_none());
+
+    let receipt = client
+        .get_transaction_receipt(TXID[2..].parse().unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(receipt.is_success());
+    assert_eq!(receipt.gas_used, 15000);
+    assert_eq!(receipt.effective_gas_price, 420);
+    assert!(receipt.to.is_some() && receipt.contract_address.is_none());
+    assert_eq!(receipt.logs.len(), 1);
+}
+
+#[tokio::test]
+async fn test_json_rpc_calls() {
+    let (client, transport) = client();
+    let caller: Address = "TB9n2jzcWoqta1xX2Mv8P3y9tyUNsGTFsQ".parse().unwrap();
+    let contract: Address = "TG3XXyExBkPp9nzdajDZsozEu4BkaSJozs".parse().unwrap();
+    let call = MethodCall {
+        caller: &caller,
+        contract: &contract,
+        selector: "transfer(address,uint256)",
+        parameter: vec![0; 64].into(),
+    };
+    assert_eq!(client.estimate_gas(&call, 0).await.unwrap(), 15000);
+    let request = transport.requests()[0].body.clone().unwrap();
+    assert!(request["params"][0]["data"]
+        .as_str()
+        .unwrap()
+        .starts_with("0xa9059cbb"));
+    assert!(matches!(
+        client.call(&call, EthBlockTag::Latest).await,
+        Err(Error::ContractExecutionFailed(
+            ContractResult::Revert,
+            Some(RevertReason::Error(reason))
+        )) if reason == "nope"
+    ));
+    assert!(matches!(
+        client
+            .request::<_, serde_json::Value>("eth_unknown", ())
+            .await,
+        Err(Error::JsonRpcError(-32601, _))
+    ));
+}
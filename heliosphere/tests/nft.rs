@@ -0,0 +1,192 @@
+mod common;
+
+use common::{address, client, string, transaction, word, CONTRACT, FROM, TO};
+use heliosphere::{HttpResponse, Log, MockTransport, Trc1155, Trc721, U256};
+use heliosphere_core::Address;
+use serde_json::json;
+
+const TRANSFER_TOPIC: &str = "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+const TRANSFER_SINGLE_TOPIC: &str =
+    "c3d58168c5ae7397731d063d5bbf3d657854427343f4c083240f7aacaa2d0f62";
+const TRANSFER_BATCH_TOPIC: &str =
+    "4a39dc06d4c0dbc64b70af90fd698a233a518aa5d07e595d983b8c0526c8f7fb";
+
+fn transport() -> MockTransport {
+    MockTransport::new()
+        .with_handler("/wallet/triggerconstantcontract", |req| {
+            let body = req.body.as_ref().unwrap();
+            let result = match body["function_selector"].as_str().unwrap() {
+                "ownerOf(uint256)" => address(&FROM.parse().unwrap()),
+                "tokenURI(uint256)" => string("ipfs://x1"),
+                "balanceOfBatch(address[],uint256[])" => {
+                    format!("{}{}{}{}", word(32), word(2), word(5), word(0))
+                }
+                _ => word(3),
+            };
+            Ok(HttpResponse::json(&json!({
+                "result": { "result": true },
+                "constant_result": [result],
+            })))
+        })
+        .with_handler("/wallet/triggersmartcontract", |_| {
+            Ok(HttpResponse::json(
+                &json!({ "result": { "result": true }, "transaction": transaction() }),
+            ))
+        })
+}
+
+#[tokio::test]
+async fn test_trc721() {
+    let transport = transport();
+    let client = client(&transport);
+    let nft = client.trc721(CONTRACT.parse().unwrap());
+    let from: Address = FROM.parse().unwrap();
+    let to: Address = TO.parse().unwrap();
+
+    assert_eq!(nft.owner_of(U256::from(1)).await.unwrap(), from);
+    assert_eq!(nft.balance_of(&from).await.unwrap(), U256::from(3));
+    assert_eq!(nft.token_uri(U256::from(1)).await.unwrap(), "ipfs://x1");
+
+    nft.safe_transfer_from(&to, &from, &to, U256::from(7))
+        .with_fee_limit(100)
+        .build()
+        .await
+        .unwrap();
+    let trigger = transport.requests().pop().unwrap().body.unwrap();
+    assert_eq!(
+        trigger["function_selector"],
+        "safeTransferFrom(address,address,uint256)"
+    );
+    assert_eq!(trigger["owner_address"], to.as_hex());
+    assert_eq!(
+        trigger["parameter"],
+        format!("{}{}{}", address(&from), address(&to), word(7))
+    );
+    assert_eq!(trigger["fee_limit"], 100);
+
+    nft.set_approval_for_all(&from, &to, true)
+        .with_fee_limit(100)
+        .build()
+        .await
+        .unwrap();
+    let trigger = transport.requests().pop().unwrap().body.unwrap();
+    assert_eq!(trigger["parameter"], format!("{}{}", address(&to), word(1)));
+}
+
+#[tokio::test]
+async fn test_trc1155() {
+    let transport = transport();
+    let client = client(&transport);
+    let token = client.trc1155(CONTRACT.parse().unwrap());
+    let from: Address = FROM.parse().unwrap();
+    let to: Address = TO.parse().unwrap();
+
+    let balances = token
+        .balance_of_batch(&[from, to], &[U256::from(1), U256::from(2)])
+        .await
+        .unwrap();
+    assert_eq!(balances, [U256::from(5), U256::ZERO]);
+    let query = transport.requests().pop().unwrap().body.unwrap();
+    assert_eq!(
+        query["parameter"],
+        format!(
+            "{}{}{}{}{}{}{}{}",
+            word(64),
+            word(160),
+            word(2),
+            address(&from),
+            address(&to),
+            word(2),
+            word(1),
+            word(2)
+        )
+    );
+
+    token
+        .safe_batch_transfer_from(&from, &from, &to, &[U256::from(1)], &[U256::from(10)], &[])
+        .with_fee_limit(100)
+        .build()
+        .await
+        .unwrap();
+    let trigger = transport.requests().pop().unwrap().body.unwrap();
+    assert_eq!(
+        trigger["function_selector"],
+        "safeBatchTransferFrom(address,address,uint256[],uint256[],bytes)"
+    );
+    // from, to, 3 offsets, 2 single element arrays and empty bytes
+    assert_eq!(trigger["parameter"].as_str().unwrap().len(), 10 * 64);
+}
+
+#[test]
+fn test_decode_transfers() {
+    let contract: Address = CONTRACT.parse().unwrap();
+    let from: Address = FROM.parse().unwrap();
+    let to: Address = TO.parse().unwrap();
+    let log = |topics: Vec<String>, data: String| -> Log {
+        serde_json::from_value(json!({
+            "address": hex::encode(&contract.as_bytes()[1..]),
+            "topics": topics,
+            "data": data,
+        }))
+        .unwrap()
+    };
+
+    let transfer = log(
+        vec![
+            TRANSFER_TOPIC.into(),
+            address(&from),
+            address(&to),
+            word(42),
+        ],
+        String::new(),
+    );
+    let decoded = Trc721::decode_transfer(&transfer).unwrap().unwrap();
+    assert_eq!(decoded.contract, contract);
+    assert_eq!((decoded.from, decoded.to), (from, to));
+    assert_eq!(decoded.token_id, U256::from(42));
+    // TRC20 transfer has value in data
+    let trc20 = log(
+        vec![TRANSFER_TOPIC.into(), address(&from), address(&to)],
+        word(42),
+    );
+    assert!(Trc721::decode_transfer(&trc20).unwrap().is_none());
+
+    let single = log(
+        vec![
+            TRANSFER_SINGLE_TOPIC.into(),
+            address(&from),
+            address(&from),
+            address(&to),
+        ],
+        format!("{}{}", word(1), word(10)),
+    );
+    let decoded = Trc1155::decode_transfer(&single).unwrap().unwrap();
+    assert_eq!(decoded.operator, from);
+    assert_eq!(decoded.ids, [U256::from(1)]);
+    assert_eq!(decoded.values, [U256::from(10)]);
+
+    let batch = log(
+        vec![
+            TRANSFER_BATCH_TOPIC.into(),
+            address(&to),
+            address(&from),
+            address(&to),
+        ],
+        format!(
+            "{}{}{}{}{}{}{}{}",
+            word(64),
+            word(160),
+            word(2),
+            word(1),
+            word(2),
+            word(2),
+            word(10),
+            word(20)
+        ),
+    );
+    let decoded = Trc1155::decode_transfer(&batch).unwrap().unwrap();
+    assert_eq!(decoded.operator, to);
+    assert_eq!(decoded.ids, [U256::from(1), U256::from(2)]);
+    assert_eq!(decoded.values, [U256::from(10), U256::from(20)]);
+    assert!(Trc1155::decode_transfer(&transfer).unwrap().is_none());
+}
@@ -0,0 +1,111 @@
+use heliosphere::{
+    Error, MockTransport, QuorumClient, RetryPolicy, RoutingStrategy, RpcClientBuilder,
+    RpcPoolBuilder,
+};
+use heliosphere_core::Address;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_pool_failover() {
+    let dead = RpcClientBuilder::new("http://127.0.0.1:1")
+        .unwrap()
+        .with_retry_policy(RetryPolicy::none())
+        .build();
+    let block = json!({
+        "blockID": format!("{:064x}", 100),
+        "block_header": {
+            "raw_data": {
+                "number": 100,
+                "txTrieRoot": "00".repeat(32),
+                "witness_address": "410b53ce4aa6f0c2f3c849f11f682702ec99622e2e",
+                "parentHash": format!("{:064x}", 99),
+                "version": 30,
+                "timestamp": 1680000000000u64,
+            },
+            "witness_signature": "00",
+        }
+    });
+    let transport = MockTransport::new()
+        .with_response("/wallet/getnowblock", block.clone())
+        .with_response("/wallet/getblock", block);
+    let healthy = RpcClientBuilder::new("http://fullnode:8090")
+        .unwrap()
+        .with_transport(transport)
+        .build();
+    let pool = RpcPoolBuilder::new()
+        .with_fullnode(dead)
+        .with_fullnode(healthy)
+        .with_strategy(RoutingStrategy::PrimaryFallback)
+        .build();
+    let block = pool
+        .call(|client| async move { client.get_latest_block().await })
+        .await
+        .unwrap();
+    assert_eq!(block.block_number(), 100);
+    let status = pool.fullnode_status();
+    assert!(!status[0].healthy && status[1].healthy);
+    pool.health_check().await;
+    assert!(!pool.fullnode_status()[0].healthy);
+    assert_eq!(
+        pool.client().unwrap().rpc_url().as_str(),
+        "http://fullnode:8090/"
+    );
+}
+
+#[tokio::test]
+async fn test_quorum_divergence() {
+    let dead = RpcClientBuilder::new("http://127.0.0.1:1")
+        .unwrap()
+        .with_retry_policy(RetryPolicy::none())
+        .build();
+    assert!(matches!(
+        QuorumClient::new(vec![dead.clone()], 2),
+        Err(Error::InvalidQuorum(2, 1))
+    ));
+    let quorum = QuorumClient::new(vec![dead.clone(), dead], 2).unwrap();
+    match quorum.read(|_| async { Ok(42) }).await {
+        Ok(x) => assert_eq!(x, 42),
+        Err(e) => panic!("unexpected {:?}", e),
+    }
+    let address: Address = "TB9n2jzcWoqta1xX2Mv8P3y9tyUNsGTFsQ".parse().unwrap();
+    match quorum.get_account_balance(&address).await {
+        Err(Error::QuorumDivergence(answers)) => {
+            assert_eq!(answers.len(), 2);
+            assert!(answers.iter().all(|x| x.answer.is_err()));
+        }
+        x => panic!("unexpected {:?}", x),
+    }
+}
+
+#[tokio::test]
+async fn test_quorum_state_error() {
+    let client = |balance: Option<u64>| {
+        let account = match balance {
+            Some(balance) => json!({ "address": "41", "balance": balance }),
+            None => json!({}),
+        };
+        RpcClientBuilder::new("http://fullnode:8090")
+            .unwrap()
+            .with_transport(MockTransport::new().with_response("/wallet/getaccount", account))
+            .build()
+    };
+    let address: Address = "TB9n2jzcWoqta1xX2Mv8P3y9tyUNsGTFsQ".parse().unwrap();
+
+    // missing account is agreed answer, not divergence
+    let quorum = QuorumClient::new(vec![client(Some(5)), client(None), client(None)], 2).unwrap();
+    assert!(matches!(
+        quorum.get_account(&address).await,
+        Err(Error::AccountNotFound)
+    ));
+
+    let quorum = QuorumClient::new(vec![client(Some(5)), client(None)], 2).unwrap();
+    match quorum.get_account_balance(&address).await {
+        Err(Error::QuorumDivergence(answers)) => {
+            assert_eq!(answers.len(), 2);
+            assert!(answers
+                .iter()
+                .any(|x| x.answer == Err("account not found".to_owned())));
+        }
+        x => panic!("unexpected {:?}", x),
+    }
+}
@@ -0,0 +1,251 @@
+mod common;
+
+use common::{client, hex_address, CONTRACT, FROM, INACTIVE, TIMESTAMP, TO};
+use heliosphere::{
+    abi, ContractResult, Error, HttpResponse, MockTransport, PreflightIssue, RevertReason,
+    RpcClientBuilder,
+};
+use heliosphere_core::transaction::Transaction;
+use serde_json::json;
+
+fn transaction(
+    kind: &str,
+    value: serde_json::Value,
+    expiration: u64,
+    fee_limit: u64,
+) -> Transaction {
+    let mut tx = common::transaction();
+    tx["raw_data_hex"] = json!("0a".repeat(100));
+    tx["raw_data"]["contract"] = json!([{ "type": kind, "parameter": { "value": value } }]);
+    tx["raw_data"]["expiration"] = json!(expiration);
+    tx["raw_data"]["fee_limit"] = json!(fee_limit);
+    serde_json::from_value(tx).unwrap()
+}
+
+fn transfer(from: &str, amount: u64, expiration: u64) -> Transaction {
+    transaction(
+        "TransferContract",
+        json!({
+            "owner_address": hex_address(from),
+            "to_address": hex_address(TO),
+            "amount": amount
+        }),
+        expiration,
+        0,
+    )
+}
+
+fn transport() -> MockTransport {
+    MockTransport::new()
+        .with_response(
+            "/wallet/getnowblock",
+            json!({
+                "blockID": format!("{:064x}", 100),
+                "block_header": {
+                    "raw_data": {
+                        "number": 100,
+                        "txTrieRoot": "00".repeat(32),
+                        "witness_address": "410b53ce4aa6f0c2f3c849f11f682702ec99622e2e",
+                        "parentHash": format!("{:064x}", 99),
+                        "version": 30,
+                        "timestamp": TIMESTAMP,
+                    },
+                    "witness_signature": "00",
+                }
+            }),
+        )
+        .with_response(
+            "/wallet/getchainparameters",
+            json!({ "chainParameter": [
+                { "key": "getTransactionFee", "value": 1000 },
+                { "key": "getEnergyFee", "value": 100 },
+                { "key": "getMaxFeeLimit", "value": 15_000_000_000u64 }
+            ] }),
+        )
+        .with_response(
+            "/wallet/getaccountresource",
+            json!({ "freeNetLimit": 600, "freeNetUsed": 600 }),
+        )
+        .with_handler("/wallet/getaccount", |req| {
+            let address = req.body.as_ref().unwrap()["address"].clone();
+            Ok(HttpResponse::json(
+                &match address == hex_address(INACTIVE) {
+                    true => json!({}),
+                    false => json!({ "address": address, "balance": 2_000_000 }),
+                },
+            ))
+        })
+        .with_response(
+            "/wallet/triggerconstantcontract",
+            json!({
+                "result": { "result": true },
+                "constant_result": [hex::encode(
+                    [&[0x08, 0xc3, 0x79, 0xa0][..], &abi::encode(&["Not enough".into()])].concat()
+                )],
+                "transaction": { "ret": [{ "contractRet": "REVERT" }] }
+            }),
+        )
+}
+
+#[tokio::test]
+async fn test_preflight_transfer() {
+    let client = client(&transport());
+    // burned bandwidth of raw data, signature and result
+    let fee = (2 + 100 + 67 + 64) * 1000;
+
+    let report = client
+        .preflight(&transfer(FROM, 1_000_000, TIMESTAMP + 60_000))
+        .await
+        .unwrap();
+    assert!(report.is_ok());
+    assert_eq!(report.cost.unwrap().total(), fee);
+
+    let report = client
+        .preflight(&transfer(FROM, 1_900_000, TIMESTAMP))
+        .await
+        .unwrap();
+    assert_eq!(
+        report.issues,
+        [
+            PreflightIssue::Expired {
+                expiration: TIMESTAMP,
+                head_timestamp: TIMESTAMP
+            },
+            PreflightIssue::InsufficientBalance {
+                required: 1_900_000 + fee,
+                balance: 2_000_000
+            }
+        ]
+    );
+    assert_eq!(
+        report.issues[1].to_string(),
+        "insufficient balance: 2133000 SUN required, 2000000 SUN available"
+    );
+
+    let report = client
+        .preflight(&transfer(INACTIVE, 1, TIMESTAMP + 25 * 3600 * 1000))
+        .await
+        .unwrap();
+    assert_eq!(report.cost, None);
+    assert_eq!(
+        report.issues,
+        [
+            PreflightIssue::ExpirationTooFar {
+                expiration: TIMESTAMP + 25 * 3600 * 1000,
+                max_expiration: TIMESTAMP + 24 * 3600 * 1000
+            },
+            PreflightIssue::SenderNotActivated
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_preflight_contract_call() {
+    let transport = transport();
+    let client = client(&transport);
+    let tx = transaction(
+        "TriggerSmartContract",
+        json!({
+            "owner_address": hex_address(FROM),
+            "contract_address": hex_address(CONTRACT),
+            "data": "a9059cbb",
+            "call_value": 5
+        }),
+        TIMESTAMP + 60_000,
+        20_000_000_000,
+    );
+    let report = client.preflight(&tx).await.unwrap();
+    // energy of failing call is not estimated, whole fee limit may be burned
+    let cost = report.cost.unwrap();
+    let bandwidth_fee = (2 + 100 + 67 + 64) * 1000;
+    assert_eq!(
+        (cost.energy, cost.energy_fee, cost.bandwidth_fee),
+        (0, 20_000_000_000, bandwidth_fee)
+    );
+    assert_eq!(
+        report.issues,
+        [
+            PreflightIssue::FeeLimitTooHigh {
+                fee_limit: 20_000_000_000,
+                max_fee_limit: 15_000_000_000
+            },
+            PreflightIssue::ContractFailed(
+                ContractResult::Revert,
+                Some(RevertReason::Error("Not enough".to_owned()))
+            ),
+            PreflightIssue::InsufficientBalance {
+                required: 5 + 20_000_000_000 + bandwidth_fee,
+                balance: 2_000_000
+            }
+        ]
+    );
+    assert_eq!(
+        report.issues[1].to_string(),
+        "contract call fails with Revert: Not enough"
+    );
+
+    let simulation = transport
+        .requests()
+        .into_iter()
+        .find(|x| x.url.ends_with("/wallet/triggerconstantcontract"))
+        .unwrap()
+        .body
+        .unwrap();
+    assert_eq!(simulation["data"], "a9059cbb");
+    assert_eq!(simulation["call_value"], 5);
+
+    // amount plus worst case fees overflowing u64 is never covered
+    let tx = transaction(
+        "TriggerSmartContract",
+        json!({
+            "owner_address": hex_address(FROM),
+            "contract_address": hex_address(CONTRACT),
+            "data": "a9059cbb",
+            "call_value": 5
+        }),
+        TIMESTAMP + 60_000,
+        u64::MAX,
+    );
+    let report = client.preflight(&tx).await.unwrap();
+    assert!(report
+        .issues
+        .contains(&PreflightIssue::InsufficientBalance {
+            required: u64::MAX,
+            balance: 2_000_000
+        }));
+}
+
+#[tokio::test]
+async fn test_broadcast_with_preflight() {
+    let transport = transport().with_response(
+        "/wallet/broadcasttransaction",
+        json!({ "result": true, "txid": "00".repeat(32) }),
+    );
+    let client = RpcClientBuilder::new("http://fullnode")
+        .unwrap()
+        .with_transport(transport.clone())
+        .with_preflight(true)
+        .build();
+
+    client
+        .broadcast_transaction(&transfer(FROM, 1_000_000, TIMESTAMP + 60_000))
+        .await
+        .unwrap();
+
+    let result = client
+        .broadcast_transaction(&transfer(FROM, 1_000_000, TIMESTAMP))
+        .await;
+    match result {
+        Err(Error::PreflightFailed(report)) => assert!(matches!(
+            report.issues[..],
+            [PreflightIssue::Expired { .. }]
+        )),
+        result => panic!("unexpected result {:?}", result),
+    }
+    let broadcasts = transport
+        .requests()
+        .iter()
+        .filter(|x| x.url.ends_with("/wallet/broadcasttransaction"))
+        .count();
+    assert_eq!(broadcasts, 1);
+}
@@ -0,0 +1,49 @@
+use std::time::{Duration, Instant};
+
+use heliosphere::{Error, RateLimiter, RetryPolicy};
+
+#[test]
+fn test_retry_backoff() {
+    let policy = RetryPolicy::new(5)
+        .with_backoff(Duration::from_millis(100), Duration::from_secs(1))
+        .with_jitter(false);
+    assert_eq!(policy.backoff(1), Duration::from_millis(100));
+    assert_eq!(policy.backoff(3), Duration::from_millis(400));
+    assert_eq!(policy.backoff(10), Duration::from_secs(1));
+    let jittered = RetryPolicy::new(5)
+        .with_backoff(Duration::from_millis(100), Duration::from_secs(1))
+        .backoff(2);
+    assert!(jittered >= Duration::from_millis(100) && jittered <= Duration::from_millis(200));
+    assert!(policy.should_retry(&Error::HttpStatus(503, String::new())));
+    assert!(!policy.should_retry(&Error::HttpStatus(400, String::new())));
+    assert!(!policy.should_retry(&Error::AccountNotFound));
+}
+
+#[tokio::test]
+async fn test_rate_limiter() {
+    let limiter = RateLimiter::new(100.0, 2);
+    let start = Instant::now();
+    for _ in 0..4 {
+        limiter.acquire().await;
+    }
+    // 2 requests from burst, 2 more at 10ms interval
+    assert!(start.elapsed() >= Duration::from_millis(15));
+}
+
+#[tokio::test]
+async fn test_rate_limiter_invalid_rate() {
+    for rate in [0.0, -1.0, f64::NAN] {
+        let limiter = RateLimiter::new(rate, 1);
+        for _ in 0..3 {
+            limiter.acquire().await;
+        }
+    }
+    // wait of tiny rate does not fit into Duration
+    let limiter = RateLimiter::new(f64::MIN_POSITIVE, 1);
+    limiter.acquire().await;
+    assert!(
+        tokio::time::timeout(Duration::from_millis(10), limiter.acquire())
+            .await
+            .is_err()
+    );
+}
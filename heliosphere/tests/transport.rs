@@ -0,0 +1,138 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use heliosphere::{
+    Consistency, Error, HttpMethod, HttpResponse, MockTransport, RetryPolicy, RpcClientBuilder,
+};
+use heliosphere_core::Address;
+use serde_json::json;
+
+fn block(number: u64) -> serde_json::Value {
+    json!({
+        "blockID": format!("{:064x}", number),
+        "block_header": {
+            "raw_data": {
+                "number": number,
+                "txTrieRoot": "00".repeat(32),
+                "witness_address": "410b53ce4aa6f0c2f3c849f11f682702ec99622e2e",
+                "parentHash": format!("{:064x}", number - 1),
+                "version": 30,
+                "timestamp": 1680000000000u64 + number * 3000,
+            },
+            "witness_signature": "00",
+        }
+    })
+}
+
+#[tokio::test]
+async fn test_mock_transport() {
+    let transport = MockTransport::new()
+        .with_response("/wallet/getnowblock", block(100))
+        .with_response(
+            "/walletsolidity/getaccount",
+            json!({ "address": "410b53ce4aa6f0c2f3c849f11f682702ec99622e2e", "balance": 5 }),
+        );
+    let client = RpcClientBuilder::new("http://fullnode:8090")
+        .unwrap()
+        .with_solidity_url("http://solidity:8091/")
+        .unwrap()
+        .with_api_key("key")
+        .with_header("x-custom", "value")
+        .with_transport(transport.clone())
+        .build();
+    let latest = client.get_latest_block().await.unwrap();
+    assert_eq!(latest.block_number(), 100);
+    let address: Address = "TB9n2jzcWoqta1xX2Mv8P3y9tyUNsGTFsQ".parse().unwrap();
+    let account = client
+        .get_account_with(&address, Consistency::Solidified)
+        .await
+        .unwrap();
+    assert_eq!(account.balance, 5);
+    assert!(matches!(
+        client.get_account(&address).await,
+        Err(Error::HttpStatus(404, _))
+    ));
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0].method, HttpMethod::Post);
+    assert_eq!(requests[0].url, "http://fullnode:8090/wallet/getnowblock");
+    assert_eq!(requests[0].header("TRON-PRO-API-KEY"), Some("key"));
+    assert_eq!(requests[0].header("X-Custom"), Some("value"));
+    assert_eq!(
+        requests[1].url,
+        "http://solidity:8091/walletsolidity/getaccount"
+    );
+    assert_eq!(
+        requests[1].body.as_ref().unwrap()["address"],
+        address.as_hex()
+    );
+}
+
+#[tokio::test]
+async fn test_mock_transport_retry() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let transport =
+        MockTransport::new().with_handler("/wallet/getnowblock", move |_| {
+            match counter.fetch_add(1, Ordering::SeqCst) {
+                0 => Err(Error::Transport("connection reset".to_owned())),
+                1 => Ok(HttpResponse::new(503, "busy").with_header("Retry-After", "0")),
+                _ => Ok(HttpResponse::json(&block(7))),
+            }
+        });
+    let client = RpcClientBuilder::new("http://fullnode:8090")
+        .unwrap()
+        .with_retry_policy(RetryPolicy::new(3).with_backoff(
+            std::time::Duration::from_millis(1),
+            std::time::Duration::from_millis(1),
+        ))
+        .with_transport(transport)
+        .build();
+    let block = client.get_latest_block().await.unwrap();
+    assert_eq!(block.block_number(), 7);
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_api_key_rotation() {
+    let transport = MockTransport::new().with_handler("/wallet/getnowblock", |req| {
+        match req.header("TRON-PRO-API-KEY") {
+            Some("second") => Ok(HttpResponse::json(&block(7))),
+            _ => Ok(HttpResponse::new(429, "quota exceeded")),
+        }
+    });
+    let client = RpcClientBuilder::new("http://fullnode:8090")
+        .unwrap()
+        .with_api_keys(["first", "second"])
+        .with_retry_policy(RetryPolicy::none())
+        .with_transport(transport.clone())
+        .build();
+    assert_eq!(client.get_latest_block().await.unwrap().block_number(), 7);
+    // rotated key is kept for following requests
+    client.get_latest_block().await.unwrap();
+    let keys: Vec<_> = transport
+        .requests()
+        .iter()
+        .map(|x| x.header("TRON-PRO-API-KEY").unwrap().to_owned())
+        .collect();
+    assert_eq!(keys, ["first", "second", "second"]);
+
+    // quota error is returned when every key is exhausted
+    let client = RpcClientBuilder::new("http://fullnode:8090")
+        .unwrap()
+        .with_api_keys(["first", "third"])
+        .with_retry_policy(RetryPolicy::none())
+        .with_transport(
+            MockTransport::new().with_handler("/wallet/getnowblock", |_| {
+                Ok(HttpResponse::new(429, "quota exceeded"))
+            }),
+        )
+        .build();
+    assert!(matches!(
+        client.get_latest_block().await,
+        Err(Error::HttpStatus(429, _))
+    ));
+}
@@ -0,0 +1,156 @@
+mod common;
+
+use common::{address, client, hex_address, string, transaction, word, CONTRACT, FROM, TO};
+use heliosphere::{Error, HttpResponse, MockTransport, TokenAmount, Trc20Amount, U256};
+use heliosphere_core::Address;
+use serde_json::json;
+
+fn transport() -> MockTransport {
+    MockTransport::new()
+        .with_handler("/wallet/triggerconstantcontract", |req| {
+            let body = req.body.as_ref().unwrap();
+            let result = match body["function_selector"].as_str().unwrap() {
+                "name()" => string("Tether USD"),
+                // bytes32 symbol
+                "symbol()" => format!("{:0<64}", hex::encode("USDT")),
+                "decimals()" => word(6),
+                "totalSupply()" => word(1_000_000_000_000),
+                "balanceOf(address)" => word(12_345_600),
+                "allowance(address,address)" => word(0),
+                // energy estimation of transfer
+                _ => word(1),
+            };
+            Ok(HttpResponse::json(&json!({
+                "result": { "result": true },
+                "constant_result": [result],
+                "energy_used": 30_000,
+            })))
+        })
+        .with_response(
+            "/wallet/getcontractinfo",
+            json!({
+                "smart_contract": {
+                    "origin_address": hex_address(FROM),
+                    "contract_address": hex_address(CONTRACT),
+                    "consume_user_resource_percent": 100,
+                },
+                "contract_state": { "energy_factor": 0 }
+            }),
+        )
+        .with_response(
+            "/wallet/getchainparameters",
+            json!({ "chainParameter": [{ "key": "getEnergyFee", "value": 420 }] }),
+        )
+        .with_handler("/wallet/triggersmartcontract", |_| {
+            Ok(HttpResponse::json(
+                &json!({ "result": { "result": true }, "transaction": transaction() }),
+            ))
+        })
+}
+
+#[test]
+fn test_token_amount() {
+    let amount = TokenAmount::parse("1.5", 6).unwrap();
+    assert_eq!(amount.raw(), U256::from(1_500_000));
+    assert_eq!(amount.to_string(), "1.5");
+    assert_eq!(TokenAmount::parse("42", 0).unwrap().to_string(), "42");
+    assert_eq!(
+        TokenAmount::parse(".000001", 6).unwrap().raw(),
+        U256::from(1)
+    );
+    assert_eq!(
+        TokenAmount::new(U256::from(7), 18).to_string(),
+        "0.000000000000000007"
+    );
+    assert_eq!(TokenAmount::new(U256::ZERO, 6).to_string(), "0");
+    for invalid in ["", ".", "1.0000001", "-1", "1e6", "1,5"] {
+        assert!(matches!(
+            TokenAmount::parse(invalid, 6),
+            Err(Error::InvalidAmount(_))
+        ));
+    }
+}
+
+#[tokio::test]
+async fn test_trc20_views() {
+    let transport = transport();
+    let usdt = client(&transport).trc20(CONTRACT.parse().unwrap());
+    let owner: Address = FROM.parse().unwrap();
+    assert_eq!(usdt.name().await.unwrap(), "Tether USD");
+    assert_eq!(usdt.symbol().await.unwrap(), "USDT");
+    assert_eq!(
+        usdt.balance_of(&owner).await.unwrap().to_string(),
+        "12.3456"
+    );
+    assert_eq!(usdt.total_supply().await.unwrap().decimals(), 6);
+    assert_eq!(
+        usdt.allowance(&owner, &TO.parse().unwrap())
+            .await
+            .unwrap()
+            .raw(),
+        U256::ZERO
+    );
+    // decimals are queried once
+    let decimals_calls = transport
+        .requests()
+        .iter()
+        .filter(|x| x.body.as_ref().unwrap()["function_selector"] == "decimals()")
+        .count();
+    assert_eq!(decimals_calls, 1);
+
+    let balance_call = transport
+        .requests()
+        .into_iter()
+        .find(|x| x.body.as_ref().unwrap()["function_selector"] == "balanceOf(address)")
+        .unwrap();
+    assert_eq!(balance_call.body.unwrap()["parameter"], address(&owner));
+}
+
+#[tokio::test]
+async fn test_trc20_transfer() {
+    let transport = transport();
+    let usdt = client(&transport).trc20(CONTRACT.parse().unwrap());
+    let from: Address = FROM.parse().unwrap();
+    let to: Address = TO.parse().unwrap();
+
+    usdt.transfer(&from, &to, "1.5").build().await.unwrap();
+    let trigger = transport.requests().pop().unwrap().body.unwrap();
+    assert_eq!(trigger["function_selector"], "transfer(address,uint256)");
+    assert_eq!(
+        trigger["parameter"],
+        format!("{}{}", address(&to), word(1_500_000))
+    );
+    // estimated energy * energy fee with default 20% margin
+    assert_eq!(trigger["fee_limit"], 30_000 * 420 * 120 / 100);
+    assert_eq!(trigger["owner_address"], from.as_hex());
+
+    usdt.approve(&from, &to, Trc20Amount::Raw(U256::MAX))
+        .with_fee_limit(100)
+        .build()
+        .await
+        .unwrap();
+    let trigger = transport.requests().pop().unwrap().body.unwrap();
+    assert_eq!(trigger["fee_limit"], 100);
+    assert!(trigger["parameter"]
+        .as_str()
+        .unwrap()
+        .ends_with(&"f".repeat(64)));
+
+    usdt.transfer_from(&to, &from, &to, 1u64)
+        .with_fee_limit(100)
+        .build()
+        .await
+        .unwrap();
+    let trigger = transport.requests().pop().unwrap().body.unwrap();
+    assert_eq!(
+        trigger["function_selector"],
+        "transferFrom(address,address,uint256)"
+    );
+    assert_eq!(trigger["owner_address"], to.as_hex());
+    assert_eq!(trigger["parameter"].as_str().unwrap().len(), 3 * 64);
+
+    assert!(matches!(
+        usdt.transfer(&from, &to, "0.0000001").build().await,
+        Err(Error::InvalidAmount(_))
+    ));
+}
@@ -0,0 +1,252 @@
+use futures::TryStreamExt;
+use heliosphere::{
+    AccountTransaction, Error, HttpResponse, MockTransport, RpcClientBuilder, TronGridFilter, U256,
+};
+use heliosphere_core::Address;
+use serde_json::json;
+use url::Url;
+
+const ACCOUNT: &str = "TB9n2jzcWoqta1xX2Mv8P3y9tyUNsGTFsQ";
+const USDT: &str = "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t";
+const TXID: &str = "04644c93d200adb9010cc6396eb77a327fbdfa81a5e9e27407f84a010169e7c4";
+
+fn query(url: &str, key: &str) -> Option<String> {
+    Url::parse(url)
+        .unwrap()
+        .query_pairs()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.into_owned())
+}
+
+fn transaction(n: u64) -> serde_json::Value {
+    json!({
+        "ret": [{ "contractRet": "SUCCESS", "fee": 0 }],
+        "signature": ["00"],
+        "txID": format!("{:064x}", n),
+        "net_usage": 268,
+        "raw_data_hex": "0a02",
+        "net_fee": 0,
+        "energy_usage": 0,
+        "blockNumber": 50_000_000 + n,
+        "block_timestamp": 1_700_000_000_000u64 + n,
+        "energy_fee": 0,
+        "energy_usage_total": 0,
+        "raw_data": {
+            "contract": [{
+                "parameter": {
+                    "value": { "amount": n, "owner_address": "410d8fb0a5ffb1a15d0f6a8b1a2e1a1d4b4c0e7e0a" },
+                    "type_url": "type.googleapis.com/protocol.TransferContract"
+                },
+                "type": "TransferContract"
+            }],
+            "ref_block_bytes": "1234",
+            "ref_block_hash": "5656565656565656",
+            "expiration": 1_700_000_060_000u64,
+            "timestamp": 1_700_000_000_000u64
+        },
+        "internal_transactions": []
+    })
+}
+
+fn client() -> (heliosphere::TronGridClient, MockTransport) {
+    let transport = MockTransport::new()
+        .with_response(
+            &format!("/v1/accounts/{}", ACCOUNT),
+            json!({
+                "data": [{
+                    "address": "410d8fb0a5ffb1a15d0f6a8b1a2e1a1d4b4c0e7e0a",
+                    "balance": 1_000_000,
+                    "create_time": 1_600_000_000_000u64,
+                    "latest_opration_time": 1_700_000_000_000u64,
+                    "trc20": [{ USDT: "12345678901234567890123" }],
+                    "frozenV2": [{ "amount": 5 }, { "type": "ENERGY" }],
+                }],
+                "success": true,
+                "meta": { "at": 1_700_000_000_000u64, "page_size": 1 }
+            }),
+        )
+        .with_handler(&format!("/v1/accounts/{}/transactions", ACCOUNT), |req| {
+            // three pages: two transactions, internal transaction, empty
+            let body = match query(&req.url, "fingerprint").as_deref() {
+                None => json!({
+                    "data": [transaction(1), transaction(2)],
+                    "success": true,
+                    "meta": { "fingerprint": "page2", "page_size": 2 }
+                }),
+                Some("page2") => json!({
+                    "data": [{
+                        "internal_tx_id": "abcd",
+                        "data": { "note": "63616c6c", "rejected": false, "call_value": { "_": 7 } },
+                        "block_timestamp": 1_700_000_000_000u64,
+                        "to_address": "410d8fb0a5ffb1a15d0f6a8b1a2e1a1d4b4c0e7e0a",
+                        "tx_id": TXID,
+                        "from_address": "41a614f803b6fd780986a42c78ec9c7f77e6ded13c"
+                    }],
+                    "success": true,
+                    "meta": { "fingerprint": "page3", "page_size": 1 }
+                }),
+                Some(_) => json!({ "data": [], "success": true, "meta": { "page_size": 0 } }),
+            };
+            Ok(HttpResponse::json(&body))
+        })
+        .with_handler(
+            &format!("/v1/accounts/{}/transactions/trc20", ACCOUNT),
+            |req| {
+                assert_eq!(query(&req.url, "contract_address").unwrap(), USDT);
+                let next = query(&req.url, "fingerprint").is_none();
+                Ok(HttpResponse::json(&json!({
+                    "data": [{
+                        "transaction_id": TXID,
+                        "token_info": { "symbol": "USDT", "address": USDT, "decimals": 6, "name": "Tether USD" },
+                        "block_timestamp": 1_700_000_000_000u64,
+                        "from": ACCOUNT,
+                        "to": USDT,
+                        "type": "Transfer",
+                        "value": "1000000"
+                    }],
+                    "success": true,
+                    "meta": if next { json!({ "fingerprint": "next" }) } else { json!({}) }
+                })))
+            },
+        )
+        .with_response(
+            &format!("/v1/transactions/{}/events", TXID),
+            json!({
+                "data": [{
+                    "block_number": 50_000_000,
+                    "block_timestamp": 1_700_000_000_000u64,
+                    "caller_contract_address": "",
+                    "contract_address": USDT,
+                    "event_index": 0,
+                    "event_name": "Transfer",
+                    "result": { "0": "0x0d8fb0a5ffb1a15d0f6a8b1a2e1a1d4b4c0e7e0a", "value": "1000000" },
+                    "result_type": { "value": "uint256" },
+                    "event": "Transfer(address indexed from, address indexed to, uint256 value)",
+                    "transaction_id": TXID,
+                    "_unconfirmed": true
+                }],
+                "success": true,
+                "meta": { "at": 1_700_000_000_000u64, "page_size": 1 }
+            }),
+        )
+        .with_response(
+            &format!("/v1/contracts/{}/events", USDT),
+            json!({ "success": false, "error": "invalid event name", "statusCode": 400 }),
+        );
+    let client = RpcClientBuilder::new("https://api.trongrid.io")
+        .unwrap()
+        .with_transport(transport.clone())
+        .build();
+    (client.trongrid(), transport)
+}
+
+#[tokio::test]
+async fn test_trongrid_account() {
+    let (client, transport) = client();
+    let address: Address = ACCOUNT.parse().unwrap();
+    let account = client
+        .get_account(&address, &TronGridFilter::new().only_confirmed())
+        .await
+        .unwrap();
+    assert_eq!(account.account.balance, 1_000_000);
+    assert_eq!(account.account.latest_operation_time, 1_700_000_000_000);
+    assert_eq!(account.trc20.len(), 1);
+    assert_eq!(account.trc20[0].contract, USDT.parse().unwrap());
+    assert_eq!(
+        account.trc20[0].balance,
+        "12345678901234567890123".parse::<U256>().unwrap()
+    );
+    let url = &transport.requests()[0].url;
+    assert_eq!(query(url, "only_confirmed").as_deref(), Some("true"));
+}
+
+#[tokio::test]
+async fn test_trongrid_pagination() {
+    let (client, transport) = client();
+    let address: Address = ACCOUNT.parse().unwrap();
+    let filter = TronGridFilter::new()
+        .min_timestamp(1_600_000_000_000)
+        .limit(2)
+        .ascending();
+    let txs: Vec<_> = client
+        .transactions(&address, filter.clone())
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(txs.len(), 3);
+    match &txs[1] {
+        AccountTransaction::Transaction(tx) => {
+            assert_eq!(tx.block_number, 50_000_002);
+            assert_eq!(tx.net_usage, 268);
+            assert_eq!(
+                tx.transaction.raw_data.contract[0].r#type,
+                "TransferContract"
+            );
+        }
+        _ => panic!("expected transaction"),
+    }
+    match &txs[2] {
+        AccountTransaction::Internal(tx) => {
+            assert_eq!(tx.tx_id.to_string(), TXID);
+            assert_eq!(tx.data.call_value["_"], 7);
+        }
+        _ => panic!("expected internal transaction"),
+    }
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(query(&requests[0].url, "fingerprint"), None);
+    assert_eq!(
+        query(&requests[2].url, "fingerprint").as_deref(),
+        Some("page3")
+    );
+    for req in &requests {
+        assert_eq!(
+            query(&req.url, "min_timestamp").as_deref(),
+            Some("1600000000000")
+        );
+        assert_eq!(query(&req.url, "limit").as_deref(), Some("2"));
+        assert_eq!(
+            query(&req.url, "order_by").as_deref(),
+            Some("block_timestamp,asc")
+        );
+    }
+
+    let page = client
+        .get_transactions_page(&address, &filter, Some("page2"))
+        .await
+        .unwrap();
+    assert_eq!(page.data.len(), 1);
+    assert_eq!(page.fingerprint.as_deref(), Some("page3"));
+}
+
+#[tokio::test]
+async fn test_trongrid_trc20_and_events() {
+    let (client, _) = client();
+    let address: Address = ACCOUNT.parse().unwrap();
+    let usdt: Address = USDT.parse().unwrap();
+    let transfers: Vec<_> = client
+        .trc20_transfers(&address, TronGridFilter::new().contract_address(usdt))
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(transfers.len(), 2);
+    assert_eq!(transfers[0].token_info.decimals, 6);
+    assert_eq!(transfers[0].from, address);
+    assert_eq!(transfers[0].value, U256::from(1_000_000));
+
+    let events = client
+        .get_transaction_events(TXID.parse().unwrap(), &TronGridFilter::new())
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].contract_address, usdt);
+    assert_eq!(events[0].caller_contract_address, None);
+    assert_eq!(events[0].result["value"], "1000000");
+    assert!(events[0].unconfirmed);
+
+    let filter = TronGridFilter::new().event_name("Nope");
+    assert!(matches!(
+        client.get_contract_events_page(&usdt, &filter, None).await,
+        Err(Error::ApiError(msg)) if msg == "invalid event name"
+    ));
+}
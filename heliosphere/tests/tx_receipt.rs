@@ -0,0 +1,88 @@
+use heliosphere::{ContractResult, RevertReason, TransactionReceipt, TransactionResult};
+
+#[test]
+fn test_receipt_deserialize() {
+    let receipt: TransactionReceipt = serde_json::from_str(
+        r#"{
+            "id": "0e0e2a3a3f9de2b1c2c5bb7d0aa7b0a2e7e3e0c1b6b6fd3d3f1cb2b1a0d0c0b0",
+            "fee": 13844850,
+            "blockNumber": 41234567,
+            "blockTimeStamp": 1700000000000,
+            "contractResult": ["0000000000000000000000000000000000000000000000000000000000000001"],
+            "contract_address": "41a614f803b6fd780986a42c78ec9c7f77e6ded13c",
+            "receipt": {"energy_usage_total": 31895, "energy_fee": 13396000, "net_fee": 345000, "result": "SUCCESS"},
+            "log": [{
+                "address": "a614f803b6fd780986a42c78ec9c7f77e6ded13c",
+                "topics": [
+                    "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                    "0000000000000000000000000b53ce4aa6f0c2f3c849f11f682702ec99622e2e",
+                    "000000000000000000000000a614f803b6fd780986a42c78ec9c7f77e6ded13c"
+                ],
+                "data": "00000000000000000000000000000000000000000000000000000000000f4240"
+            }],
+            "internal_transactions": [{
+                "hash": "1e0e2a3a3f9de2b1c2c5bb7d0aa7b0a2e7e3e0c1b6b6fd3d3f1cb2b1a0d0c0b0",
+                "caller_address": "41a614f803b6fd780986a42c78ec9c7f77e6ded13c",
+                "transferTo_address": "410b53ce4aa6f0c2f3c849f11f682702ec99622e2e",
+                "callValueInfo": [{"callValue": 100}],
+                "note": "63616c6c"
+            }]
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(receipt.result, TransactionResult::Success);
+    assert_eq!(
+        receipt.receipt.energy_fee + receipt.receipt.net_fee,
+        13741000
+    );
+    assert_eq!(
+        receipt.logs[0].address.as_base58(),
+        "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t"
+    );
+    assert_eq!(receipt.logs[0].topics.len(), 3);
+    assert_eq!(receipt.contract_result(0).unwrap()[31], 1);
+    assert_eq!(
+        receipt.internal_transactions[0].note.as_deref(),
+        Some("call")
+    );
+
+    let failed: TransactionReceipt = serde_json::from_str(
+        r#"{
+            "id": "0e0e2a3a3f9de2b1c2c5bb7d0aa7b0a2e7e3e0c1b6b6fd3d3f1cb2b1a0d0c0b0",
+            "result": "FAILED",
+            "resMessage": "5245564552542f6f70636f6465",
+            "contractResult": ["08c379a00000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000001245524332303a206c6f772062616c616e63650000000000000000000000000000"],
+            "receipt": {"result": "REVERT"}
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(failed.result, TransactionResult::Failed);
+    assert_eq!(failed.res_message.as_deref(), Some("REVERT/opcode"));
+    assert_eq!(failed.receipt.result, ContractResult::Revert);
+    assert!(!failed.is_success());
+    assert_eq!(
+        failed.revert_reason(),
+        Some(RevertReason::Error("ERC20: low balance".to_owned()))
+    );
+}
+
+#[test]
+fn test_revert_reason_decode() {
+    let panic =
+        hex::decode("4e487b710000000000000000000000000000000000000000000000000000000000000011")
+            .unwrap();
+    assert_eq!(
+        RevertReason::decode(&panic),
+        Some(RevertReason::Panic(0x11))
+    );
+    let custom = hex::decode("e450d38c0000").unwrap();
+    assert_eq!(
+        RevertReason::decode(&custom),
+        Some(RevertReason::Custom([0xe4, 0x50, 0xd3, 0x8c], vec![0, 0]))
+    );
+    assert_eq!(RevertReason::decode(&[]), None);
+    let result: ContractResult = serde_json::from_str("\"OUT_OF_ENERGY\"").unwrap();
+    assert_eq!(result, ContractResult::OutOfEnergy);
+    let result: ContractResult = serde_json::from_str("\"SOMETHING_NEW\"").unwrap();
+    assert_eq!(result, ContractResult::Unknown);
+}